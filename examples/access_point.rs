@@ -0,0 +1,37 @@
+//! Create, activate, and tear down a Proton access point.
+//! 
+//! This example must be executed with root permissions.
+
+use std::time::Duration;
+
+use proton::{
+    ap::{
+        AccessPoint,
+        HotspotConfig,
+    },
+    error::ProtonResult,
+};
+
+#[tokio::main]
+async fn main() -> ProtonResult<()> {
+    // Configure the hotspot, starting from the defaults
+    let config = HotspotConfig {
+        ssid: "Proton".to_string(),
+        pass: "correct horse battery staple".to_string(),
+        ..Default::default()
+    };
+
+    // Create the access point (this also activates it)
+    let ap = AccessPoint::new("wlan0", config).await?;
+
+    // Print the payload of a QR code that guests can scan to join
+    println!("{}", ap.config.wifi_qr_payload());
+
+    // Keep the hotspot up for a minute
+    tokio::time::sleep(Duration::from_secs(60)).await;
+
+    // Tear down the hotspot
+    ap.shutdown().await?;
+
+    Ok (())
+}
@@ -0,0 +1,42 @@
+//! Deauthenticate a device from a Proton access point by MAC address.
+//! 
+//! This example must be executed with root permissions.
+
+use proton::{
+    ap::{
+        AccessPoint,
+        HotspotConfig,
+    },
+    device::ReasonCode,
+    error::ProtonResult,
+    mac::MacAddr,
+};
+
+#[tokio::main]
+async fn main() -> ProtonResult<()> {
+    // Configure the hotspot
+    let config: HotspotConfig = (
+        "Proton".to_string(),         // SSID
+        "password".to_string(),       // Password
+        "192.168.0.0/24".to_string(), // CIDR range
+        "192.168.0.1".to_string(),    // Gateway
+        "wpa-psk".to_string(),        // Security
+        "2.4".to_string(),            // Band
+    ).try_into()?;
+
+    // Create the access point on the wireless interface
+    let mut ap = AccessPoint::new("wlan0", config).await?;
+
+    // Deauthenticate a single device
+    let mac: MacAddr = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into();
+    ap.deauth(mac, ReasonCode::Unspecified).await?;
+
+    // Deauthenticate everyone else, since the AP is going down
+    let (count, failed) = ap.deauth_all(ReasonCode::Leaving).await?;
+    println!("Deauthenticated {} devices", count);
+    for mac in failed {
+        println!("Could not deauthenticate {}", mac);
+    }
+
+    Ok (())
+}
@@ -0,0 +1,34 @@
+//! List all devices connected to a Proton access point.
+//! 
+//! This example must be executed with root permissions.
+
+use proton::{
+    ap::{
+        AccessPoint,
+        HotspotConfig,
+    },
+    error::ProtonResult,
+};
+
+#[tokio::main]
+async fn main() -> ProtonResult<()> {
+    // Configure the hotspot
+    let config: HotspotConfig = (
+        "Proton".to_string(),         // SSID
+        "password".to_string(),       // Password
+        "192.168.0.0/24".to_string(), // CIDR range
+        "192.168.0.1".to_string(),    // Gateway
+        "wpa-psk".to_string(),        // Security
+        "2.4".to_string(),            // Band
+    ).try_into()?;
+
+    // Create the access point on the wireless interface
+    let mut ap = AccessPoint::new("wlan0", config).await?;
+
+    // Scan the network for connected devices
+    for device in ap.scan().await? {
+        println!("{:#?}", device);
+    }
+
+    Ok (())
+}
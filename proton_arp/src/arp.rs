@@ -1,17 +1,41 @@
 //! Address resolution manager.
 
-use std::net::Ipv4Addr;
+use std::{
+    net::Ipv4Addr,
+    time::Duration,
+};
 
 use cidr::Ipv4Cidr;
 
-use proton_err::ProtonResult;
+use pnet::datalink::Config;
+
+use proton_err::{
+    ProtonError,
+    ProtonResult,
+};
 
 use proton_mac::MacAddr;
 
+use proton_nif::NetworkInterface;
+
 use crate::{
     ArpCache,
     ArpCacheIterator,
-    scan,
+    ArpMode,
+    build_request_frame,
+    merge_rounds,
+    parse_arp_reply,
+    scan::{
+        host_addresses,
+        scan_partial,
+    },
+    scan_cidr_with_options,
+    scan_with_options,
+    ScanOptions,
+    ScanReport,
+    ARP_READ_TIMEOUT,
+    ARP_ROUND_GAP,
+    DEFAULT_ARP_REFRESH_TIME,
 };
 
 /// An address resolution manager that provides ARP caching
@@ -25,6 +49,9 @@ pub struct ArpManager {
 
     /// The name of the network interface to be scanned.
     ifname: String,
+
+    /// The options of each scan.
+    options: ScanOptions,
 }
 
 impl ArpManager {
@@ -41,10 +68,36 @@ impl ArpManager {
             range,
             cache: ArpCache::new(),
             ifname: ifname.to_string(),
+            options: ScanOptions::default(),
         }
     }
 
-    /// Scan the network and refresh the ARP cache.
+    /// Set the options of each scan.
+    /// 
+    /// # Parameters
+    /// - `options` (`ScanOptions`): the new scan options
+    /// 
+    /// # Returns
+    /// None.
+    pub fn set_scan_options(&mut self, options: ScanOptions) {
+        self.options = options;
+    }
+
+    /// Set the CIDR range of the network.
+    /// 
+    /// The ARP cache is cleared, since its entries belong to the previous network.
+    /// 
+    /// # Parameters
+    /// - `range` (`Ipv4Cidr`): the new CIDR range of the network
+    /// 
+    /// # Returns
+    /// None.
+    pub fn set_range(&mut self, range: Ipv4Cidr) {
+        self.range = range;
+        self.cache = ArpCache::new();
+    }
+
+    /// Scan the network and replace the ARP cache with the replies.
     /// 
     /// # Parameters
     /// None.
@@ -52,24 +105,239 @@ impl ArpManager {
     /// # Returns
     /// A `ProtonResult<()>` indicating the status of the result.
     pub async fn scan(&mut self) -> ProtonResult<()> {
-        let mut addresses = Vec::new();
-
-        // Assemble list of addresses to be scanned
-        for ipv4 in self.range.iter().addresses() {
-            // Skip network address and broadcast address
-            if ipv4 != self.range.first_address()
-                && ipv4 != self.range.last_address()
-            {
-                addresses.push(ipv4);
-            }
+        // Scan the network and update the cache
+        self.cache.set(scan_cidr_with_options(self.range, &self.ifname, self.options).await?);
+
+        Ok (())
+    }
+
+    /// Scan the network several times and replace the ARP cache with the
+    /// union of the replies.
+    /// 
+    /// Hosts that are briefly busy may miss a single scan, so each round
+    /// gives them another chance to reply.  Rounds are `ARP_ROUND_GAP` apart,
+    /// and the freshest reply for each address wins.
+    /// 
+    /// # Parameters
+    /// - `rounds` (`usize`): the number of scans (at least 1)
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating the status of the result.  If any
+    /// round fails, the cache is left unchanged.
+    pub async fn scan_rounds(&mut self, rounds: usize) -> ProtonResult<()> {
+        let (range, options) = (self.range, self.options);
+        let ifname = self.ifname.as_str();
+
+        self.cache = merge_rounds(rounds, ARP_ROUND_GAP, || scan_cidr_with_options(range, ifname, options)).await?;
+
+        Ok (())
+    }
+
+    /// Scan the network and merge the replies into the ARP cache.
+    /// 
+    /// Unlike `ArpManager::scan`, hosts that miss a single scan stay cached
+    /// until they are older than `DEFAULT_ARP_REFRESH_TIME`.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating the status of the result.
+    pub async fn scan_merge(&mut self) -> ProtonResult<()> {
+        // Scan the network and merge the replies into the cache
+        self.cache.merge(scan_cidr_with_options(self.range, &self.ifname, self.options).await?);
+
+        // Age out hosts that have not replied recently
+        self.cache.evict_stale(DEFAULT_ARP_REFRESH_TIME);
+
+        Ok (())
+    }
+
+    /// Scan only the addresses of the network without a fresh cache entry.
+    /// 
+    /// Addresses that are not cached, or whose entry is older than
+    /// `DEFAULT_ARP_REFRESH_TIME`, are probed and the replies are merged into
+    /// the cache.  Stale entries that did not reply are evicted.  On a
+    /// mostly static network, this sends far fewer requests than
+    /// `ArpManager::scan`, which remains available for a complete refresh.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<usize>` containing the number of addresses probed,
+    /// if the scan was successful.
+    pub async fn scan_incremental(&mut self) -> ProtonResult<usize> {
+        let targets = self.cache.uncached(&host_addresses(self.range), DEFAULT_ARP_REFRESH_TIME);
+        let count = targets.len();
+
+        // Skip the scan, and its listener delay, if every host is fresh
+        if count > 0 {
+            self.cache.merge(scan_with_options(targets, &self.ifname, self.options).await?);
         }
 
-        // Scan the network and update the cache
-        self.cache.set(scan(addresses, &self.ifname).await?);
+        // Age out hosts that have not replied recently
+        self.cache.evict_stale(DEFAULT_ARP_REFRESH_TIME);
+
+        Ok (count)
+    }
+
+    /// Scan the network, replace the ARP cache with the replies, and report
+    /// the outcome of every probed address.
+    /// 
+    /// Unlike `ArpManager::scan`, this distinguishes hosts that did not reply
+    /// (`ProbeStatus::Silent`) from addresses that were never probed (absent
+    /// from the report), and flags addresses that more than one device
+    /// claimed (`ProbeStatus::Conflict`).
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<ScanReport>` containing the outcome of every host
+    /// address of the network, if the scan was successful.
+    pub async fn scan_report(&mut self) -> ProtonResult<ScanReport> {
+        let probed = host_addresses(self.range);
+
+        // Scan the network
+        let replies = scan_with_options(probed.clone(), &self.ifname, self.options).await?;
+
+        // Match the replies against the probed addresses
+        let report = ScanReport::new(&probed, &replies);
+        self.cache.set(replies);
+
+        Ok (report)
+    }
+
+    /// Resolve the IPv4 address of a single MAC address.
+    /// 
+    /// ARP cannot address a request by MAC, so this broadcasts a request for
+    /// every host of the network and matches the replies against the MAC
+    /// address.  All replies are merged into the cache.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address to resolve
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Option<Ipv4Addr>>` containing the IPv4 address of the
+    /// device, if it replied.
+    pub async fn resolve_mac(&mut self, mac: MacAddr) -> ProtonResult<Option<Ipv4Addr>> {
+        // Scan the network and merge the replies into the cache
+        self.cache.merge(scan_cidr_with_options(self.range, &self.ifname, self.options).await?);
+
+        Ok (self.lookup_mac(mac))
+    }
+
+    /// Scan the network and refresh the ARP cache, giving up after `timeout`.
+    /// 
+    /// The ARP reply listener only waits for replies for the time left after
+    /// sending the requests (see `ScanOptions::timeout`), so the cache is
+    /// replaced with the replies received by then.  If the requests cannot
+    /// all be sent in time, the replies received so far are merged into the
+    /// cache instead, since hosts that were never probed may still be
+    /// connected.
+    /// 
+    /// # Parameters
+    /// - `timeout` (`Duration`): the maximum duration of the scan
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating the status of the result, or
+    /// `ProtonError::ScanTimedOut` if the requests could not all be sent in time.
+    pub async fn scan_with_timeout(&mut self, timeout: Duration) -> ProtonResult<()> {
+        let options = ScanOptions {
+            timeout: Some (timeout),
+            ..self.options
+        };
+
+        let (entries, complete) = scan_partial(host_addresses(self.range), &self.ifname, options).await?;
+
+        // Keep the partial replies, but report the timeout
+        if !complete {
+            self.cache.merge(entries);
+            return Err (ProtonError::ScanTimedOut);
+        }
+
+        self.cache.set(entries);
 
         Ok (())
     }
 
+    /// Broadcast a gratuitous ARP announcement of an IPv4 address.
+    /// 
+    /// The announcement is an ARP request whose sender and target protocol
+    /// addresses are both `ipv4`, which makes other hosts on the network
+    /// update their ARP caches.  A host that replies to it is using the
+    /// same IPv4 address, so the first host claiming `ipv4` is awaited for
+    /// at most the listener delay of the scan options.
+    /// 
+    /// # Parameters
+    /// - `ipv4` (`Ipv4Addr`): the IPv4 address to announce
+    /// - `mac` (`MacAddr`): the MAC address that owns `ipv4`
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Option<MacAddr>>` containing the MAC address of
+    /// another device using `ipv4`, if one replied.
+    pub async fn announce(&self, ipv4: Ipv4Addr, mac: MacAddr) -> ProtonResult<Option<MacAddr>> {
+        let mut interface = self.open_interface()?;
+
+        // Wait for another host claiming the address
+        let eth_frame = build_request_frame(mac, ipv4, ipv4, ArpMode::Request);
+        let is_conflict = |frame: &[u8]| parse_arp_reply(frame, mac)
+            .is_some_and(|entry| entry.ipv4 == ipv4);
+        let reply = interface.send_and_wait_reply(&eth_frame, self.options.listener_delay, is_conflict).await;
+
+        Ok (reply
+            .and_then(|frame| parse_arp_reply(&frame, mac))
+            .map(|entry| entry.mac))
+    }
+
+    /// Probe whether an IPv4 address is already in use on the network.
+    /// 
+    /// The probe is an ARP request whose sender protocol address is
+    /// `0.0.0.0` (see `ArpMode::Probe`), so it can be sent before claiming
+    /// `ipv4` without polluting other hosts' ARP caches.  The first reply
+    /// from a host claiming `ipv4` is awaited for at most the listener delay
+    /// of the scan options.
+    /// 
+    /// # Parameters
+    /// - `ipv4` (`Ipv4Addr`): the IPv4 address to probe
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Option<MacAddr>>` containing the MAC address of the
+    /// device using `ipv4`, if one replied, or
+    /// `ProtonError::InterfaceNotReady` if the interface has no MAC address.
+    pub async fn probe(&self, ipv4: Ipv4Addr) -> ProtonResult<Option<MacAddr>> {
+        let mut interface = self.open_interface()?;
+        let our_mac: MacAddr = interface.mac
+            .ok_or(ProtonError::InterfaceNotReady (self.ifname.clone()))?
+            .into();
+
+        // Wait for a host claiming the address
+        let eth_frame = build_request_frame(our_mac, Ipv4Addr::UNSPECIFIED, ipv4, ArpMode::Probe);
+        let is_reply = |frame: &[u8]| parse_arp_reply(frame, our_mac)
+            .is_some_and(|entry| entry.ipv4 == ipv4);
+        let reply = interface.send_and_wait_reply(&eth_frame, self.options.listener_delay, is_reply).await;
+
+        Ok (reply
+            .and_then(|frame| parse_arp_reply(&frame, our_mac))
+            .map(|entry| entry.mac))
+    }
+
+    /// Open a channel on the network interface for a single request/reply
+    /// exchange.
+    /// 
+    /// The channel has a read timeout (`ARP_READ_TIMEOUT`), so waiting for a
+    /// reply gives up at most one read timeout late, and no read is left
+    /// running afterwards.
+    fn open_interface(&self) -> ProtonResult<NetworkInterface> {
+        let config = Config {
+            read_timeout: Some (ARP_READ_TIMEOUT),
+            ..Config::default()
+        };
+
+        NetworkInterface::open_with_config(&self.ifname, config)
+    }
+
     /// Get an iterator of the cache, without consuming the cache.
     /// 
     /// # Parameters
@@ -89,12 +357,21 @@ impl ArpManager {
     /// # Returns
     /// An `Option<Ipv4Addr>` containing to the provided MAC address, if available.
     pub fn lookup_mac(&self, mac: MacAddr) -> Option<Ipv4Addr> {
-        for entry in self.cache() {
-            if entry.mac == mac {
-                return Some (entry.ipv4);
-            }
-        }
+        self.cache.iter()
+            .find(|entry| entry.mac == mac)
+            .map(|entry| entry.ipv4)
+    }
 
-        None
+    /// Look up an IPv4 address, returning its corresponding MAC if available.
+    /// 
+    /// # Parameters
+    /// - `ipv4` (`Ipv4Addr`): the IPv4 address to look up
+    /// 
+    /// # Returns
+    /// An `Option<MacAddr>` containing to the provided IPv4 address, if available.
+    pub fn lookup_ip(&self, ipv4: Ipv4Addr) -> Option<MacAddr> {
+        self.cache.iter()
+            .find(|entry| entry.ipv4 == ipv4)
+            .map(|entry| entry.mac)
     }
 }
\ No newline at end of file
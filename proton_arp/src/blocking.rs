@@ -0,0 +1,85 @@
+//! Blocking facade over the asynchronous ARP scan.
+//! 
+//! These functions run the scan on a runtime of their own, so they can be
+//! called from a plain `fn main()`.  Each call builds a multi-threaded
+//! runtime, spawning `BLOCKING_WORKER_THREADS` (2) worker threads that are
+//! shut down when the scan completes.  They must **not** be called from
+//! within an existing Tokio runtime, which panics.
+
+use std::{
+    future::Future,
+    net::Ipv4Addr,
+};
+
+use cidr::Ipv4Cidr;
+
+use tokio::runtime::Builder;
+
+use proton_err::{
+    ProtonError,
+    ProtonResult,
+};
+
+use proton_mac::MacAddr;
+
+use crate::{
+    scan,
+    scan_cidr,
+    ScanResult,
+};
+
+/// Number of worker threads of the blocking runtime.
+/// 
+/// The ARP reply listener may be parked in a blocking read, so the requester
+/// needs a worker of its own.
+const BLOCKING_WORKER_THREADS: usize = 2;
+
+/// Scan all host addresses of a CIDR range and return all ARP replies, blocking
+/// the current thread until the scan completes.
+/// 
+/// # Parameters
+/// - `range` (`Ipv4Cidr`): the CIDR range to scan
+/// - `ifname` (`&str`): the name of the wireless interface to scan
+/// 
+/// # Returns
+/// A `ScanResult` containing the ARP responses
+/// received, if the scan was successful.
+pub fn scan_blocking(range: Ipv4Cidr, ifname: &str) -> ScanResult {
+    block_on(scan_cidr(range, ifname))
+}
+
+/// Resolve the MAC address of a single IPv4 address, blocking the current
+/// thread until the scan completes.
+/// 
+/// # Parameters
+/// - `ipv4` (`Ipv4Addr`): the IPv4 address to resolve
+/// - `ifname` (`&str`): the name of the wireless interface to scan
+/// 
+/// # Returns
+/// A `ProtonResult<MacAddr>` containing the MAC address of the host, or
+/// `ProtonError::UnknownHost` if it did not reply.
+pub fn resolve_blocking(ipv4: Ipv4Addr, ifname: &str) -> ProtonResult<MacAddr> {
+    block_on(scan(vec![ipv4], ifname))?
+        .into_iter()
+        .find(|entry| entry.ipv4 == ipv4)
+        .map(|entry| entry.mac)
+        .ok_or(ProtonError::UnknownHost (ipv4))
+}
+
+/// Run a scan to completion on a new runtime.
+fn block_on<F>(scan: F) -> ScanResult
+    where F: Future<Output = ScanResult>
+{
+    let runtime = Builder::new_multi_thread()
+        .worker_threads(BLOCKING_WORKER_THREADS)
+        .enable_all()
+        .build()?;
+
+    let result = runtime.block_on(scan);
+
+    // Note: the listener may still be parked in a blocking read,
+    //  so do not wait for it to be aborted
+    runtime.shutdown_background();
+
+    result
+}
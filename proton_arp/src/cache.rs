@@ -1,16 +1,35 @@
 //! ARP caching utilities.
 
 use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    fs,
+    slice::Iter,
     vec::IntoIter,
     net::Ipv4Addr,
+    path::Path,
     time::{
         Duration,
         Instant,
+        SystemTime,
     },
 };
 
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+
+use proton_err::ProtonResult;
+
 use proton_mac::MacAddr;
 
+use crate::DEFAULT_ARP_REFRESH_TIME;
+
 #[derive(Clone)]
 /// An address resolution cache.
 /// 
@@ -62,6 +81,122 @@ impl ArpCache {
     pub fn set(&mut self, cache: Vec<ArpCacheEntry>) {
         self.cache = cache;
     }
+
+    /// Merge new entries into the ARP cache.
+    /// 
+    /// An entry for an IPv4 address that is already cached replaces the
+    /// cached entry if it is at least as fresh, refreshing its timestamp.
+    /// Entries for new addresses are added.  Cached entries without a new
+    /// entry are kept.
+    /// 
+    /// # Parameters
+    /// - `entries` (`Vec<ArpCacheEntry>`): the new entries
+    /// 
+    /// # Returns
+    /// None.
+    pub fn merge(&mut self, entries: Vec<ArpCacheEntry>) {
+        // Index the position of the first cached entry of each address
+        let mut positions: HashMap<Ipv4Addr, usize> = HashMap::with_capacity(self.cache.len());
+        for (position, cached) in self.cache.iter().enumerate() {
+            positions.entry(cached.ipv4).or_insert(position);
+        }
+
+        for entry in entries {
+            match positions.get(&entry.ipv4) {
+                Some (&position) => if entry.created >= self.cache[position].created {
+                    self.cache[position] = entry;
+                },
+                None => {
+                    positions.insert(entry.ipv4, self.cache.len());
+                    self.cache.push(entry);
+                },
+            }
+        }
+    }
+
+    /// Remove all entries that need to be refreshed.
+    /// 
+    /// # Parameters
+    /// - `refresh` (`Duration`): the maximum age of an entry
+    /// 
+    /// # Returns
+    /// A `usize` containing the number of entries removed.
+    pub fn evict_stale(&mut self, refresh: Duration) -> usize {
+        let count = self.cache.len();
+        self.cache.retain(|entry| !entry.check(refresh));
+
+        count - self.cache.len()
+    }
+
+    /// Select the addresses that are not cached, or whose entry needs to be
+    /// refreshed.
+    /// 
+    /// # Parameters
+    /// - `addresses` (`&[Ipv4Addr]`): the candidate IPv4 addresses
+    /// - `refresh` (`Duration`): the maximum age of an entry
+    /// 
+    /// # Returns
+    /// A `Vec<Ipv4Addr>` containing each candidate address without a fresh
+    /// entry, in the order of `addresses`.
+    pub fn uncached(&self, addresses: &[Ipv4Addr], refresh: Duration) -> Vec<Ipv4Addr> {
+        let fresh: HashSet<Ipv4Addr> = self.cache.iter()
+            .filter(|entry| !entry.check(refresh))
+            .map(|entry| entry.ipv4)
+            .collect();
+
+        addresses.iter()
+            .filter(|ipv4| !fresh.contains(ipv4))
+            .copied()
+            .collect()
+    }
+
+    /// Get a borrowing iterator over the ARP cache.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Iter<'_, ArpCacheEntry>` over all cache entries.
+    pub fn iter(&self) -> Iter<'_, ArpCacheEntry> {
+        self.cache.iter()
+    }
+
+    /// Save the ARP cache to a file as JSON.
+    /// 
+    /// # Parameters
+    /// - `path` (`&Path`): the path of the file to write
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the cache was saved.
+    pub fn save(&self, path: &Path) -> ProtonResult<()> {
+        let json = serde_json::to_string(&self.cache)?;
+        fs::write(path, json)?;
+
+        Ok (())
+    }
+
+    /// Load an ARP cache from a JSON file written by `ArpCache::save`.
+    /// 
+    /// Entries older than `DEFAULT_ARP_REFRESH_TIME`, or older than the
+    /// monotonic clock can represent (e.g. from before a reboot), are
+    /// dropped as stale.
+    /// 
+    /// # Parameters
+    /// - `path` (`&Path`): the path of the file to read
+    /// 
+    /// # Returns
+    /// A `ProtonResult<ArpCache>` containing the fresh entries of the
+    /// saved cache, if the file could be read.
+    pub fn load(path: &Path) -> ProtonResult<Self> {
+        let json = fs::read_to_string(path)?;
+        let entries: Vec<ArpCacheEntry> = serde_json::from_str(&json)?;
+
+        Ok (Self {
+            cache: entries.into_iter()
+                .filter(|entry| !entry.check(DEFAULT_ARP_REFRESH_TIME))
+                .collect(),
+        })
+    }
 }
 
 impl IntoIterator for ArpCache {
@@ -102,7 +237,7 @@ impl Iterator for ArpCacheIterator {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone, Copy)]
 /// An address resolution cache entry.
 /// 
 /// An ARP cache entry stores a device's IPv4 and MAC addresses as well as the time
@@ -115,7 +250,14 @@ pub struct ArpCacheEntry {
     pub mac: MacAddr,
 
     /// The time that this entry was created.
-    created: Instant,
+    /// 
+    /// This is a monotonic time, so that the age of an entry is not affected
+    /// by changes to the system clock.  It is saved as a wall-clock time so
+    /// that entries remain meaningful across restarts.  It is `None` if the
+    /// saved time is older than the monotonic clock can represent, in which
+    /// case the entry is always stale.
+    #[serde(with = "wall_clock")]
+    created: Option<Instant>,
 }
 
 impl ArpCacheEntry {
@@ -131,22 +273,71 @@ impl ArpCacheEntry {
         Self {
             ipv4,
             mac,
-            created: Instant::now(),
+            created: Some (Instant::now()),
         }
     }
 
+    /// Get the wall-clock time at which this entry was created.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Option<SystemTime>` containing the creation time of this entry, or
+    /// `None` if it is older than the monotonic clock can represent.
+    pub fn created(&self) -> Option<SystemTime> {
+        self.created.map(wall_clock::to_system_time)
+    }
+
     /// Check if this entry needs to be refreshed (as of call time).
     /// 
     /// # Parameters
     /// - `refresh` (`Duration`): the amount of time after which this entry
-    /// should be refreshed.
+    ///   should be refreshed.
     /// 
     /// # Returns
     /// A `bool` indicating whether or not this entry should be refreshed.
     pub fn check(&self, refresh: Duration) -> bool {
         // Check the time
-        let now = Instant::now();
+        self.created.is_none_or(|created| created.elapsed() >= refresh)
+    }
+}
+
+/// Conversion of the monotonic creation time of an ARP cache entry to and
+/// from the wall-clock time that is saved.
+mod wall_clock {
+    use super::*;
+
+    /// Convert a monotonic time to a wall-clock time, as of call time.
+    /// 
+    /// # Parameters
+    /// - `instant` (`Instant`): the monotonic time
+    /// 
+    /// # Returns
+    /// A `SystemTime` containing the corresponding wall-clock time.
+    pub fn to_system_time(instant: Instant) -> SystemTime {
+        SystemTime::now() - instant.elapsed()
+    }
+
+    /// Serialize a monotonic time as a wall-clock time.
+    pub fn serialize<S: Serializer>(instant: &Option<Instant>, serializer: S) -> Result<S::Ok, S::Error> {
+        instant.map(to_system_time).serialize(serializer)
+    }
+
+    /// Deserialize a wall-clock time as a monotonic time.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Instant>, D::Error> {
+        let Some (time) = Option::<SystemTime>::deserialize(deserializer)? else {
+            return Ok (None);
+        };
+
+        // Note: a time from the future (the clock was set back) is treated
+        //  as now, and a time older than the monotonic clock can represent
+        //  (e.g. from before a reboot) is left out, so the entry is stale
+        //  regardless of how long ago the system booted.
+        let age = SystemTime::now()
+            .duration_since(time)
+            .unwrap_or_default();
 
-        now - self.created >= refresh
+        Ok (Instant::now().checked_sub(age))
     }
 }
\ No newline at end of file
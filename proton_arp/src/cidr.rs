@@ -0,0 +1,68 @@
+//! IPv4 CIDR range helpers.
+
+use std::net::Ipv4Addr;
+
+use cidr::Ipv4Cidr;
+
+/// Extra address helpers for `Ipv4Cidr`.
+pub trait Ipv4CidrExt {
+    /// Get the network address of this range.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Ipv4Addr` containing the first address of the range.
+    fn network(&self) -> Ipv4Addr;
+
+    /// Get the broadcast address of this range.
+    /// 
+    /// Point-to-point `/31` ranges (RFC 3021) and single-host `/32` ranges
+    /// have no broadcast address, since every address is a host.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Option<Ipv4Addr>` containing the last address of the range, if
+    /// the range has a broadcast address.
+    fn broadcast(&self) -> Option<Ipv4Addr>;
+
+    /// List the host addresses of this range.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `Vec<Ipv4Addr>` containing every address of the range except the
+    /// network and broadcast addresses (or every address of a `/31` or
+    /// `/32` range), in ascending order.
+    fn hosts(&self) -> Vec<Ipv4Addr>;
+}
+
+impl Ipv4CidrExt for Ipv4Cidr {
+    fn network(&self) -> Ipv4Addr {
+        self.first_address()
+    }
+
+    fn broadcast(&self) -> Option<Ipv4Addr> {
+        if self.network_length() >= 31 {
+            None
+        } else {
+            Some (self.last_address())
+        }
+    }
+
+    fn hosts(&self) -> Vec<Ipv4Addr> {
+        // Every address is a host if there is no broadcast address
+        let Some (broadcast) = self.broadcast() else {
+            return self.iter().addresses().collect();
+        };
+        let network = self.network();
+
+        self.iter()
+            .addresses()
+            .filter(|ipv4| *ipv4 != network && *ipv4 != broadcast)
+            .collect()
+    }
+}
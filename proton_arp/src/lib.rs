@@ -4,20 +4,63 @@
 #![deny(missing_docs)]
 
 mod arp;
+#[cfg(feature = "blocking")]
+mod blocking;
 mod cache;
+mod cidr;
+mod ndp;
+mod report;
 mod scan;
 
 use std::time::Duration;
 
 pub use arp::ArpManager;
 
+#[cfg(feature = "blocking")]
+pub use blocking::{
+    resolve_blocking,
+    scan_blocking,
+};
+
 pub use cache::{
     ArpCache,
     ArpCacheEntry,
     ArpCacheIterator,
 };
 
-pub use scan::scan;
+pub use cidr::Ipv4CidrExt;
+
+pub use ndp::{
+    NdpEntry,
+    parse_neighbors,
+    scan_ipv6,
+};
+
+pub use report::{
+    ProbeStatus,
+    ScanReport,
+};
+
+pub use scan::{
+    ARP_CHANNEL_BUFFER_SIZE,
+    ARP_LISTENER_DELAY,
+    ARP_READ_TIMEOUT,
+    ArpMode,
+    build_request_frame,
+    merge_rounds,
+    parse_arp_reply,
+    scan,
+    scan_cidr,
+    scan_cidr_with_options,
+    scan_interface,
+    scan_interface_partial,
+    scan_with_options,
+    ScanOptions,
+    ScanResult,
+};
 
 /// The default ARP cache entry refresh time (2 minutes).
-pub static DEFAULT_ARP_REFRESH_TIME: Duration = Duration::from_secs(120);
\ No newline at end of file
+pub static DEFAULT_ARP_REFRESH_TIME: Duration = Duration::from_secs(120);
+
+/// The default delay between the rounds of `ArpManager::scan_rounds` (500 milliseconds).
+pub static ARP_ROUND_GAP: Duration = Duration::from_millis(500);
@@ -0,0 +1,99 @@
+//! IPv6 neighbor discovery scanning utility.
+//! 
+//! IPv6 prefixes are far too large to probe address by address, so this scan
+//! reads the neighbor cache of the kernel, which neighbor discovery fills in
+//! as hosts on the link talk to the interface.  Hosts usually have both a
+//! link-local and a global address, so both kinds of address are discovered.
+
+mod neigh;
+
+use std::net::Ipv6Addr;
+
+use cidr::Ipv6Cidr;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use pnet::datalink::interfaces;
+
+use proton_err::{
+    ProtonError,
+    ProtonResult,
+};
+
+use proton_mac::MacAddr;
+
+use neigh::read_neighbors;
+
+pub use neigh::parse_neighbors;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+/// A neighbor discovered by an IPv6 scan.
+pub struct NdpEntry {
+    /// The IPv6 address of the neighbor.
+    pub ipv6: Ipv6Addr,
+
+    /// The MAC address of the neighbor.
+    pub mac: MacAddr,
+}
+
+impl NdpEntry {
+    /// Check if the address of this neighbor is link-local (`fe80::/10`).
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the address is link-local.
+    pub fn is_link_local(&self) -> bool {
+        is_link_local(&self.ipv6)
+    }
+}
+
+/// Check if an IPv6 address is link-local (`fe80::/10`).
+fn is_link_local(ipv6: &Ipv6Addr) -> bool {
+    ipv6.segments()[0] & 0xffc0 == 0xfe80
+}
+
+/// Discover the IPv6 neighbors of a network interface.
+/// 
+/// Link-local neighbors are always returned; other neighbors are returned
+/// only if their address is within `prefix`.  Use `NdpEntry::is_link_local`
+/// to tell them apart.  A neighbor may appear once per address.
+/// 
+/// **Note**: this reads the neighbor cache of the kernel over Netlink, so
+/// only neighbors that recently exchanged traffic with the interface are
+/// found.
+/// 
+/// # Parameters
+/// - `prefix` (`Ipv6Cidr`): the global prefix of the network
+/// - `ifname` (`&str`): the name of the network interface to scan
+/// 
+/// # Returns
+/// A `ProtonResult<Vec<NdpEntry>>` containing the neighbors discovered, if
+/// the interface exists and its neighbor cache could be read.
+pub async fn scan_ipv6(prefix: Ipv6Cidr, ifname: &str) -> ProtonResult<Vec<NdpEntry>> {
+    let ifindex = interfaces()
+        .into_iter()
+        .find(|iface| iface.name == ifname)
+        .ok_or(ProtonError::CouldNotFindInterface (ifname.to_string()))?
+        .index;
+
+    // Read the neighbor cache of the interface, off the async workers
+    let neighbors = tokio::task::spawn_blocking(move || read_neighbors(ifindex)).await??;
+
+    // Construct a list of distinct entries within the scope of the scan
+    let mut entries: Vec<NdpEntry> = Vec::new();
+
+    for entry in neighbors {
+        let in_scope = entry.is_link_local() || prefix.contains(&entry.ipv6);
+
+        if in_scope && !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+
+    Ok (entries)
+}
@@ -0,0 +1,164 @@
+//! Kernel neighbor table utilities.
+//! 
+//! The neighbor table is dumped over a `NETLINK_ROUTE` socket with an
+//! `RTM_GETNEIGH` request, to which the kernel answers with one
+//! `RTM_NEWNEIGH` message per neighbor, followed by `NLMSG_DONE`.
+
+use std::net::Ipv6Addr;
+
+use neli::{
+    consts::{
+        Nda,
+        NlFamily,
+        NlmF,
+        Nlmsg,
+        RtAddrFamily,
+        Rtm,
+        Rtn,
+    },
+    err::Nlmsgerr,
+    nl::Nlmsghdr,
+    rtnl::{
+        Ndmsg,
+        Rtattrs,
+    },
+    socket::NlSocket,
+    Nl,
+    StreamReadBuffer,
+    MAX_NL_LENGTH,
+};
+
+use proton_err::{
+    ProtonError,
+    ProtonResult,
+};
+
+use proton_mac::MacAddr;
+
+use super::NdpEntry;
+
+/// Read the IPv6 neighbor table of a network interface.
+/// 
+/// **Note**: this blocks until the kernel has sent the whole table, which it
+/// does right away.
+/// 
+/// # Parameters
+/// - `ifindex` (`u32`): the index of the network interface
+/// 
+/// # Returns
+/// A `ProtonResult<Vec<NdpEntry>>` containing each resolved neighbor, if the
+/// neighbor table could be read.
+pub(crate) fn read_neighbors(ifindex: u32) -> ProtonResult<Vec<NdpEntry>> {
+    let mut socket = NlSocket::connect(NlFamily::Route, None, None, true)?;
+
+    // Request a dump of the IPv6 neighbor table, without filters
+    let request = Ndmsg {
+        ndm_family: RtAddrFamily::Inet6,
+        ndm_index: 0,
+        ndm_state: Vec::new(),
+        ndm_flags: Vec::new(),
+        ndm_type: Rtn::Unspec,
+        rtattrs: Rtattrs::empty(),
+    };
+
+    socket.send_nl(Nlmsghdr::new(
+        None,
+        Rtm::Getneigh,
+        vec![NlmF::Request, NlmF::Dump],
+        None,
+        None,
+        request,
+    ))?;
+
+    let mut entries = Vec::new();
+    let mut datagram = vec![0; MAX_NL_LENGTH];
+
+    loop {
+        let len = socket.recv(&mut datagram, 0)?;
+
+        if len == 0 {
+            return Err (ProtonError::NoResponseFromNetlink);
+        }
+
+        if parse_neighbors(&datagram[..len], ifindex, &mut entries)? {
+            return Ok (entries);
+        }
+    }
+}
+
+/// Decode the payload of a Netlink message.
+fn decode<P: Nl>(payload: &[u8]) -> ProtonResult<P> {
+    let mut buffer = StreamReadBuffer::new(payload);
+    buffer.set_size_hint(payload.len());
+
+    Ok (P::deserialize(&mut buffer)?)
+}
+
+/// Parse the Netlink messages of an IPv6 neighbor table dump.
+/// 
+/// Neighbors of other interfaces and neighbors without a link-layer address
+/// (e.g. `INCOMPLETE` or `FAILED` entries, whose resolution is pending or
+/// failed) are skipped.
+/// 
+/// # Parameters
+/// - `messages` (`&[u8]`): the Netlink messages received, in native byte order
+/// - `ifindex` (`u32`): the index of the network interface
+/// - `entries` (`&mut Vec<NdpEntry>`): the resolved neighbors found so far,
+///   which those in `messages` are appended to, in order
+/// 
+/// # Returns
+/// A `ProtonResult<bool>` indicating whether or not the dump is complete
+/// (i.e. `messages` ended with `NLMSG_DONE`), if the messages were well-formed
+/// and the kernel did not answer with an error.
+pub fn parse_neighbors(messages: &[u8], ifindex: u32, entries: &mut Vec<NdpEntry>) -> ProtonResult<bool> {
+    let mut buffer = StreamReadBuffer::new(messages);
+    let mut read = 0;
+
+    while !buffer.at_end() {
+        let message = Nlmsghdr::<u16, Vec<u8>>::deserialize(&mut buffer)?;
+
+        // Note: neli pads the payload of a truncated message with zeros, so
+        //  its length is checked against the bytes received
+        if read + message.nl_len as usize > messages.len() {
+            return Err (ProtonError::Other (format!("malformed Netlink message of {} bytes", message.nl_len)));
+        }
+        read += message.asize();
+
+        match Nlmsg::from(message.nl_type) {
+            Nlmsg::Done => return Ok (true),
+            Nlmsg::Error => {
+                // An error message without an error is an acknowledgement
+                let error = decode::<Nlmsgerr<Rtm>>(&message.nl_payload)?.error;
+
+                if error != 0 {
+                    return Err (ProtonError::from_errno(error));
+                }
+            },
+            _ if Rtm::from(message.nl_type) == Rtm::Newneigh => {
+                if let Some (entry) = parse_neighbor(&decode(&message.nl_payload)?, ifindex) {
+                    entries.push(entry);
+                }
+            },
+            _ => (),
+        }
+    }
+
+    Ok (false)
+}
+
+/// Parse a neighbor of an `RTM_NEWNEIGH` message.
+fn parse_neighbor(neighbor: &Ndmsg, ifindex: u32) -> Option<NdpEntry> {
+    if neighbor.ndm_family != RtAddrFamily::Inet6 || u32::try_from(neighbor.ndm_index).ok()? != ifindex {
+        return None;
+    }
+
+    // The kernel only sends the link-layer address of a resolved neighbor
+    let attr = |nda: Nda| neighbor.rtattrs.iter()
+        .find(|attr| attr.rta_type == nda)
+        .map(|attr| attr.rta_payload.as_slice());
+
+    Some (NdpEntry {
+        ipv6: <[u8; 16]>::try_from(attr(Nda::Dst)?).ok().map(Ipv6Addr::from)?,
+        mac: <[u8; 6]>::try_from(attr(Nda::Lladdr)?).ok().map(MacAddr::from)?,
+    })
+}
@@ -0,0 +1,145 @@
+//! Structured results of an ARP scan.
+
+use std::{
+    net::Ipv4Addr,
+    slice::Iter,
+};
+
+use serde::Serialize;
+
+use proton_mac::MacAddr;
+
+use crate::ArpCacheEntry;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+/// The outcome of probing a single IPv4 address.
+pub enum ProbeStatus {
+    /// A single device replied.
+    Replied (MacAddr),
+
+    /// No device replied.
+    Silent,
+
+    /// More than one device replied, indicating an address conflict.
+    Conflict (Vec<MacAddr>),
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+/// A complete report of an ARP scan, with the outcome of every probed address.
+pub struct ScanReport {
+    /// Each probed IPv4 address and its outcome, in probe order.
+    probes: Vec<(Ipv4Addr, ProbeStatus)>,
+}
+
+impl ScanReport {
+    /// Construct a report by matching replies against the probed addresses.
+    /// 
+    /// Replies from addresses that were not probed are ignored, and repeated
+    /// replies from the same device are counted once.
+    /// 
+    /// # Parameters
+    /// - `probed` (`&[Ipv4Addr]`): the IPv4 addresses that were probed
+    /// - `replies` (`&[ArpCacheEntry]`): the ARP replies that were received
+    /// 
+    /// # Returns
+    /// A new `ScanReport` with one probe per probed address.
+    pub fn new(probed: &[Ipv4Addr], replies: &[ArpCacheEntry]) -> Self {
+        let probes = probed.iter()
+            .map(|ipv4| {
+                // Collect the distinct devices that replied for this address
+                let mut macs: Vec<MacAddr> = Vec::new();
+                for reply in replies.iter().filter(|reply| reply.ipv4 == *ipv4) {
+                    if !macs.contains(&reply.mac) {
+                        macs.push(reply.mac);
+                    }
+                }
+
+                let status = match macs.len() {
+                    0 => ProbeStatus::Silent,
+                    1 => ProbeStatus::Replied (macs[0]),
+                    _ => ProbeStatus::Conflict (macs),
+                };
+
+                (*ipv4, status)
+            })
+            .collect();
+
+        Self {
+            probes,
+        }
+    }
+
+    /// Get the outcome of probing an IPv4 address.
+    /// 
+    /// # Parameters
+    /// - `ipv4` (`Ipv4Addr`): the IPv4 address to look up
+    /// 
+    /// # Returns
+    /// An `Option<&ProbeStatus>` containing the outcome, if the address was probed.
+    pub fn status(&self, ipv4: Ipv4Addr) -> Option<&ProbeStatus> {
+        self.probes.iter()
+            .find(|(probed, _)| *probed == ipv4)
+            .map(|(_, status)| status)
+    }
+
+    /// Get the addresses that no device replied for.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `Vec<Ipv4Addr>` containing each silent address.
+    pub fn silent(&self) -> Vec<Ipv4Addr> {
+        self.probes.iter()
+            .filter(|(_, status)| *status == ProbeStatus::Silent)
+            .map(|(ipv4, _)| *ipv4)
+            .collect()
+    }
+
+    /// Get the addresses that more than one device replied for.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `Vec<Ipv4Addr>` containing each conflicting address.
+    pub fn conflicts(&self) -> Vec<Ipv4Addr> {
+        self.probes.iter()
+            .filter(|(_, status)| matches!(status, ProbeStatus::Conflict (_)))
+            .map(|(ipv4, _)| *ipv4)
+            .collect()
+    }
+
+    /// Get an iterator over every probed address and its outcome.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An iterator over each probed IPv4 address and its outcome, in probe order.
+    pub fn iter(&self) -> Iter<'_, (Ipv4Addr, ProbeStatus)> {
+        self.probes.iter()
+    }
+
+    /// Get the number of probed addresses.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `usize` containing the number of probed addresses.
+    pub fn len(&self) -> usize {
+        self.probes.len()
+    }
+
+    /// Check if no addresses were probed.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the report is empty.
+    pub fn is_empty(&self) -> bool {
+        self.probes.is_empty()
+    }
+}
@@ -4,55 +4,266 @@ mod reply;
 mod request;
 
 use std::{
+    future::Future,
     net::Ipv4Addr,
     time::Duration,
 };
 
+use cidr::Ipv4Cidr;
+
+use pnet::datalink::Config;
+
 use tokio::{
     sync::mpsc,
-    task,
+    task::{
+        self,
+        AbortHandle,
+    },
+    time::{
+        self,
+        Instant,
+    },
 };
 
-use proton_err::ProtonResult;
+use proton_err::{
+    ProtonError,
+    ProtonResult,
+};
 
 use proton_nif::NetworkInterface;
 
-use crate::ArpCacheEntry;
+use crate::{
+    ArpCache,
+    ArpCacheEntry,
+    Ipv4CidrExt,
+};
 
 use reply::listen;
+pub use reply::parse_arp_reply;
 use request::request;
+pub use request::{
+    ArpMode,
+    build_request_frame,
+};
 
-/// Buffer size for the asynchronous communication channel for ARP replies.
+/// Default buffer size for the asynchronous communication channel for ARP replies.
 pub const ARP_CHANNEL_BUFFER_SIZE: usize = 256;
 
 /// Default delay to wait before closing the ARP reply listener.
 pub static ARP_LISTENER_DELAY: Duration = Duration::from_millis(2_500);
 
+/// Read timeout of the channel opened for a scan.
+/// 
+/// The listener checks whether the scan is over (or was cancelled) between
+/// reads, so this bounds how long it holds the receiver afterwards.
+pub const ARP_READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The result of an ARP scan: the ARP cache entries of every host that replied.
+pub type ScanResult = ProtonResult<Vec<ArpCacheEntry>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Options of an ARP scan.
+pub struct ScanOptions {
+    /// Time to wait for replies after the last request.
+    pub listener_delay: Duration,
+
+    /// Number of replies that the reply channel can hold.
+    /// 
+    /// Replies are collected once the requests are done, so a scan keeps at
+    /// most this many replies; a reply that arrives when the channel is full
+    /// stalls the listener until the scan ends, and is then dropped.  Scans
+    /// of large ranges (e.g. a `/16`) should raise this at the cost of about
+    /// `size_of::<ArpCacheEntry>()` bytes per reply.
+    pub buffer_size: usize,
+
+    /// Maximum duration of the whole scan, if any.
+    /// 
+    /// The listener only waits for replies until the scan has run for this
+    /// long, so a timeout shorter than `listener_delay` still returns the
+    /// replies received so far.  If the requests cannot all be sent in time,
+    /// the scan fails with `ProtonError::ScanTimedOut`.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            listener_delay: ARP_LISTENER_DELAY,
+            buffer_size: ARP_CHANNEL_BUFFER_SIZE,
+            timeout: None,
+        }
+    }
+}
+
+/// A guard that aborts the tasks of a scan when it is dropped.
+/// 
+/// This ensures that, if the future returned by `scan` is dropped before it
+/// completes, its listener and requester tasks do not keep running and holding
+/// the network interface.  Aborting a task that has already completed has no
+/// effect.  Aborting cannot interrupt a blocking read, so the listener also
+/// stops by itself within one read timeout (see `ARP_READ_TIMEOUT`) once the
+/// requester is gone.
+pub(crate) struct AbortOnDrop (pub(crate) Vec<AbortHandle>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        for handle in &self.0 {
+            handle.abort();
+        }
+    }
+}
+
 /// Scan the provided list of IPv4 addresses and return all ARP replies.
 /// 
+/// If the returned future is dropped before completion, the spawned listener
+/// and requester tasks are aborted at their next suspension point.
+/// 
+/// # Parameters
+/// - `ips` (`Vec<Ipv4Addr>`): the IPv4 addresses to scan
+/// - `ifname` (`&str`): the name of the wireless interface to scan
+/// 
+/// # Returns
+/// A `ScanResult` containing the ARP responses
+/// received, if the scan was successful.
+pub async fn scan(ips: Vec<Ipv4Addr>, ifname: &str) -> ScanResult {
+    scan_with_options(ips, ifname, ScanOptions::default()).await
+}
+
+/// Scan the provided list of IPv4 addresses with the provided options and
+/// return all ARP replies.
+/// 
 /// # Parameters
 /// - `ips` (`Vec<Ipv4Addr>`): the IPv4 addresses to scan
 /// - `ifname` (`&str`): the name of the wireless interface to scan
+/// - `options` (`ScanOptions`): the options of the scan
 /// 
 /// # Returns
-/// A `ProtonResult<Vec<ArpCacheEntry>>` containing the ARP responses
+/// A `ScanResult` containing the ARP responses
 /// received, if the scan was successful.
-pub async fn scan(ips: Vec<Ipv4Addr>, ifname: &str) -> ProtonResult<Vec<ArpCacheEntry>> {
+pub async fn scan_with_options(
+    ips: Vec<Ipv4Addr>,
+    ifname: &str,
+    options: ScanOptions,
+) -> ScanResult {
     // Get the wireless network interface
-    let interface = NetworkInterface::new(ifname)?;
+    // Note: the read timeout lets the listener exit without waiting for
+    //  another frame once the scan is over
+    let config = Config {
+        read_timeout: Some (ARP_READ_TIMEOUT),
+        ..Config::default()
+    };
+    let interface = NetworkInterface::open_with_config(ifname, config)?;
+
+    scan_interface(interface, ips, options).await
+}
+
+/// Scan the provided list of IPv4 addresses with the provided options and
+/// return all ARP replies, even if the scan timed out.
+/// 
+/// # Parameters
+/// - `ips` (`Vec<Ipv4Addr>`): the IPv4 addresses to scan
+/// - `ifname` (`&str`): the name of the wireless interface to scan
+/// - `options` (`ScanOptions`): the options of the scan
+/// 
+/// # Returns
+/// A `ProtonResult<(Vec<ArpCacheEntry>, bool)>` containing the ARP
+/// responses received, and whether or not every request was sent before
+/// the timeout (see `scan_interface_partial`).
+pub(crate) async fn scan_partial(
+    ips: Vec<Ipv4Addr>,
+    ifname: &str,
+    options: ScanOptions,
+) -> ProtonResult<(Vec<ArpCacheEntry>, bool)> {
+    let config = Config {
+        read_timeout: Some (ARP_READ_TIMEOUT),
+        ..Config::default()
+    };
+    let interface = NetworkInterface::open_with_config(ifname, config)?;
+
+    scan_interface_partial(interface, ips, options).await
+}
+
+/// Scan the provided list of IPv4 addresses on an open network interface
+/// and return all ARP replies.
+/// 
+/// The interface should have been opened with a read timeout (e.g.
+/// `ARP_READ_TIMEOUT`).  Otherwise, the listener holds the receiver of the
+/// interface after the scan (or after the scan is dropped) until the next
+/// frame arrives.  If the returned future is dropped before completion, the
+/// spawned listener and requester tasks are aborted.
+/// 
+/// # Parameters
+/// - `interface` (`NetworkInterface`): the network interface to scan
+/// - `ips` (`Vec<Ipv4Addr>`): the IPv4 addresses to scan
+/// - `options` (`ScanOptions`): the options of the scan
+/// 
+/// # Returns
+/// A `ScanResult` containing the ARP responses received, if the scan was
+/// successful.  If the interface has no MAC or IPv4 address, this returns
+/// `ProtonError::InterfaceNotReady`.
+pub async fn scan_interface(
+    interface: NetworkInterface,
+    ips: Vec<Ipv4Addr>,
+    options: ScanOptions,
+) -> ScanResult {
+    let (entries, complete) = scan_interface_partial(interface, ips, options).await?;
+
+    // Do not pass off a partial scan as a complete one
+    if !complete {
+        return Err (ProtonError::ScanTimedOut);
+    }
+
+    Ok (entries)
+}
+
+/// Scan the provided list of IPv4 addresses on an open network interface
+/// and return all ARP replies, even if the scan timed out.
+/// 
+/// Unlike `scan_interface`, the replies received before `options.timeout`
+/// are returned even if the requests could not all be sent in time, so
+/// that a caller can keep them (e.g. merge them into an `ArpCache`) before
+/// reporting the timeout.
+/// 
+/// # Parameters
+/// - `interface` (`NetworkInterface`): the network interface to scan
+/// - `ips` (`Vec<Ipv4Addr>`): the IPv4 addresses to scan
+/// - `options` (`ScanOptions`): the options of the scan
+/// 
+/// # Returns
+/// A `ProtonResult<(Vec<ArpCacheEntry>, bool)>` containing the ARP
+/// responses received, and whether or not every request was sent before
+/// the timeout.  If the interface has no MAC or IPv4 address, this returns
+/// `ProtonError::InterfaceNotReady`.
+pub async fn scan_interface_partial(
+    interface: NetworkInterface,
+    ips: Vec<Ipv4Addr>,
+    options: ScanOptions,
+) -> ProtonResult<(Vec<ArpCacheEntry>, bool)> {
+    // The requests and replies need the addresses of the interface
+    let (Some (mac), Some (ipv4)) = (interface.mac, interface.ipv4) else {
+        return Err (ProtonError::InterfaceNotReady (interface.name));
+    };
+
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
 
     // Create an asynchronous communication channel for received replies
-    let (reply_tx, reply_rx) = mpsc::channel::<ArpCacheEntry>(ARP_CHANNEL_BUFFER_SIZE);
+    // Note: a channel must have room for at least one reply
+    let (reply_tx, reply_rx) = mpsc::channel::<ArpCacheEntry>(options.buffer_size.max(1));
 
     // Begin listening for ARP replies
-    let rx_task = task::spawn(listen(interface.clone(), reply_tx));
+    // Note: the listener and requester share one channel, which is intended,
+    // since the listener only receives and the requester only sends
+    let rx_task = task::spawn(listen(interface.clone(), mac.into(), reply_tx));
 
     // Begin making ARP requests
-    let tx_task = task::spawn(request(interface, ips, reply_rx));
+    let tx_task = task::spawn(request(interface, mac, ipv4, ips, reply_rx, options.listener_delay, deadline));
+
+    // Abort both tasks if this future is dropped
+    let _guard = AbortOnDrop (vec![rx_task.abort_handle(), tx_task.abort_handle()]);
 
     // Await the transmitter
     // After completing it will pass back the async channel receiver
-    let mut reply_rx = tx_task.await?;
+    let (mut reply_rx, complete) = tx_task.await?;
 
     // Await the listener
     rx_task.await?;
@@ -65,5 +276,85 @@ pub async fn scan(ips: Vec<Ipv4Addr>, ifname: &str) -> ProtonResult<Vec<ArpCache
         entries.push(entry)
     }
 
-    Ok (entries)
-}
\ No newline at end of file
+    Ok ((entries, complete))
+}
+
+/// Scan all host addresses of a CIDR range and return all ARP replies.
+/// 
+/// The network address and the broadcast address of the range are skipped.
+/// 
+/// # Parameters
+/// - `range` (`Ipv4Cidr`): the CIDR range to scan
+/// - `ifname` (`&str`): the name of the wireless interface to scan
+/// 
+/// # Returns
+/// A `ScanResult` containing the ARP responses
+/// received, if the scan was successful.
+pub async fn scan_cidr(range: Ipv4Cidr, ifname: &str) -> ScanResult {
+    scan_cidr_with_options(range, ifname, ScanOptions::default()).await
+}
+
+/// Scan all host addresses of a CIDR range with the provided options and
+/// return all ARP replies.
+/// 
+/// The network address and the broadcast address of the range are skipped.
+/// 
+/// # Parameters
+/// - `range` (`Ipv4Cidr`): the CIDR range to scan
+/// - `ifname` (`&str`): the name of the wireless interface to scan
+/// - `options` (`ScanOptions`): the options of the scan
+/// 
+/// # Returns
+/// A `ScanResult` containing the ARP responses
+/// received, if the scan was successful.
+pub async fn scan_cidr_with_options(
+    range: Ipv4Cidr,
+    ifname: &str,
+    options: ScanOptions,
+) -> ScanResult {
+    scan_with_options(host_addresses(range), ifname, options).await
+}
+
+/// Run several rounds of a scan and merge the replies.
+/// 
+/// Rounds are `gap` apart, and the freshest reply for each address wins
+/// (see `ArpCache::merge`).
+/// 
+/// # Parameters
+/// - `rounds` (`usize`): the number of rounds (at least 1)
+/// - `gap` (`Duration`): the delay between two rounds
+/// - `scan` (`impl FnMut() -> impl Future<Output = ScanResult>`): runs a
+///   single round
+/// 
+/// # Returns
+/// A `ProtonResult<ArpCache>` containing the union of the replies, if
+/// every round was successful.
+pub async fn merge_rounds<F, Fut>(
+    rounds: usize,
+    gap: Duration,
+    mut scan: F,
+) -> ProtonResult<ArpCache>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ScanResult>,
+{
+    let mut replies = ArpCache::new();
+
+    for round in 0..rounds.max(1) {
+        if round > 0 {
+            time::sleep(gap).await;
+        }
+
+        replies.merge(scan().await?);
+    }
+
+    Ok (replies)
+}
+
+/// List all host addresses of a CIDR range.
+/// 
+/// The network address and the broadcast address of the range are skipped
+/// (see `Ipv4CidrExt::hosts`).
+pub(crate) fn host_addresses(range: Ipv4Cidr) -> Vec<Ipv4Addr> {
+    range.hosts()
+}
@@ -11,28 +11,36 @@ use pnet::{
     },
 };
 
-use tokio::sync::mpsc;
+use tokio::{
+    sync::mpsc,
+    task,
+};
+
+use proton_mac::MacAddr;
 
 use proton_nif::NetworkInterface;
 
 use crate::ArpCacheEntry;
 
 /// Receive a series of ARP replies.
-///
+/// 
+/// The listener stops once the channel closes.  If the receiver of the
+/// interface has a read timeout, this is noticed within one read timeout,
+/// even if no frames arrive; otherwise, it is only noticed on the next frame.
+/// 
 /// # Parameters
 /// - `interface` (`NetworkInterface`): the network interface to use
+/// - `mac` (`MacAddr`): the MAC address of the interface
 /// - `tx` (`Sender<ArpCacheEntry>`): the cache entry transmitter
 /// 
 /// # Returns
 /// None.
 pub async fn listen(
     mut interface: NetworkInterface,
+    mac: MacAddr,
     tx: mpsc::Sender<ArpCacheEntry>,
 ) {
-    // Get interface MAC address
-    let mac = interface.mac.unwrap();
-
-    while let Some (packet) = interface.recv().await {
+    loop {
         // Check if the MPSC channel has closed
         // There's no point in continuing if it is because
         //  all future packets will be dropped anyways
@@ -40,37 +48,23 @@ pub async fn listen(
             break;
         }
 
-        // Convert to ETH Frame
-        let eth_frame = if let Some (f) = EthernetPacket::new(&packet) {
-            f
-        } else {
-            continue;
+        let packet = match interface.poll_recv().await {
+            Ok (Some (packet)) => packet,
+            Ok (None) => {
+                // Let the runtime cancel this task between reads
+                task::yield_now().await;
+                continue;
+            },
+            Err (_) => break,
         };
 
-        // Check ETH Frame Type
-        let frame_type = eth_frame.get_ethertype();
-        if frame_type != EtherTypes::Arp {
-            continue;
-        }
-
-        // Convert to ARP Packet
-        let arp_packet = if let Some (a) = ArpPacket::new(eth_frame.payload()) {
-            a
+        // Construct cache entry
+        let entry = if let Some (e) = parse_arp_reply(&packet, mac) {
+            e
         } else {
             continue;
         };
 
-        // Drop the frame if it was sent from our own computer
-        if arp_packet.get_sender_hw_addr() == mac {
-            continue;
-        }
-
-        // Construct cache entry
-        let entry = ArpCacheEntry::new(
-            arp_packet.get_sender_proto_addr(),
-            arp_packet.get_sender_hw_addr().into(),
-        );
-
         // Send the reply
         let send = tx.send(entry);
 
@@ -79,7 +73,41 @@ pub async fn listen(
         if send.await.is_err() {
             break;
         }
+    }
+}
 
-        // If there are no packets left, the function returns
+/// Parse an Ethernet frame into an ARP cache entry.
+/// 
+/// # Parameters
+/// - `frame` (`&[u8]`): the received Ethernet frame
+/// - `our_mac` (`MacAddr`): the MAC address of the receiving interface
+/// 
+/// # Returns
+/// An `Option<ArpCacheEntry>` containing the sender of the ARP packet, or
+/// `None` if the frame is malformed, is not an ARP packet, or was sent from
+/// the receiving interface.
+pub fn parse_arp_reply(frame: &[u8], our_mac: MacAddr) -> Option<ArpCacheEntry> {
+    // Convert to ETH Frame
+    let eth_frame = EthernetPacket::new(frame)?;
+
+    // Check ETH Frame Type
+    let frame_type = eth_frame.get_ethertype();
+    if frame_type != EtherTypes::Arp {
+        return None;
+    }
+
+    // Convert to ARP Packet
+    let arp_packet = ArpPacket::new(eth_frame.payload())?;
+
+    // Drop the frame if it was sent from our own computer
+    let sender_mac: MacAddr = arp_packet.get_sender_hw_addr().into();
+    if sender_mac == our_mac {
+        return None;
     }
-}
\ No newline at end of file
+
+    // Construct cache entry
+    Some (ArpCacheEntry::new(
+        arp_packet.get_sender_proto_addr(),
+        sender_mac,
+    ))
+}
@@ -1,6 +1,9 @@
 //! ARP Request functionality.
 
-use std::net::Ipv4Addr;
+use std::{
+    net::Ipv4Addr,
+    time::Duration,
+};
 
 use pnet::{
     datalink::MacAddr,
@@ -15,69 +18,149 @@ use pnet::{
             MutableEthernetPacket,
         },
         MutablePacket,
+        Packet,
     },
 };
 
 use tokio::{
     sync::mpsc,
-    time,
+    time::{
+        self,
+        Instant,
+    },
 };
 
 use proton_nif::NetworkInterface;
 
 use crate::ArpCacheEntry;
 
-use super::ARP_LISTENER_DELAY;
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// The kind of ARP request to construct.
+pub enum ArpMode {
+    #[default]
+    /// A normal request, whose sender protocol address is our IPv4 address.
+    Request,
+
+    /// A probe for duplicate address detection (RFC 5227), whose sender
+    /// protocol address is `0.0.0.0` so that other hosts do not update their
+    /// ARP caches.
+    Probe,
+}
 
 /// Make a series of ARP requests to the provided IPv4 addresses.
 /// 
+/// If a deadline is provided, no requests are sent after it, and the
+/// listener only waits for replies until it.
+/// 
 /// # Parameters
 /// - `interface` (`NetworkInterface`): the network interface to use
+/// - `interface_mac` (`MacAddr`): the MAC address of the interface
+/// - `interface_ipv4` (`Ipv4Addr`): the IPv4 address of the interface
 /// - `ips` (`Ipv4Addr`): the IPv4 addresses to scan
 /// - `rx` (`Receiver<ArpCacheEntry>`): the cache entry receiver, to be closed after transmission
+/// - `listener_delay` (`Duration`): the time to wait for replies before closing the receiver
+/// - `deadline` (`Option<Instant>`): the time by which the receiver must be closed
 /// 
 /// # Returns
-/// `Receiver<ArpCacheEntry>` to be read after channel closure.
+/// `Receiver<ArpCacheEntry>` to be read after channel closure, and a `bool`
+/// indicating whether or not every request was sent before the deadline.
 pub async fn request(
     mut interface: NetworkInterface,
+    interface_mac: MacAddr,
+    interface_ipv4: Ipv4Addr,
     ips: Vec<Ipv4Addr>,
     mut rx: mpsc::Receiver<ArpCacheEntry>,
-) -> mpsc::Receiver<ArpCacheEntry> {
-    // Get MAC address of interface
-    let interface_mac = interface.mac.unwrap();
-
-    // Get IPv4 address of interface
-    let interface_ipv4 = interface.ipv4.unwrap();
+    listener_delay: Duration,
+    deadline: Option<Instant>,
+) -> (mpsc::Receiver<ArpCacheEntry>, bool) {
+    let mut complete = true;
 
     // Iterate over IPv4 addresses
     for ipv4 in ips {
-        // Construct ETH frame
-        let mut eth_frame = MutableEthernetPacket::owned(vec![0u8; 48]).unwrap();
-        eth_frame.set_ethertype(EtherTypes::Arp);
-        eth_frame.set_source(interface_mac);
-        eth_frame.set_destination(MacAddr::broadcast());
-
-        // Construct ARP Packet
-        let mut arp_packet = MutableArpPacket::new(eth_frame.payload_mut()).unwrap();
-        arp_packet.set_hardware_type(ArpHardwareTypes::Ethernet);
-        arp_packet.set_protocol_type(EtherTypes::Ipv4);
-        arp_packet.set_hw_addr_len(6);
-        arp_packet.set_proto_addr_len(4);
-        arp_packet.set_operation(ArpOperations::Request);
-        arp_packet.set_sender_hw_addr(interface_mac);
-        arp_packet.set_sender_proto_addr(interface_ipv4);
-        arp_packet.set_target_hw_addr(MacAddr::zero());
-        arp_packet.set_target_proto_addr(ipv4);
+        // Stop sending once the deadline has passed
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            complete = false;
+            break;
+        }
+
+        let eth_frame = request_frame(interface_mac, interface_ipv4, ipv4, ArpMode::Request);
 
         interface.send(eth_frame.to_immutable()).await;
     }
 
-    // Wait
-    time::sleep(ARP_LISTENER_DELAY).await;
+    // Wait, but never past the deadline
+    let listen_until = Instant::now() + listener_delay;
+    time::sleep_until(deadline.map_or(listen_until, |deadline| deadline.min(listen_until))).await;
 
     // Close the channel
     // This stops the receiver
     rx.close();
 
-    rx
-}
\ No newline at end of file
+    (rx, complete)
+}
+
+/// Construct the bytes of a broadcast ARP request frame.
+/// 
+/// # Parameters
+/// - `sender_mac` (`proton_mac::MacAddr`): the MAC address of the sender
+/// - `sender_ipv4` (`Ipv4Addr`): the IPv4 address of the sender (ignored
+///   in `ArpMode::Probe`)
+/// - `target_ipv4` (`Ipv4Addr`): the IPv4 address to resolve
+/// - `mode` (`ArpMode`): the kind of request
+/// 
+/// # Returns
+/// A `Vec<u8>` containing the Ethernet frame.
+pub fn build_request_frame(
+    sender_mac: proton_mac::MacAddr,
+    sender_ipv4: Ipv4Addr,
+    target_ipv4: Ipv4Addr,
+    mode: ArpMode,
+) -> Vec<u8> {
+    request_frame(sender_mac.into(), sender_ipv4, target_ipv4, mode)
+        .packet()
+        .to_vec()
+}
+
+/// Construct a broadcast ARP request frame.
+/// 
+/// # Parameters
+/// - `sender_mac` (`MacAddr`): the MAC address of the sender
+/// - `sender_ipv4` (`Ipv4Addr`): the IPv4 address of the sender (ignored
+///   in `ArpMode::Probe`)
+/// - `target_ipv4` (`Ipv4Addr`): the IPv4 address to resolve
+/// - `mode` (`ArpMode`): the kind of request
+/// 
+/// # Returns
+/// A `MutableEthernetPacket` containing the ARP request.
+fn request_frame(
+    sender_mac: MacAddr,
+    sender_ipv4: Ipv4Addr,
+    target_ipv4: Ipv4Addr,
+    mode: ArpMode,
+) -> MutableEthernetPacket<'static> {
+    // Probes must not claim an IPv4 address
+    let sender_ipv4 = match mode {
+        ArpMode::Request => sender_ipv4,
+        ArpMode::Probe => Ipv4Addr::UNSPECIFIED,
+    };
+
+    // Construct ETH frame
+    let mut eth_frame = MutableEthernetPacket::owned(vec![0u8; 48]).unwrap();
+    eth_frame.set_ethertype(EtherTypes::Arp);
+    eth_frame.set_source(sender_mac);
+    eth_frame.set_destination(MacAddr::broadcast());
+
+    // Construct ARP Packet
+    let mut arp_packet = MutableArpPacket::new(eth_frame.payload_mut()).unwrap();
+    arp_packet.set_hardware_type(ArpHardwareTypes::Ethernet);
+    arp_packet.set_protocol_type(EtherTypes::Ipv4);
+    arp_packet.set_hw_addr_len(6);
+    arp_packet.set_proto_addr_len(4);
+    arp_packet.set_operation(ArpOperations::Request);
+    arp_packet.set_sender_hw_addr(sender_mac);
+    arp_packet.set_sender_proto_addr(sender_ipv4);
+    arp_packet.set_target_hw_addr(MacAddr::zero());
+    arp_packet.set_target_proto_addr(target_ipv4);
+
+    eth_frame
+}
@@ -0,0 +1,191 @@
+//! Testing the ARP cache implementation.
+
+use std::net::Ipv4Addr;
+
+use proton_arp::{
+    ArpCache,
+    ArpCacheEntry,
+    DEFAULT_ARP_REFRESH_TIME,
+};
+
+use proton_mac::MacAddr;
+
+#[test]
+fn iter_borrows_cache() {
+    // Construct a cache with two entries
+    let mut cache = ArpCache::new();
+    cache.add(Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into());
+    cache.add(Ipv4Addr::new(192, 168, 0, 3), [0x12, 0x34, 0x56, 0x78, 0x90, 0xac].into());
+
+    // Iterate without consuming the cache
+    let ips = cache.iter()
+        .map(|entry| entry.ipv4)
+        .collect::<Vec<Ipv4Addr>>();
+
+    assert_eq!(ips, vec![Ipv4Addr::new(192, 168, 0, 2), Ipv4Addr::new(192, 168, 0, 3)]);
+
+    // The cache can still be consumed afterwards
+    let macs = cache.into_iter()
+        .map(|entry| entry.mac)
+        .collect::<Vec<MacAddr>>();
+
+    assert_eq!(macs.len(), 2);
+}
+
+#[test]
+fn save_and_load_cache() {
+    // Construct a cache with one entry
+    let mut cache = ArpCache::new();
+    cache.add(Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into());
+
+    // Save the cache and load it back
+    let path = std::env::temp_dir().join("proton_arp_save_and_load_cache.json");
+    cache.save(&path).unwrap();
+    let loaded = ArpCache::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let entries = loaded.into_iter().collect::<Vec<_>>();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].ipv4, Ipv4Addr::new(192, 168, 0, 2));
+    assert_eq!(entries[0].mac, [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into());
+}
+
+#[test]
+fn load_drops_stale_entries() {
+    // Write a cache file with an entry from the Unix epoch
+    let path = std::env::temp_dir().join("proton_arp_load_drops_stale_entries.json");
+    std::fs::write(
+        &path,
+        r#"[{"ipv4":"192.168.0.2","mac":[18,52,86,120,144,171],"created":{"secs_since_epoch":0,"nanos_since_epoch":0}}]"#,
+    ).unwrap();
+
+    let loaded = ArpCache::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.into_iter().count(), 0);
+}
+
+#[test]
+fn merge_updates_and_adds_entries() {
+    // Construct a cache with two entries
+    let mut cache = ArpCache::new();
+    cache.add(Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into());
+    cache.add(Ipv4Addr::new(192, 168, 0, 3), [0x12, 0x34, 0x56, 0x78, 0x90, 0xac].into());
+
+    // Merge a changed entry and a new entry
+    cache.merge(vec![
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xff].into()),
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 4), [0x12, 0x34, 0x56, 0x78, 0x90, 0xad].into()),
+    ]);
+
+    let entries = cache.iter()
+        .map(|entry| (entry.ipv4, entry.mac))
+        .collect::<Vec<(Ipv4Addr, MacAddr)>>();
+
+    assert_eq!(entries, vec![
+        (Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xff].into()),
+        (Ipv4Addr::new(192, 168, 0, 3), [0x12, 0x34, 0x56, 0x78, 0x90, 0xac].into()),
+        (Ipv4Addr::new(192, 168, 0, 4), [0x12, 0x34, 0x56, 0x78, 0x90, 0xad].into()),
+    ]);
+}
+
+#[test]
+fn evict_stale_entries() {
+    // Construct a cache with a fresh entry and an entry from the Unix epoch
+    let stale: ArpCacheEntry = serde_json::from_str(
+        r#"{"ipv4":"192.168.0.3","mac":[18,52,86,120,144,172],"created":{"secs_since_epoch":0,"nanos_since_epoch":0}}"#,
+    ).unwrap();
+
+    let mut cache = ArpCache::new();
+    cache.add(Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into());
+    cache.merge(vec![stale]);
+
+    assert_eq!(cache.evict_stale(DEFAULT_ARP_REFRESH_TIME), 1);
+    assert_eq!(cache.iter().map(|entry| entry.ipv4).collect::<Vec<_>>(), vec![Ipv4Addr::new(192, 168, 0, 2)]);
+}
+
+#[test]
+fn entry_round_trips_through_json() {
+    let entry = ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into());
+
+    let json = serde_json::to_string(&entry).unwrap();
+    let loaded: ArpCacheEntry = serde_json::from_str(&json).unwrap();
+
+    // The absolute timestamp survives, so freshness checks agree
+    let drift = loaded.created().unwrap()
+        .duration_since(entry.created().unwrap())
+        .unwrap_or_else(|e| e.duration());
+    assert!(drift < std::time::Duration::from_secs(1));
+    assert_eq!(loaded.check(DEFAULT_ARP_REFRESH_TIME), entry.check(DEFAULT_ARP_REFRESH_TIME));
+    assert!(loaded.check(std::time::Duration::ZERO));
+}
+
+#[test]
+fn unrepresentable_entries_are_stale() {
+    let entry: ArpCacheEntry = serde_json::from_str(
+        r#"{"ipv4":"192.168.0.2","mac":[18,52,86,120,144,171],"created":null}"#,
+    ).unwrap();
+
+    assert!(entry.created().is_none());
+    assert!(entry.check(std::time::Duration::MAX));
+}
+
+#[test]
+fn uncached_skips_fresh_entries() {
+    // Construct a cache with two fresh entries and a stale entry
+    let stale: ArpCacheEntry = serde_json::from_str(
+        r#"{"ipv4":"192.168.0.4","mac":[18,52,86,120,144,173],"created":{"secs_since_epoch":0,"nanos_since_epoch":0}}"#,
+    ).unwrap();
+
+    let mut cache = ArpCache::new();
+    cache.add(Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into());
+    cache.add(Ipv4Addr::new(192, 168, 0, 3), [0x12, 0x34, 0x56, 0x78, 0x90, 0xac].into());
+    cache.merge(vec![stale]);
+
+    let addresses = (2..=5)
+        .map(|host| Ipv4Addr::new(192, 168, 0, host))
+        .collect::<Vec<Ipv4Addr>>();
+
+    // Only the stale and the unknown addresses need to be probed
+    assert_eq!(
+        cache.uncached(&addresses, DEFAULT_ARP_REFRESH_TIME),
+        vec![Ipv4Addr::new(192, 168, 0, 4), Ipv4Addr::new(192, 168, 0, 5)],
+    );
+
+    // An empty cache needs every address probed
+    assert_eq!(ArpCache::new().uncached(&addresses, DEFAULT_ARP_REFRESH_TIME), addresses);
+}
+
+#[test]
+fn merge_rounds_keeps_union_and_freshest() {
+    // An old reply from a device that has since changed its MAC address
+    let old: ArpCacheEntry = serde_json::from_str(
+        r#"{"ipv4":"192.168.0.2","mac":[18,52,86,120,144,170],"created":{"secs_since_epoch":0,"nanos_since_epoch":0}}"#,
+    ).unwrap();
+
+    // Each round hears from a different subset of hosts
+    let first_round = vec![
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into()),
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 3), [0x12, 0x34, 0x56, 0x78, 0x90, 0xac].into()),
+    ];
+    let second_round = vec![
+        old,
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 4), [0x12, 0x34, 0x56, 0x78, 0x90, 0xad].into()),
+    ];
+
+    let mut cache = ArpCache::new();
+    cache.merge(first_round);
+    cache.merge(second_round);
+
+    let entries = cache.iter()
+        .map(|entry| (entry.ipv4, entry.mac))
+        .collect::<Vec<(Ipv4Addr, MacAddr)>>();
+
+    // Every host is present, and the older reply did not replace the fresher one
+    assert_eq!(entries, vec![
+        (Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into()),
+        (Ipv4Addr::new(192, 168, 0, 3), [0x12, 0x34, 0x56, 0x78, 0x90, 0xac].into()),
+        (Ipv4Addr::new(192, 168, 0, 4), [0x12, 0x34, 0x56, 0x78, 0x90, 0xad].into()),
+    ]);
+}
@@ -0,0 +1,37 @@
+//! Testing IPv4 CIDR range helpers.
+
+use std::net::Ipv4Addr;
+
+use cidr::Ipv4Cidr;
+
+use proton_arp::Ipv4CidrExt;
+
+#[test]
+fn slash_24() {
+    let range: Ipv4Cidr = "192.168.0.0/24".parse().unwrap();
+    let hosts = range.hosts();
+
+    assert_eq!(range.network(), Ipv4Addr::new(192, 168, 0, 0));
+    assert_eq!(range.broadcast(), Some (Ipv4Addr::new(192, 168, 0, 255)));
+    assert_eq!(hosts.len(), 254);
+    assert_eq!(hosts.first(), Some (&Ipv4Addr::new(192, 168, 0, 1)));
+    assert_eq!(hosts.last(), Some (&Ipv4Addr::new(192, 168, 0, 254)));
+}
+
+#[test]
+fn slash_31() {
+    let range: Ipv4Cidr = "10.0.0.0/31".parse().unwrap();
+
+    // Both addresses of a point-to-point link are hosts
+    assert_eq!(range.network(), Ipv4Addr::new(10, 0, 0, 0));
+    assert_eq!(range.broadcast(), None);
+    assert_eq!(range.hosts(), vec![Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 0, 1)]);
+}
+
+#[test]
+fn slash_32() {
+    let range: Ipv4Cidr = "10.0.0.7/32".parse().unwrap();
+
+    assert_eq!(range.broadcast(), None);
+    assert_eq!(range.hosts(), vec![Ipv4Addr::new(10, 0, 0, 7)]);
+}
@@ -0,0 +1,171 @@
+//! Testing the neighbor table parser.
+
+use std::net::Ipv6Addr;
+
+use proton_arp::{
+    parse_neighbors,
+    NdpEntry,
+};
+
+use proton_err::ProtonError;
+
+use proton_mac::MacAddr;
+
+/// The MAC address of the neighbor.
+const THEIR_MAC: [u8; 6] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab];
+
+/// The link-local address of the neighbor.
+const THEIR_LINK_LOCAL: Ipv6Addr = Ipv6Addr::new(0xfe80, 0, 0, 0, 0x1034, 0x56ff, 0xfe78, 0x90ab);
+
+/// The global address of the neighbor.
+const THEIR_GLOBAL: Ipv6Addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2);
+
+/// The index of the network interface scanned.
+const IFINDEX: u32 = 3;
+
+/// Netlink message type of an error.
+const NLMSG_ERROR: u16 = 2;
+
+/// Netlink message type ending a dump.
+const NLMSG_DONE: u16 = 3;
+
+/// Netlink message type of a neighbor.
+const RTM_NEWNEIGH: u16 = 28;
+
+/// Netlink message type of a neighbor table request.
+const RTM_GETNEIGH: u16 = 30;
+
+/// Netlink flag of a message that is part of a multipart dump.
+const NLM_F_MULTI: u16 = 2;
+
+/// Netlink attribute type of the address of a neighbor.
+const NDA_DST: u16 = 1;
+
+/// Netlink attribute type of the link-layer address of a neighbor.
+const NDA_LLADDR: u16 = 2;
+
+/// Address family of an IPv4 neighbor.
+const AF_INET: u8 = 2;
+
+/// Address family of an IPv6 neighbor.
+const AF_INET6: u8 = 10;
+
+/// Construct a Netlink message, padded to a 4-byte boundary.
+fn message(kind: u16, payload: &[u8]) -> Vec<u8> {
+    let mut message = Vec::new();
+    message.extend_from_slice(&(16 + payload.len() as u32).to_ne_bytes());
+    message.extend_from_slice(&kind.to_ne_bytes());
+    message.extend_from_slice(&NLM_F_MULTI.to_ne_bytes());
+    message.extend_from_slice(&[0; 8]);
+    message.extend_from_slice(payload);
+    message.resize(message.len().next_multiple_of(4), 0);
+    message
+}
+
+/// Construct an `NLMSG_ERROR` message answering a neighbor table request.
+fn error(errno: i32) -> Vec<u8> {
+    let mut payload = errno.to_ne_bytes().to_vec();
+    payload.extend_from_slice(&message(RTM_GETNEIGH, &[AF_INET6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+
+    message(NLMSG_ERROR, &payload)
+}
+
+/// Construct an `RTM_NEWNEIGH` message.
+fn neighbor(family: u8, ifindex: u32, ipv6: Ipv6Addr, mac: Option<[u8; 6]>) -> Vec<u8> {
+    let mut payload = vec![family, 0, 0, 0];
+    payload.extend_from_slice(&ifindex.to_ne_bytes());
+    payload.extend_from_slice(&[0; 4]);
+
+    payload.extend_from_slice(&20u16.to_ne_bytes());
+    payload.extend_from_slice(&NDA_DST.to_ne_bytes());
+    payload.extend_from_slice(&ipv6.octets());
+
+    if let Some (mac) = mac {
+        payload.extend_from_slice(&10u16.to_ne_bytes());
+        payload.extend_from_slice(&NDA_LLADDR.to_ne_bytes());
+        payload.extend_from_slice(&mac);
+        payload.extend_from_slice(&[0; 2]);
+    }
+
+    message(RTM_NEWNEIGH, &payload)
+}
+
+#[test]
+fn parse_resolved_neighbors() {
+    let messages = [
+        neighbor(AF_INET6, IFINDEX, THEIR_LINK_LOCAL, Some (THEIR_MAC)),
+        neighbor(AF_INET6, IFINDEX, THEIR_GLOBAL, Some (THEIR_MAC)),
+        neighbor(AF_INET6, IFINDEX, Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 3), None),
+        message(NLMSG_DONE, &[0; 4]),
+    ].concat();
+
+    let mut entries: Vec<NdpEntry> = Vec::new();
+
+    assert!(parse_neighbors(&messages, IFINDEX, &mut entries).unwrap());
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0].ipv6, THEIR_LINK_LOCAL);
+    assert_eq!(entries[0].mac, MacAddr::from(THEIR_MAC));
+    assert!(entries[0].is_link_local());
+
+    assert_eq!(entries[1].ipv6, THEIR_GLOBAL);
+    assert_eq!(entries[1].mac, MacAddr::from(THEIR_MAC));
+    assert!(!entries[1].is_link_local());
+}
+
+#[test]
+fn skip_other_neighbors() {
+    let messages = [
+        neighbor(AF_INET6, IFINDEX + 1, THEIR_GLOBAL, Some (THEIR_MAC)),
+        neighbor(AF_INET, IFINDEX, THEIR_GLOBAL, Some (THEIR_MAC)),
+    ].concat();
+
+    let mut entries: Vec<NdpEntry> = Vec::new();
+
+    // The dump continues in the next datagram
+    assert!(!parse_neighbors(&messages, IFINDEX, &mut entries).unwrap());
+    assert!(entries.is_empty());
+}
+
+#[test]
+fn report_netlink_errors() {
+    let mut entries: Vec<NdpEntry> = Vec::new();
+
+    // EPERM
+    let e = parse_neighbors(&error(-1), IFINDEX, &mut entries).unwrap_err();
+    assert!(matches!(e, ProtonError::MustHaveRootPermissions));
+
+    // Truncated message
+    let truncated = neighbor(AF_INET6, IFINDEX, THEIR_GLOBAL, Some (THEIR_MAC));
+    assert!(parse_neighbors(&truncated[..truncated.len() - 4], IFINDEX, &mut entries).is_err());
+}
+
+#[test]
+fn report_errors_ending_dumps() {
+    // EBUSY, after the first neighbors of the dump
+    let messages = [
+        neighbor(AF_INET6, IFINDEX, THEIR_LINK_LOCAL, Some (THEIR_MAC)),
+        neighbor(AF_INET6, IFINDEX, THEIR_GLOBAL, Some (THEIR_MAC)),
+        error(-16),
+    ].concat();
+
+    let mut entries: Vec<NdpEntry> = Vec::new();
+
+    let e = parse_neighbors(&messages, IFINDEX, &mut entries).unwrap_err();
+    assert!(e.is_transient());
+    assert_eq!(entries.len(), 2);
+}
+
+#[test]
+fn skip_acknowledgements() {
+    let messages = [
+        error(0),
+        neighbor(AF_INET6, IFINDEX, THEIR_GLOBAL, Some (THEIR_MAC)),
+        message(NLMSG_DONE, &[0; 4]),
+    ].concat();
+
+    let mut entries: Vec<NdpEntry> = Vec::new();
+
+    assert!(parse_neighbors(&messages, IFINDEX, &mut entries).unwrap());
+    assert_eq!(entries.len(), 1);
+}
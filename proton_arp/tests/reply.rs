@@ -0,0 +1,62 @@
+//! Testing the ARP reply parser.
+
+use std::net::Ipv4Addr;
+
+use proton_arp::parse_arp_reply;
+
+use proton_mac::MacAddr;
+
+/// The MAC address of the receiving interface.
+const OUR_MAC: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x01];
+
+/// The MAC address of the replying device.
+const THEIR_MAC: [u8; 6] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab];
+
+/// Construct an ARP reply frame from a sender to the receiving interface.
+fn arp_reply(sender_mac: [u8; 6], sender_ipv4: [u8; 4]) -> Vec<u8> {
+    let mut frame = Vec::new();
+
+    // Ethernet header
+    frame.extend_from_slice(&OUR_MAC);
+    frame.extend_from_slice(&sender_mac);
+    frame.extend_from_slice(&[0x08, 0x06]);
+
+    // ARP packet
+    frame.extend_from_slice(&[0x00, 0x01, 0x08, 0x00, 6, 4, 0x00, 0x02]);
+    frame.extend_from_slice(&sender_mac);
+    frame.extend_from_slice(&sender_ipv4);
+    frame.extend_from_slice(&OUR_MAC);
+    frame.extend_from_slice(&[192, 168, 0, 1]);
+
+    frame
+}
+
+#[test]
+fn parse_reply() {
+    let entry = parse_arp_reply(&arp_reply(THEIR_MAC, [192, 168, 0, 2]), OUR_MAC.into()).unwrap();
+
+    assert_eq!(entry.ipv4, Ipv4Addr::new(192, 168, 0, 2));
+    assert_eq!(entry.mac, MacAddr::from(THEIR_MAC));
+}
+
+#[test]
+fn drop_our_own_frames() {
+    assert!(parse_arp_reply(&arp_reply(OUR_MAC, [192, 168, 0, 1]), OUR_MAC.into()).is_none());
+}
+
+#[test]
+fn drop_non_arp_frames() {
+    let mut frame = arp_reply(THEIR_MAC, [192, 168, 0, 2]);
+    frame[12..14].copy_from_slice(&[0x08, 0x00]);
+
+    assert!(parse_arp_reply(&frame, OUR_MAC.into()).is_none());
+}
+
+#[test]
+fn drop_malformed_frames() {
+    let frame = arp_reply(THEIR_MAC, [192, 168, 0, 2]);
+
+    assert!(parse_arp_reply(&[], OUR_MAC.into()).is_none());
+    assert!(parse_arp_reply(&frame[..10], OUR_MAC.into()).is_none());
+    assert!(parse_arp_reply(&frame[..20], OUR_MAC.into()).is_none());
+}
@@ -0,0 +1,68 @@
+//! Testing ARP scan reports.
+
+use std::net::Ipv4Addr;
+
+use proton_arp::{
+    ArpCacheEntry,
+    ProbeStatus,
+    ScanReport,
+};
+
+use proton_mac::MacAddr;
+
+/// The MAC address of a device.
+const MAC: [u8; 6] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab];
+
+/// The MAC address of another device.
+const OTHER_MAC: [u8; 6] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xac];
+
+/// The probed addresses `192.168.0.2` through `192.168.0.4`.
+fn probed() -> Vec<Ipv4Addr> {
+    (2..=4).map(|host| Ipv4Addr::new(192, 168, 0, host)).collect()
+}
+
+#[test]
+fn report_each_probe() {
+    let replies = [
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 2), MAC.into()),
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 4), OTHER_MAC.into()),
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 4), MAC.into()),
+    ];
+
+    let report = ScanReport::new(&probed(), &replies);
+
+    assert_eq!(report.len(), 3);
+    assert_eq!(report.status(Ipv4Addr::new(192, 168, 0, 2)), Some (&ProbeStatus::Replied (MAC.into())));
+    assert_eq!(report.status(Ipv4Addr::new(192, 168, 0, 3)), Some (&ProbeStatus::Silent));
+    assert_eq!(
+        report.status(Ipv4Addr::new(192, 168, 0, 4)),
+        Some (&ProbeStatus::Conflict (vec![MacAddr::from(OTHER_MAC), MacAddr::from(MAC)])),
+    );
+    assert_eq!(report.silent(), [Ipv4Addr::new(192, 168, 0, 3)]);
+    assert_eq!(report.conflicts(), [Ipv4Addr::new(192, 168, 0, 4)]);
+}
+
+#[test]
+fn repeated_replies_are_not_conflicts() {
+    let replies = [
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 2), MAC.into()),
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 2), MAC.into()),
+    ];
+
+    let report = ScanReport::new(&probed(), &replies);
+
+    assert_eq!(report.status(Ipv4Addr::new(192, 168, 0, 2)), Some (&ProbeStatus::Replied (MAC.into())));
+    assert!(report.conflicts().is_empty());
+}
+
+#[test]
+fn unprobed_addresses_are_not_reported() {
+    let replies = [
+        ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 9), MAC.into()),
+    ];
+
+    let report = ScanReport::new(&probed(), &replies);
+
+    assert_eq!(report.status(Ipv4Addr::new(192, 168, 0, 9)), None);
+    assert_eq!(report.silent().len(), 3);
+}
@@ -0,0 +1,47 @@
+//! Testing the ARP request frame builder.
+
+use std::net::Ipv4Addr;
+
+use proton_arp::{
+    ArpMode,
+    build_request_frame,
+};
+
+use proton_mac::MacAddr;
+
+/// The MAC address of the sending interface.
+const OUR_MAC: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x01];
+
+/// The offset of the sender protocol address in an ARP frame.
+const SENDER_PROTO_ADDR: usize = 28;
+
+/// The offset of the target protocol address in an ARP frame.
+const TARGET_PROTO_ADDR: usize = 38;
+
+#[test]
+fn request_sets_sender() {
+    let frame = build_request_frame(
+        MacAddr::from(OUR_MAC),
+        Ipv4Addr::new(192, 168, 0, 1),
+        Ipv4Addr::new(192, 168, 0, 2),
+        ArpMode::Request,
+    );
+
+    assert_eq!(&frame[SENDER_PROTO_ADDR..SENDER_PROTO_ADDR + 4], &[192, 168, 0, 1]);
+    assert_eq!(&frame[TARGET_PROTO_ADDR..TARGET_PROTO_ADDR + 4], &[192, 168, 0, 2]);
+}
+
+#[test]
+fn probe_zeroes_sender() {
+    let frame = build_request_frame(
+        MacAddr::from(OUR_MAC),
+        Ipv4Addr::new(192, 168, 0, 1),
+        Ipv4Addr::new(192, 168, 0, 1),
+        ArpMode::Probe,
+    );
+
+    // The sender hardware address is still ours
+    assert_eq!(&frame[22..28], &OUR_MAC);
+    assert_eq!(&frame[SENDER_PROTO_ADDR..SENDER_PROTO_ADDR + 4], &[0, 0, 0, 0]);
+    assert_eq!(&frame[TARGET_PROTO_ADDR..TARGET_PROTO_ADDR + 4], &[192, 168, 0, 1]);
+}
@@ -0,0 +1,283 @@
+//! Testing the ARP scan.
+
+use std::{
+    collections::VecDeque,
+    io,
+    net::Ipv4Addr,
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use pnet::datalink::{
+    self,
+    DataLinkReceiver,
+    DataLinkSender,
+    MacAddr,
+};
+
+use tokio::{
+    runtime::Builder,
+    time,
+};
+
+use proton_arp::{
+    ArpCacheEntry,
+    ArpManager,
+    ScanOptions,
+    ScanResult,
+};
+
+use proton_err::ProtonError;
+
+use proton_nif::NetworkInterface;
+
+/// A transmitter that discards every frame.
+struct MockSender;
+
+impl DataLinkSender for MockSender {
+    fn build_and_send(
+        &mut self,
+        num_packets: usize,
+        packet_size: usize,
+        func: &mut dyn FnMut(&mut [u8]),
+    ) -> Option<io::Result<()>> {
+        for _ in 0..num_packets {
+            func(&mut vec![0u8; packet_size]);
+        }
+
+        Some (Ok (()))
+    }
+
+    fn send_to(&mut self, _packet: &[u8], _dst: Option<datalink::NetworkInterface>) -> Option<io::Result<()>> {
+        Some (Ok (()))
+    }
+}
+
+/// A transmitter that takes `delay` to send each frame.
+struct SlowSender {
+    delay: Duration,
+}
+
+impl DataLinkSender for SlowSender {
+    fn build_and_send(
+        &mut self,
+        num_packets: usize,
+        packet_size: usize,
+        func: &mut dyn FnMut(&mut [u8]),
+    ) -> Option<io::Result<()>> {
+        for _ in 0..num_packets {
+            thread::sleep(self.delay);
+            func(&mut vec![0u8; packet_size]);
+        }
+
+        Some (Ok (()))
+    }
+
+    fn send_to(&mut self, _packet: &[u8], _dst: Option<datalink::NetworkInterface>) -> Option<io::Result<()>> {
+        thread::sleep(self.delay);
+
+        Some (Ok (()))
+    }
+}
+
+/// A receiver that yields queued frames, then times out after `idle` per read.
+struct MockReceiver {
+    frames: VecDeque<Vec<u8>>,
+    current: Vec<u8>,
+    idle: Duration,
+}
+
+impl DataLinkReceiver for MockReceiver {
+    fn next(&mut self) -> io::Result<&[u8]> {
+        match self.frames.pop_front() {
+            Some (frame) => {
+                self.current = frame;
+                Ok (&self.current)
+            },
+            None => {
+                thread::sleep(self.idle);
+                Err (io::ErrorKind::TimedOut.into())
+            },
+        }
+    }
+}
+
+/// Construct an ARP reply frame from `192.168.0.2` to the scanning host.
+fn arp_reply() -> Vec<u8> {
+    let their_mac = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab];
+    let mut frame = Vec::new();
+
+    // Ethernet header
+    frame.extend_from_slice(&[0x02, 0, 0, 0, 0, 1]);
+    frame.extend_from_slice(&their_mac);
+    frame.extend_from_slice(&[0x08, 0x06]);
+
+    // ARP packet
+    frame.extend_from_slice(&[0x00, 0x01, 0x08, 0x00, 6, 4, 0x00, 0x02]);
+    frame.extend_from_slice(&their_mac);
+    frame.extend_from_slice(&[192, 168, 0, 2]);
+    frame.extend_from_slice(&[0x02, 0, 0, 0, 0, 1]);
+    frame.extend_from_slice(&[192, 168, 0, 1]);
+
+    frame
+}
+
+/// Construct a mock interface with the addresses of a scanning host.
+fn interface(frames: Vec<Vec<u8>>) -> NetworkInterface {
+    let rx = MockReceiver {
+        frames: frames.into(),
+        current: Vec::new(),
+        idle: Duration::from_millis(10),
+    };
+    let mut interface = NetworkInterface::from_channel("mock0", Box::new(MockSender), Box::new(rx));
+    interface.mac = Some (MacAddr::new(0x02, 0, 0, 0, 0, 1));
+    interface.ipv4 = Some (Ipv4Addr::new(192, 168, 0, 1));
+
+    interface
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dropped_scan_releases_interface() {
+    let mut interface = interface(Vec::new());
+
+    // Start a scan on a clone of the interface, then drop it part-way through
+    let scan = proton_arp::scan_interface(interface.clone(), vec![Ipv4Addr::new(192, 168, 0, 2)], ScanOptions::default());
+
+    assert!(time::timeout(Duration::from_millis(100), scan).await.is_err());
+
+    // The listener releases the shared receiver within one read
+    let frame = time::timeout(Duration::from_secs(1), interface.poll_recv()).await;
+
+    assert!(matches!(frame, Ok (Ok (None))));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn short_timeout_keeps_replies() {
+    let interface = interface(vec![arp_reply()]);
+    let options = ScanOptions {
+        timeout: Some (Duration::from_millis(200)),
+        ..ScanOptions::default()
+    };
+
+    // The timeout is much shorter than the listener delay
+    let start = Instant::now();
+    let entries = proton_arp::scan_interface(interface, vec![Ipv4Addr::new(192, 168, 0, 2)], options).await.unwrap();
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].ipv4, Ipv4Addr::new(192, 168, 0, 2));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn timed_out_scan_keeps_partial_replies() {
+    let rx = MockReceiver {
+        frames: vec![arp_reply()].into(),
+        current: Vec::new(),
+        idle: Duration::from_millis(10),
+    };
+    let sender = SlowSender {
+        delay: Duration::from_millis(50),
+    };
+    let mut interface = NetworkInterface::from_channel("mock0", Box::new(sender), Box::new(rx));
+    interface.mac = Some (MacAddr::new(0x02, 0, 0, 0, 0, 1));
+    interface.ipv4 = Some (Ipv4Addr::new(192, 168, 0, 1));
+    let options = ScanOptions {
+        timeout: Some (Duration::from_millis(200)),
+        ..ScanOptions::default()
+    };
+    let ips: Vec<Ipv4Addr> = (2..=20).map(|host| Ipv4Addr::new(192, 168, 0, host)).collect();
+
+    // Only a few of the requests can be sent in time
+    let (entries, complete) = proton_arp::scan_interface_partial(interface.clone(), ips.clone(), options).await.unwrap();
+
+    assert!(!complete);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].ipv4, Ipv4Addr::new(192, 168, 0, 2));
+
+    // The complete variant reports the timeout
+    let result = proton_arp::scan_interface(interface, ips, options).await;
+
+    assert!(matches!(result, Err (ProtonError::ScanTimedOut)));
+}
+
+#[tokio::test]
+async fn scan_interface_not_ready() {
+    let mut interface = interface(Vec::new());
+    interface.ipv4 = None;
+
+    let result = proton_arp::scan_interface(interface, vec![Ipv4Addr::new(192, 168, 0, 2)], ScanOptions::default()).await;
+
+    assert!(matches!(result, Err (ProtonError::InterfaceNotReady (ifname)) if ifname == "mock0"));
+}
+
+#[test]
+#[ignore = "requires raw socket privileges"]
+fn announce_on_loopback() {
+    let runtime = Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        let arp = ArpManager::new("127.0.0.0/8".parse().unwrap(), "lo");
+
+        let conflict = arp.announce(Ipv4Addr::new(127, 0, 0, 1), [0x02, 0, 0, 0, 0, 1].into()).await.unwrap();
+
+        assert_eq!(conflict, None);
+    });
+}
+
+#[tokio::test]
+async fn scan_missing_interface() {
+    let result: ScanResult = proton_arp::scan(vec![Ipv4Addr::new(127, 0, 0, 2)], "proton-missing0").await;
+
+    assert!(matches!(result, Err (ProtonError::CouldNotFindInterface (ifname)) if ifname == "proton-missing0"));
+}
+
+#[tokio::test]
+async fn merge_rounds_keeps_every_round() {
+    // Each round hears from a different subset of hosts
+    let mut rounds = VecDeque::from([
+        vec![ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 2), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into())],
+        vec![],
+        vec![ArpCacheEntry::new(Ipv4Addr::new(192, 168, 0, 3), [0x12, 0x34, 0x56, 0x78, 0x90, 0xac].into())],
+    ]);
+    let scan = || std::future::ready(Ok (rounds.pop_front().unwrap()));
+
+    let cache = proton_arp::merge_rounds(3, Duration::ZERO, scan).await.unwrap();
+    let ips = cache.iter()
+        .map(|entry| entry.ipv4)
+        .collect::<Vec<Ipv4Addr>>();
+
+    assert!(rounds.is_empty());
+    assert_eq!(ips, vec![Ipv4Addr::new(192, 168, 0, 2), Ipv4Addr::new(192, 168, 0, 3)]);
+}
+
+#[tokio::test]
+async fn merge_rounds_fails_with_any_round() {
+    let mut round = 0;
+    let scan = || {
+        round += 1;
+        std::future::ready(if round == 2 { Err (ProtonError::ScanTimedOut) } else { Ok (Vec::new()) })
+    };
+
+    let result = proton_arp::merge_rounds(3, Duration::ZERO, scan).await;
+
+    assert!(matches!(result, Err (ProtonError::ScanTimedOut)));
+}
+
+#[tokio::test]
+async fn merge_rounds_runs_at_least_once() {
+    let mut count = 0;
+    let scan = || {
+        count += 1;
+        std::future::ready(Ok (Vec::new()))
+    };
+
+    proton_arp::merge_rounds(0, Duration::ZERO, scan).await.unwrap();
+
+    assert_eq!(count, 1);
+}
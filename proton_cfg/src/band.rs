@@ -0,0 +1,172 @@
+//! Wireless frequency bands supported by the hotspot.
+
+use std::{
+    fmt::{
+        Display,
+        Formatter,
+        Result,
+    },
+    str::FromStr,
+};
+
+use serde::{
+    de,
+    Deserialize,
+    Deserializer,
+};
+
+use proton_err::ProtonError;
+
+/// Channels of the 2.4 GHz band (channel 14 is only legal in Japan, so it
+/// is excluded).
+const CHANNELS_2_4_GHZ: [u8; 13] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+
+/// Channels of the 5 GHz band (U-NII-1, U-NII-2, U-NII-2e, and U-NII-3),
+/// with 20 MHz spacing.
+const CHANNELS_5_GHZ: [u8; 25] = [
+    36, 40, 44, 48,
+    52, 56, 60, 64,
+    100, 104, 108, 112, 116, 120, 124, 128, 132, 136, 140, 144,
+    149, 153, 157, 161, 165,
+];
+
+/// Channels of the 5 GHz band that require Dynamic Frequency Selection
+/// (U-NII-2 and U-NII-2e).
+const DFS_CHANNELS_5_GHZ: [u8; 16] = [
+    52, 56, 60, 64,
+    100, 104, 108, 112, 116, 120, 124, 128, 132, 136, 140, 144,
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// The frequency band of a hotspot.
+pub enum Band {
+    #[default]
+    /// 2.4 GHz (802.11b/g).
+    TwoFourGhz,
+
+    /// 5 GHz (802.11a).
+    FiveGhz,
+}
+
+impl Band {
+    /// Get the NetworkManager band setting for this band.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `&'static str` containing the value of `802-11-wireless.band`.
+    pub fn nm_band(&self) -> &'static str {
+        use Band::*;
+        match self {
+            TwoFourGhz => "bg",
+            FiveGhz => "a",
+        }
+    }
+
+    /// Get the legal channels of this band.
+    /// 
+    /// This assumes a common regulatory domain (e.g. FCC or ETSI), so the
+    /// driver may still reject some of these channels in other countries.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `&'static [u8]` containing the channels, in ascending order.  On
+    /// 5 GHz, this includes the DFS channels (see `Band::dfs_channels`).
+    pub fn channels(&self) -> &'static [u8] {
+        use Band::*;
+        match self {
+            TwoFourGhz => &CHANNELS_2_4_GHZ,
+            FiveGhz => &CHANNELS_5_GHZ,
+        }
+    }
+
+    /// Get the channels of this band that require Dynamic Frequency Selection.
+    /// 
+    /// An access point on a DFS channel must first listen for radar, and
+    /// must move off the channel if radar is detected, so these channels
+    /// can take a minute to come up and may change at any time.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `&'static [u8]` containing the DFS channels, a subset of
+    /// `Band::channels` (empty on 2.4 GHz).
+    pub fn dfs_channels(&self) -> &'static [u8] {
+        use Band::*;
+        match self {
+            TwoFourGhz => &[],
+            FiveGhz => &DFS_CHANNELS_5_GHZ,
+        }
+    }
+
+    /// Check if a channel is legal on this band.
+    /// 
+    /// # Parameters
+    /// - `channel` (`u8`): the channel
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not `channel` is one of `Band::channels`.
+    pub fn valid_channel(&self, channel: u8) -> bool {
+        self.channels().contains(&channel)
+    }
+
+    /// Check if an operating frequency is in this band.
+    /// 
+    /// Channel numbers are only unique within a band (e.g. channel 37 is on
+    /// both 5 GHz and 6 GHz), so frequencies tell the bands apart.
+    /// 
+    /// # Parameters
+    /// - `frequency_mhz` (`u32`): the operating frequency, in MHz
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not `frequency_mhz` is in this band.
+    pub fn contains_frequency(&self, frequency_mhz: u32) -> bool {
+        use Band::*;
+        match self {
+            TwoFourGhz => (2400..2500).contains(&frequency_mhz),
+            FiveGhz => (5150..5925).contains(&frequency_mhz),
+        }
+    }
+}
+
+impl FromStr for Band {
+    type Err = ProtonError;
+
+    /// Parse a band, case-insensitively.
+    /// 
+    /// Accepted values are `2.4`, `2.4ghz`, `2.4 ghz`, `bg`, `b`, and `g` for
+    /// 2.4 GHz, and `5`, `5ghz`, `5 ghz`, and `a` for 5 GHz.
+    fn from_str(band: &str) -> std::result::Result<Self, Self::Err> {
+        match band.trim().to_lowercase().as_str() {
+            "2.4" | "2.4ghz" | "2.4 ghz" | "bg" | "b" | "g" => Ok (Band::TwoFourGhz),
+            "5" | "5ghz" | "5 ghz" | "a" => Ok (Band::FiveGhz),
+            _ => Err (ProtonError::UnknownBand (band.to_string())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Band {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Display for Band {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        use Band::*;
+        let band = match self {
+            TwoFourGhz => "2.4 GHz",
+            FiveGhz => "5 GHz",
+        };
+
+        write!(f, "{}", band)
+    }
+}
@@ -0,0 +1,54 @@
+//! Differences between two hotspot configurations.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A field that differs between two hotspot configurations.
+pub enum ConfigChange {
+    /// The SSID changed.
+    Ssid,
+
+    /// The password changed.
+    Password,
+
+    /// The security mode changed.
+    Security,
+
+    /// The frequency band changed.
+    Band,
+
+    /// The gateway address changed.
+    Gateway,
+
+    /// The CIDR address range changed.
+    Cidr,
+
+    /// The IPv6 prefix or gateway changed.
+    Ipv6,
+
+    /// The operating channel changed.
+    Channel,
+
+    /// The SSID visibility changed.
+    Hidden,
+}
+
+impl ConfigChange {
+    /// Check if applying this change requires the hotspot to be recreated.
+    /// 
+    /// Recreating the hotspot disconnects every client.  Changes to fields
+    /// that are not part of the NetworkManager connection (the CIDR range and
+    /// IPv6 prefix, which are only used for device discovery) can be applied
+    /// without dropping clients.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the hotspot must be recreated.
+    pub fn requires_restart(&self) -> bool {
+        use ConfigChange::*;
+        match self {
+            Ssid | Password | Security | Band | Gateway | Channel | Hidden => true,
+            Cidr | Ipv6 => false,
+        }
+    }
+}
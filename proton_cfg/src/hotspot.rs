@@ -1,14 +1,32 @@
 //! Define an abstraction over hotspot configuration data.
 
-use std::net::Ipv4Addr;
+use std::net::{
+    Ipv4Addr,
+    Ipv6Addr,
+};
 
-use cidr::Ipv4Cidr;
+use cidr::{
+    Ipv4Cidr,
+    Ipv6Cidr,
+};
 
 use proton_err::{
     ProtonError,
     ProtonResult,
 };
 
+use crate::{
+    Band,
+    ConfigChange,
+    Security,
+};
+
+/// Minimum length of a WPA passphrase.
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Maximum length of a WPA passphrase.
+const MAX_PASSPHRASE_LEN: usize = 63;
+
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
 /// Define a structure holding a hotspot configuration.
@@ -19,52 +37,196 @@ pub struct HotspotConfig {
     /// Password of the hotspot.
     pub pass: String,
 
-    /// Security type of the hotspot.
-    pub security: String,
+    /// Security mode of the hotspot.
+    pub security: Security,
 
     /// Frequency band.
-    pub band: String,
+    pub band: Band,
 
     /// IPv4 address of the access point (gateway address).
     pub gateway: Ipv4Addr,
 
     /// IPv4 CIDR address range of the network.
     pub cidr: Ipv4Cidr,
+
+    /// IPv6 prefix of the network, if it is dual-stack.
+    /// 
+    /// When set, connected devices are also resolved over IPv6 neighbor
+    /// discovery.  `None` keeps the network IPv4-only.
+    pub ipv6_prefix: Option<Ipv6Cidr>,
+
+    /// IPv6 address of the access point, if it is dual-stack.
+    /// 
+    /// This is only recorded, and must be within `ipv6_prefix`.
+    pub ipv6_gateway: Option<Ipv6Addr>,
+
+    /// Operating channel of the hotspot, if one has been selected.
+    /// 
+    /// `None` leaves channel selection up to the driver.  The channel must
+    /// be legal on `band` (see `Band::valid_channel`).
+    pub channel: Option<u8>,
+
+    /// Whether or not the SSID is hidden (`802-11-wireless.hidden`).
+    /// 
+    /// **Note**: hiding the SSID is a weak security measure, since the SSID is
+    /// still sent in the clear whenever a client connects.  It only keeps the
+    /// network out of casual network lists.
+    pub hidden: bool,
+
+    /// Whether or not to accept insecure configurations, such as an open
+    /// hotspot with a password (which the password would not protect).
+    /// 
+    /// This never enables WEP, which Proton cannot configure (see
+    /// `Security::from_str`), so WEP is rejected whether or not this is set.
+    pub allow_insecure: bool,
 }
 
-impl From<(String, String, String, String, String, String)> for HotspotConfig {
-    fn from(config: (String, String, String, String, String, String)) -> Self {
-        let (ssid, pass, cidr, gateway, security, band) = config;
+impl HotspotConfig {
+    /// Validate this hotspot configuration.
+    /// 
+    /// Secured hotspots must have a passphrase of 8 to 63 characters.  Hotspots
+    /// without a password (open or OWE) must have an empty password, since a
+    /// password that is silently ignored suggests that the wrong security mode
+    /// was chosen; this is only accepted if `allow_insecure` is set.  The
+    /// channel, if one is selected, must be legal on the band.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the configuration is
+    /// valid, or `ProtonError::OpenWithPassword` if a hotspot without a
+    /// password was given one.
+    pub fn validate(&self) -> ProtonResult<()> {
+        if let Some (channel) = self.channel {
+            if !self.band.valid_channel(channel) {
+                return Err (ProtonError::ChannelNotInBand {
+                    channel,
+                    band: self.band.to_string(),
+                });
+            }
+        }
 
-        // Parse CIDR
+        if !self.security.requires_password() {
+            if !self.pass.is_empty() && !self.allow_insecure {
+                return Err (ProtonError::OpenWithPassword);
+            }
+
+            return Ok (());
+        }
+
+        let length = self.pass.chars().count();
+        if !(MIN_PASSPHRASE_LEN..=MAX_PASSPHRASE_LEN).contains(&length) {
+            return Err (ProtonError::InvalidPassphraseLength (length));
+        }
+
+        Ok (())
+    }
+
+    /// List the fields that differ between this configuration and another.
+    /// 
+    /// # Parameters
+    /// - `other` (`&HotspotConfig`): the configuration to compare against
+    /// 
+    /// # Returns
+    /// A `Vec<ConfigChange>` containing each field that differs, in
+    /// declaration order.  The list is empty if the configurations are equal.
+    pub fn diff(&self, other: &Self) -> Vec<ConfigChange> {
+        let mut changes = Vec::new();
+
+        if self.ssid != other.ssid {
+            changes.push(ConfigChange::Ssid);
+        }
+        if self.pass != other.pass {
+            changes.push(ConfigChange::Password);
+        }
+        if self.security != other.security {
+            changes.push(ConfigChange::Security);
+        }
+        if self.band != other.band {
+            changes.push(ConfigChange::Band);
+        }
+        if self.gateway != other.gateway {
+            changes.push(ConfigChange::Gateway);
+        }
+        if self.cidr != other.cidr {
+            changes.push(ConfigChange::Cidr);
+        }
+        if self.ipv6_prefix != other.ipv6_prefix || self.ipv6_gateway != other.ipv6_gateway {
+            changes.push(ConfigChange::Ipv6);
+        }
+        if self.channel != other.channel {
+            changes.push(ConfigChange::Channel);
+        }
+        if self.hidden != other.hidden {
+            changes.push(ConfigChange::Hidden);
+        }
+
+        changes
+    }
+}
+
+impl Default for HotspotConfig {
+    /// Construct a WPA2 hotspot named `Proton` on `192.168.0.0/24`
+    /// (gateway `192.168.0.1`) in the 2.4 GHz band.
+    /// 
+    /// **Note**: the default password is the placeholder `password`, which is
+    /// insecure and should always be changed before the hotspot is created.
+    fn default() -> Self {
         // Note: it's okay to use `Result::unwrap` here because we pass
         // `Ipv4Cidr::new` static arguments.
-        let cidr = match parse_cidr(&cidr) {
-            Ok (c) => c,
-            Err (_) => Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 24).unwrap(),
-        };
+        Self {
+            ssid: "Proton".to_string(),
+            pass: "password".to_string(),
+            security: Security::Wpa2,
+            band: Band::TwoFourGhz,
+            gateway: Ipv4Addr::new(192, 168, 0, 1),
+            cidr: Ipv4Cidr::new(Ipv4Addr::new(192, 168, 0, 0), 24).unwrap(),
+            ipv6_prefix: None,
+            ipv6_gateway: None,
+            channel: None,
+            hidden: false,
+            allow_insecure: false,
+        }
+    }
+}
+
+impl TryFrom<(String, String, String, String, String, String)> for HotspotConfig {
+    type Error = ProtonError;
+
+    /// Parse a hotspot configuration from its SSID, password, CIDR range,
+    /// gateway, security mode, and band.
+    /// 
+    /// Any field that cannot be parsed is an error, rather than silently
+    /// falling back to its default.
+    fn try_from(config: (String, String, String, String, String, String)) -> ProtonResult<Self> {
+        let (ssid, pass, cidr, gateway, security, band) = config;
+
+        // Parse CIDR
+        let cidr = parse_cidr(&cidr)?;
 
         // Parse IPv4 gateway
-        let gateway = match str::parse::<Ipv4Addr>(&gateway) {
-            Ok (g) => g,
-            Err (_) => Ipv4Addr::new(192, 168, 0, 1),
-        };
+        let gateway = str::parse::<Ipv4Addr>(&gateway)?;
+
+        // Parse security
+        let security = security.parse::<Security>()?;
 
         // Parse band
-        let band = match band.as_str() {
-            "2.4" => "bg",
-            "5" => "a",
-            _ => "bg", // default to 2.4 GHz
-        }.to_string();
+        let band = band.parse::<Band>()?;
 
-        Self {
+        Ok (Self {
             ssid,
             pass,
             security,
             gateway,
             cidr,
             band,
-        }
+            ipv6_prefix: None,
+            ipv6_gateway: None,
+            channel: None,
+            hidden: false,
+            allow_insecure: false,
+        })
     }
 }
 
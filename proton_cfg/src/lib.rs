@@ -1,5 +1,15 @@
 //! Configuration structures for the Proton access point management library.
 
+mod band;
+mod change;
 mod hotspot;
+mod qr;
+mod security;
 
-pub use hotspot::HotspotConfig;
\ No newline at end of file
+pub use band::Band;
+
+pub use change::ConfigChange;
+
+pub use hotspot::HotspotConfig;
+
+pub use security::Security;
@@ -0,0 +1,75 @@
+//! Wi-Fi join QR codes for hotspot configurations.
+
+#[cfg(feature = "qrcode")]
+use qrcode::{
+    QrCode,
+    render::svg,
+};
+
+#[cfg(feature = "qrcode")]
+use proton_err::ProtonResult;
+
+use crate::HotspotConfig;
+
+impl HotspotConfig {
+    /// Construct the payload of a Wi-Fi join QR code for this hotspot.
+    /// 
+    /// The payload has the standard form `WIFI:T:<type>;S:<ssid>;P:<pass>;;`,
+    /// which most phone cameras recognize.  Hotspots without a password (open
+    /// or OWE) omit it, and hidden hotspots add `H:true;` so that phones probe for the SSID.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `String` containing the QR code payload.
+    pub fn wifi_qr_payload(&self) -> String {
+        let mut payload = format!(
+            "WIFI:T:{};S:{};",
+            self.security.qr_token(),
+            escape(&self.ssid),
+        );
+
+        if self.security.requires_password() {
+            payload.push_str(&format!("P:{};", escape(&self.pass)));
+        }
+
+        if self.hidden {
+            payload.push_str("H:true;");
+        }
+
+        payload.push(';');
+
+        payload
+    }
+
+    /// Render a Wi-Fi join QR code for this hotspot as an SVG image.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<String>` containing the SVG document, if the payload
+    /// could be encoded.
+    #[cfg(feature = "qrcode")]
+    pub fn qr_svg(&self) -> ProtonResult<String> {
+        let code = QrCode::new(self.wifi_qr_payload())?;
+
+        Ok (code.render::<svg::Color>().build())
+    }
+}
+
+/// Escape the special characters of a Wi-Fi QR code field.
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+
+    for c in field.chars() {
+        if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
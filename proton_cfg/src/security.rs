@@ -0,0 +1,205 @@
+//! Wireless security modes supported by the hotspot.
+
+use std::{
+    fmt::{
+        Display,
+        Formatter,
+        Result,
+    },
+    str::FromStr,
+};
+
+use serde::{
+    de,
+    Deserialize,
+    Deserializer,
+};
+
+use proton_err::ProtonError;
+
+/// NetworkManager protected management frames setting requiring PMF
+/// (`NM_SETTING_WIRELESS_SECURITY_PMF_REQUIRED`).
+const NM_PMF_REQUIRED: i32 = 3;
+
+/// NetworkManager protected management frames setting enabling PMF for the
+/// clients that support it (`NM_SETTING_WIRELESS_SECURITY_PMF_OPTIONAL`).
+const NM_PMF_OPTIONAL: i32 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The security mode of a hotspot.
+pub enum Security {
+    /// Open network, without a password.
+    Open,
+
+    /// Enhanced Open (Opportunistic Wireless Encryption), which encrypts
+    /// traffic without a password.
+    Owe,
+
+    /// WPA2 Personal (pre-shared key).
+    Wpa2,
+
+    /// WPA3 Personal (Simultaneous Authentication of Equals).
+    Wpa3,
+
+    /// WPA2/WPA3 transitional mode, which accepts both WPA2 and WPA3 clients.
+    /// 
+    /// NetworkManager offers SAE alongside the pre-shared key of `wpa-psk`
+    /// hotspots when protected management frames are enabled, so this mode
+    /// uses `key-mgmt=wpa-psk` with optional PMF, which WPA2 clients may
+    /// leave out.
+    Wpa2Wpa3,
+}
+
+impl Security {
+    /// Get the NetworkManager key management setting for this security mode.
+    /// 
+    /// Open hotspots have no `802-11-wireless-security` setting at all, since
+    /// `key-mgmt=none` means static WEP in NetworkManager.
+    /// 
+    /// **Note**: the NetworkManager hotspot API only adds this setting to
+    /// hotspots with a password, so the key management of OWE hotspots must
+    /// be written to the connection afterwards.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Option<&'static str>` containing the value of
+    /// `802-11-wireless-security.key-mgmt`, or `None` if the setting must be
+    /// left out.
+    pub fn key_mgmt(&self) -> Option<&'static str> {
+        use Security::*;
+        match self {
+            Open => None,
+            Owe => Some ("owe"),
+            Wpa2 | Wpa2Wpa3 => Some ("wpa-psk"),
+            Wpa3 => Some ("sae"),
+        }
+    }
+
+    /// Get the authentication type token used in Wi-Fi join QR codes.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `&'static str` containing the value of the `T:` field.
+    pub fn qr_token(&self) -> &'static str {
+        use Security::*;
+        match self {
+            // Join QR codes have no token for OWE, which also needs no password
+            Open | Owe => "nopass",
+            // Transitional networks also accept WPA2-only clients
+            Wpa2 | Wpa2Wpa3 => "WPA",
+            Wpa3 => "SAE",
+        }
+    }
+
+    /// Check if this security mode requires driver support for SAE.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not this mode uses WPA3 (SAE).
+    pub fn requires_sae(&self) -> bool {
+        matches!(self, Security::Wpa3 | Security::Wpa2Wpa3)
+    }
+
+    /// Check if this security mode requires driver support for OWE.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not this mode uses OWE.
+    pub fn requires_owe(&self) -> bool {
+        *self == Security::Owe
+    }
+
+    /// Get the NetworkManager protected management frames setting for this
+    /// security mode.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Option<i32>` containing the value of
+    /// `802-11-wireless-security.pmf`, or `None` if NetworkManager's default
+    /// is kept.
+    pub fn pmf(&self) -> Option<i32> {
+        use Security::*;
+        match self {
+            // OWE requires protected management frames
+            Owe => Some (NM_PMF_REQUIRED),
+            // WPA3 clients of transitional networks must use PMF, but WPA2
+            // clients may not support it
+            Wpa2Wpa3 => Some (NM_PMF_OPTIONAL),
+            Open | Wpa2 | Wpa3 => None,
+        }
+    }
+
+    /// Check if this security mode requires a password.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not this mode uses a pre-shared key.
+    pub fn requires_password(&self) -> bool {
+        !matches!(self, Security::Open | Security::Owe)
+    }
+}
+
+impl FromStr for Security {
+    type Err = ProtonError;
+
+    /// Parse a security mode, case-insensitively.
+    /// 
+    /// Accepted values are `open`, `none`, and the empty string for open
+    /// hotspots, `owe`, `enhanced-open`, and `wpa3-owe` for Enhanced Open,
+    /// `wpa2`, `wpa-psk`, and `psk` for WPA2, `wpa3` and `sae` for WPA3, and
+    /// `wpa2-wpa3` and `wpa2/wpa3` for WPA2/WPA3.
+    /// 
+    /// WEP (`wep`, `wep40`, `wep104`, `wep128`, `wep-open`, `wep-shared`)
+    /// is rejected with `ProtonError::InsecureSecurity`, since its keys can
+    /// be cracked in minutes.  Proton never creates WEP hotspots, even with
+    /// `HotspotConfig::allow_insecure`: the hotspot API only sets a
+    /// pre-shared key, not the WEP keys that such a hotspot would need.
+    fn from_str(security: &str) -> std::result::Result<Self, Self::Err> {
+        match security.trim().to_lowercase().as_str() {
+            "" | "open" | "none" => Ok (Security::Open),
+            "owe" | "enhanced-open" | "wpa3-owe" => Ok (Security::Owe),
+            "wpa2" | "wpa-psk" | "psk" => Ok (Security::Wpa2),
+            "wpa3" | "sae" => Ok (Security::Wpa3),
+            "wpa2-wpa3" | "wpa2/wpa3" => Ok (Security::Wpa2Wpa3),
+            "wep" | "wep40" | "wep104" | "wep128" | "wep-open" | "wep-shared" => Err (ProtonError::InsecureSecurity ("WEP".to_string())),
+            _ => Err (ProtonError::UnknownSecurity (security.to_string())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Security {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Display for Security {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        use Security::*;
+        let security = match self {
+            Open => "open",
+            Owe => "OWE",
+            Wpa2 => "WPA2",
+            Wpa3 => "WPA3",
+            Wpa2Wpa3 => "WPA2/WPA3",
+        };
+
+        write!(f, "{}", security)
+    }
+}
@@ -0,0 +1,248 @@
+//! Testing hotspot configuration defaults.
+
+use std::net::Ipv4Addr;
+
+use proton_cfg::{
+    Band,
+    ConfigChange,
+    HotspotConfig,
+    Security,
+};
+
+use proton_err::ProtonError;
+
+#[test]
+fn default_config() {
+    let config = HotspotConfig::default();
+
+    assert_eq!(config.ssid, "Proton");
+    assert_eq!(config.security, Security::Wpa2);
+    assert_eq!(config.band, Band::TwoFourGhz);
+    assert_eq!(config.gateway, Ipv4Addr::new(192, 168, 0, 1));
+    assert_eq!(config.cidr.to_string(), "192.168.0.0/24");
+    assert!(config.cidr.contains(&config.gateway));
+    assert_eq!(config.ipv6_prefix, None);
+    assert_eq!(config.ipv6_gateway, None);
+}
+
+#[test]
+fn validate_passphrase_length() {
+    let mut config = HotspotConfig::default();
+    assert!(config.validate().is_ok());
+
+    config.pass = "short".to_string();
+    assert!(config.validate().is_err());
+
+    config.pass = "x".repeat(64);
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn open_hotspot_skips_passphrase() {
+    let config: HotspotConfig = (
+        "Cafe".to_string(),
+        String::new(),
+        "192.168.0.0/24".to_string(),
+        "192.168.0.1".to_string(),
+        "open".to_string(),
+        "2.4".to_string(),
+    ).try_into().unwrap();
+
+    assert_eq!(config.security, Security::Open);
+    assert_eq!(config.security.key_mgmt(), None);
+    assert!(!config.security.requires_password());
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn owe_hotspot_skips_passphrase() {
+    let config = HotspotConfig {
+        pass: String::new(),
+        security: Security::Owe,
+        ..Default::default()
+    };
+
+    assert_eq!(config.security.key_mgmt(), Some ("owe"));
+    assert_eq!(config.security.pmf(), Some (3));
+    assert!(config.security.requires_owe());
+    assert!(!config.security.requires_password());
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn reject_unparsable_fields() {
+    let config = |cidr: &str, gateway: &str, security: &str, band: &str| HotspotConfig::try_from((
+        "Cafe".to_string(),
+        "password".to_string(),
+        cidr.to_string(),
+        gateway.to_string(),
+        security.to_string(),
+        band.to_string(),
+    ));
+
+    assert!(config("192.168.0.0/24", "192.168.0.1", "wpa2", "5").is_ok());
+    assert!(matches!(config("192.168.0.0/24", "192.168.0.1", "wpa9", "5"), Err (ProtonError::UnknownSecurity (_))));
+    assert!(matches!(config("192.168.0.0/24", "192.168.0.1", "wpa2", "6ghz"), Err (ProtonError::UnknownBand (_))));
+    assert!(config("192.168.0.0", "192.168.0.1", "wpa2", "5").is_err());
+    assert!(config("192.168.0.0/24", "gateway", "wpa2", "5").is_err());
+}
+
+#[test]
+fn parse_security_aliases() {
+    assert_eq!("WPA2".parse::<Security>().unwrap(), Security::Wpa2);
+    assert_eq!("wpa-psk".parse::<Security>().unwrap(), Security::Wpa2);
+    assert_eq!("psk".parse::<Security>().unwrap(), Security::Wpa2);
+    assert_eq!("SAE".parse::<Security>().unwrap(), Security::Wpa3);
+    assert_eq!("open".parse::<Security>().unwrap(), Security::Open);
+    assert_eq!("wpa3-owe".parse::<Security>().unwrap(), Security::Owe);
+    assert_eq!("Enhanced-Open".parse::<Security>().unwrap(), Security::Owe);
+}
+
+#[test]
+fn transition_mode_makes_pmf_optional() {
+    assert_eq!("wpa2/wpa3".parse::<Security>().unwrap(), Security::Wpa2Wpa3);
+    assert_eq!("WPA2-WPA3".parse::<Security>().unwrap(), Security::Wpa2Wpa3);
+    assert!(matches!("wpa-psk sae".parse::<Security>(), Err (ProtonError::UnknownSecurity (_))));
+
+    assert_eq!(Security::Wpa2Wpa3.key_mgmt(), Some ("wpa-psk"));
+    assert_eq!(Security::Wpa2Wpa3.pmf(), Some (2));
+    assert!(Security::Wpa2Wpa3.requires_sae());
+    assert!(Security::Wpa2Wpa3.requires_password());
+}
+
+#[test]
+fn reject_wep() {
+    for wep in ["wep", "WEP104", "wep-shared"] {
+        let error = wep.parse::<Security>().unwrap_err();
+
+        assert!(matches!(error, ProtonError::InsecureSecurity (_)));
+        assert!(error.to_string().contains("use WPA2 or WPA3"));
+    }
+}
+
+#[test]
+fn reject_open_with_password() {
+    let mut config = HotspotConfig {
+        security: Security::Open,
+        ..Default::default()
+    };
+
+    assert!(matches!(config.validate(), Err (ProtonError::OpenWithPassword)));
+
+    config.allow_insecure = true;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn parse_band_aliases() {
+    assert_eq!("2.4".parse::<Band>().unwrap(), Band::TwoFourGhz);
+    assert_eq!("2.4GHz".parse::<Band>().unwrap(), Band::TwoFourGhz);
+    assert_eq!("bg".parse::<Band>().unwrap(), Band::TwoFourGhz);
+    assert_eq!("5 GHz".parse::<Band>().unwrap(), Band::FiveGhz);
+    assert_eq!("a".parse::<Band>().unwrap(), Band::FiveGhz);
+
+    let error = "6".parse::<Band>().unwrap_err().to_string();
+    assert!(error.contains("expected one of"));
+}
+
+#[test]
+fn band_channels() {
+    assert!(Band::TwoFourGhz.valid_channel(6));
+    assert!(Band::TwoFourGhz.valid_channel(13));
+    assert!(!Band::TwoFourGhz.valid_channel(14));
+    assert!(!Band::TwoFourGhz.valid_channel(36));
+    assert!(Band::FiveGhz.valid_channel(36));
+    assert!(Band::FiveGhz.valid_channel(165));
+    assert!(!Band::FiveGhz.valid_channel(6));
+    assert!(!Band::FiveGhz.valid_channel(38));
+
+    // DFS channels are a documented subset of the legal channels
+    assert!(Band::TwoFourGhz.dfs_channels().is_empty());
+    assert!(Band::FiveGhz.dfs_channels().contains(&52));
+    assert!(!Band::FiveGhz.dfs_channels().contains(&36));
+    assert!(Band::FiveGhz.dfs_channels().iter().all(|channel| Band::FiveGhz.valid_channel(*channel)));
+}
+
+#[test]
+fn band_frequencies() {
+    assert!(Band::TwoFourGhz.contains_frequency(2412));
+    assert!(Band::TwoFourGhz.contains_frequency(2484));
+    assert!(!Band::TwoFourGhz.contains_frequency(5180));
+    assert!(Band::FiveGhz.contains_frequency(5180));
+    assert!(Band::FiveGhz.contains_frequency(5825));
+
+    // 6 GHz channels reuse 2.4 and 5 GHz channel numbers
+    assert!(!Band::TwoFourGhz.contains_frequency(5955));
+    assert!(!Band::FiveGhz.contains_frequency(6135));
+}
+
+#[test]
+fn validate_channel_band() {
+    let mut config = HotspotConfig {
+        channel: Some (36),
+        ..Default::default()
+    };
+
+    assert!(matches!(config.validate(), Err (ProtonError::ChannelNotInBand { channel: 36, .. })));
+
+    config.band = Band::FiveGhz;
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn diff_equal_configs() {
+    let config = HotspotConfig::default();
+
+    assert!(config.diff(&config.clone()).is_empty());
+}
+
+#[test]
+fn diff_band_only() {
+    let old = HotspotConfig::default();
+    let new = HotspotConfig {
+        band: Band::FiveGhz,
+        ..old.clone()
+    };
+
+    assert_eq!(old.diff(&new), [ConfigChange::Band]);
+    assert!(ConfigChange::Band.requires_restart());
+}
+
+#[test]
+fn diff_without_restart() {
+    let old = HotspotConfig::default();
+    let new = HotspotConfig {
+        cidr: "192.168.0.0/23".parse().unwrap(),
+        ipv6_prefix: Some ("fd00:1::/64".parse().unwrap()),
+        ..old.clone()
+    };
+
+    let changes = old.diff(&new);
+
+    assert_eq!(changes, [ConfigChange::Cidr, ConfigChange::Ipv6]);
+    assert!(!changes.iter().any(ConfigChange::requires_restart));
+}
+
+#[test]
+fn diff_channel_requires_restart() {
+    let old = HotspotConfig::default();
+    let new = HotspotConfig {
+        channel: Some (6),
+        ..old.clone()
+    };
+
+    assert_eq!(old.diff(&new), [ConfigChange::Channel]);
+    assert!(ConfigChange::Channel.requires_restart());
+}
+
+#[test]
+fn diff_hidden_requires_restart() {
+    let old = HotspotConfig::default();
+    let new = HotspotConfig {
+        hidden: true,
+        ..old.clone()
+    };
+
+    assert_eq!(old.diff(&new), [ConfigChange::Hidden]);
+    assert!(ConfigChange::Hidden.requires_restart());
+}
@@ -0,0 +1,58 @@
+//! Testing the Wi-Fi join QR code payload.
+
+use proton_cfg::HotspotConfig;
+
+/// Construct a hotspot configuration.
+fn config(ssid: &str, pass: &str, security: &str) -> HotspotConfig {
+    (
+        ssid.to_string(),
+        pass.to_string(),
+        "192.168.0.0/24".to_string(),
+        "192.168.0.1".to_string(),
+        security.to_string(),
+        "2.4".to_string(),
+    ).try_into().unwrap()
+}
+
+#[test]
+fn wpa2_payload() {
+    let payload = config("Proton", "password", "wpa-psk").wifi_qr_payload();
+
+    assert_eq!(&payload, "WIFI:T:WPA;S:Proton;P:password;;");
+}
+
+#[test]
+fn wpa3_payload() {
+    let payload = config("Proton", "password", "sae").wifi_qr_payload();
+
+    assert_eq!(&payload, "WIFI:T:SAE;S:Proton;P:password;;");
+}
+
+#[test]
+fn open_payload() {
+    let payload = config("Cafe", "", "open").wifi_qr_payload();
+
+    assert_eq!(&payload, "WIFI:T:nopass;S:Cafe;;");
+}
+
+#[test]
+fn owe_payload() {
+    let payload = config("Cafe", "", "owe").wifi_qr_payload();
+
+    assert_eq!(&payload, "WIFI:T:nopass;S:Cafe;;");
+}
+
+#[test]
+fn escaped_payload() {
+    let payload = config("a;b,c", "d:e\\f", "wpa-psk").wifi_qr_payload();
+
+    assert_eq!(&payload, r"WIFI:T:WPA;S:a\;b\,c;P:d\:e\\f;;");
+}
+
+#[test]
+fn hidden_payload() {
+    let mut config = config("Proton", "password", "wpa-psk");
+    config.hidden = true;
+
+    assert_eq!(&config.wifi_qr_payload(), "WIFI:T:WPA;S:Proton;P:password;H:true;;");
+}
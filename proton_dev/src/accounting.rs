@@ -0,0 +1,111 @@
+//! Per-device bandwidth accounting.
+
+use std::collections::HashMap;
+
+use proton_mac::MacAddr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Cumulative byte counters for a single device.
+pub struct Counters {
+    /// Total bytes received from the device.
+    pub rx_bytes: u64,
+
+    /// Total bytes transmitted to the device.
+    pub tx_bytes: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+/// Cumulative bandwidth accounting for devices, keyed by MAC address.
+/// 
+/// This structure is independent of how traffic is observed, so byte deltas
+/// may come from Netlink station counters or from a forwarding path.
+/// Station counters are totals since the station connected, so they are
+/// recorded with `Accounting::record_totals`, which computes the deltas.
+pub struct Accounting {
+    /// Byte counters for each device.
+    counters: HashMap<MacAddr, Counters>,
+
+    /// Last station totals of each connected device.
+    totals: HashMap<MacAddr, Counters>,
+}
+
+impl Accounting {
+    /// Construct a new, empty accounting table.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A new `Accounting` with no recorded devices.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record traffic for a device.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address of the device
+    /// - `rx_delta` (`u64`): the number of bytes received since the last record
+    /// - `tx_delta` (`u64`): the number of bytes transmitted since the last record
+    /// 
+    /// # Returns
+    /// None.
+    pub fn record(&mut self, mac: MacAddr, rx_delta: u64, tx_delta: u64) {
+        let counters = self.counters.entry(mac).or_default();
+
+        counters.rx_bytes = counters.rx_bytes.saturating_add(rx_delta);
+        counters.tx_bytes = counters.tx_bytes.saturating_add(tx_delta);
+    }
+
+    /// Record the traffic of a device from its station totals.
+    /// 
+    /// The delta is the difference from the last totals of the device.  A
+    /// device without previous totals, or whose totals decreased (i.e. it
+    /// reconnected and its counters were reset), is recorded in full.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address of the device
+    /// - `rx_total` (`u64`): the number of bytes received since the device connected
+    /// - `tx_total` (`u64`): the number of bytes transmitted since the device connected
+    /// 
+    /// # Returns
+    /// None.
+    pub fn record_totals(&mut self, mac: MacAddr, rx_total: u64, tx_total: u64) {
+        let totals = Counters {
+            rx_bytes: rx_total,
+            tx_bytes: tx_total,
+        };
+        let last = self.totals.insert(mac, totals).unwrap_or_default();
+
+        // Check if the counters were reset
+        let delta = |total: u64, last: u64| total.checked_sub(last).unwrap_or(total);
+
+        self.record(mac, delta(rx_total, last.rx_bytes), delta(tx_total, last.tx_bytes));
+    }
+
+    /// Discard the station totals of devices that are no longer connected,
+    /// so that their next totals are recorded in full.  Their counters
+    /// are kept.
+    /// 
+    /// # Parameters
+    /// - `connected` (`&[MacAddr]`): the MAC addresses of connected devices
+    /// 
+    /// # Returns
+    /// None.
+    pub fn retain(&mut self, connected: &[MacAddr]) {
+        self.totals.retain(|mac, _| connected.contains(mac));
+    }
+
+    /// Get the counters of every recorded device.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `Vec<(MacAddr, Counters)>` containing the current counters of each device.
+    pub fn snapshot(&self) -> Vec<(MacAddr, Counters)> {
+        self.counters.iter()
+            .map(|(mac, counters)| (*mac, *counters))
+            .collect()
+    }
+}
@@ -1,6 +1,15 @@
 //! Connected device data.
 
-use std::net::Ipv4Addr;
+use std::{
+    net::{
+        Ipv4Addr,
+        Ipv6Addr,
+    },
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
 
 use nl80211::{
     parse_i8,
@@ -12,9 +21,16 @@ use serde::Serialize;
 
 use proton_arp::ArpManager;
 
+use proton_err::ProtonResult;
+
 use proton_mac::MacAddr;
 
-#[derive(Serialize, Clone, Copy, Debug)]
+use crate::{
+    frequency_to_channel,
+    StationFlags,
+};
+
+#[derive(Serialize, Clone, Debug)]
 /// Information about a connected network device.
 pub struct Device {
     /// MAC address of the device.
@@ -23,38 +39,276 @@ pub struct Device {
     /// IPv4 address of the device.
     pub ipv4: Ipv4Addr,
 
+    /// IPv6 address of the device, if it was resolved (see
+    /// `DeviceManager::resolve_ipv6`).
+    pub ipv6: Option<Ipv6Addr>,
+
     /// Signal strength of last received signal, in dBm.
     pub signal_strength: i8,
 
+    /// Average signal strength of recent scans, in dBm.
+    /// 
+    /// This equals `signal_strength` unless signal smoothing is enabled (see
+    /// `DeviceManager::set_signal_smoothing`).
+    pub signal_avg: i8,
+
+    /// Transmit bitrate negotiated with the device, in Mbit/s (`0.0` if
+    /// unavailable).
+    pub tx_bitrate_mbps: f32,
+
+    /// Receive bitrate negotiated with the device, in Mbit/s (`0.0` if
+    /// unavailable).
+    pub rx_bitrate_mbps: f32,
+
+    /// Total bytes received from the device since it connected (`0` if
+    /// unavailable).
+    pub rx_bytes: u64,
+
+    /// Total bytes transmitted to the device since it connected (`0` if
+    /// unavailable).
+    pub tx_bytes: u64,
+
     /// Time since the device was connected, in seconds.
     pub connection_time: u32,
+
+    /// Time since the device was last active, in milliseconds (`0` if
+    /// unavailable).
+    pub inactive_ms: u32,
+
+    /// Whether or not the device is authorized, i.e. has completed the
+    /// handshake (`false` if unavailable).
+    pub authorized: bool,
+
+    /// Flags of the device reported by `nl80211` (empty if unavailable).
+    pub flags: StationFlags,
+
+    /// Wall-clock time at which the device connected, as of the scan (the
+    /// Unix epoch if the clock reads earlier than the connection time).
+    pub connected_since: SystemTime,
+
+    /// Operating frequency of the device, in MHz (`0` if unavailable).
+    pub frequency_mhz: u32,
+
+    /// Operating channel of the device (`0` if unavailable).
+    pub channel: u8,
+
+    /// Whether or not the device is using a randomized (locally administered)
+    /// MAC address.
+    pub randomized: bool,
+
+    /// Hostname of the device, if it was resolved (see
+    /// `DeviceManager::resolve_hostnames`).
+    pub hostname: Option<String>,
+
+    /// The `nl80211` station that this device was converted from, if it was
+    /// kept (see `Device::from_station_full`).  This is not serialized.
+    #[serde(skip)]
+    pub station: Option<Station>,
 }
 
 impl Device {
     /// Convert a `Station` into a `Device` by checking the ARP cache.
-    pub fn from_station(station: Station, arp: &ArpManager) -> Self {
+    /// 
+    /// # Parameters
+    /// - `station` (`Station`): the station reported by `nl80211`
+    /// - `arp` (`&ArpManager`): the ARP manager used to look up the IPv4 address
+    /// - `frequency_mhz` (`u32`): the operating frequency of the interface
+    ///   that the station is connected to, in MHz
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Device>` containing the device corresponding to the
+    /// station, or `ProtonError::InvalidMacAddrLength` if the station's MAC
+    /// address is missing or malformed.
+    pub fn from_station(station: Station, arp: &ArpManager, frequency_mhz: u32) -> ProtonResult<Self> {
+        Self::parse_station(&station, arp, frequency_mhz)
+    }
+
+    /// Convert a `Station` into a `Device` by checking the ARP cache, keeping
+    /// the station for attributes that `Device` does not parse.
+    /// 
+    /// # Parameters
+    /// - `station` (`Station`): the station reported by `nl80211`
+    /// - `arp` (`&ArpManager`): the ARP manager used to look up the IPv4 address
+    /// - `frequency_mhz` (`u32`): the operating frequency of the interface
+    ///   that the station is connected to, in MHz
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Device>` containing the device corresponding to the
+    /// station, or `ProtonError::InvalidMacAddrLength` if the station's MAC
+    /// address is missing or malformed.
+    pub fn from_station_full(station: Station, arp: &ArpManager, frequency_mhz: u32) -> ProtonResult<Self> {
+        let mut device = Self::parse_station(&station, arp, frequency_mhz)?;
+        device.station = Some (station);
+
+        Ok (device)
+    }
+
+    /// Get the `nl80211` station that this device was converted from.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Option<&Station>` containing the station, if it was kept (see
+    /// `Device::from_station_full` and `DeviceManager::set_keep_stations`).
+    pub fn raw_station(&self) -> Option<&Station> {
+        self.station.as_ref()
+    }
+
+    /// Convert a borrowed `Station` into a `Device`, without keeping it.
+    fn parse_station(station: &Station, arp: &ArpManager, frequency_mhz: u32) -> ProtonResult<Self> {
         // Get hardware address of the station
-        let mac: MacAddr = station.bssid
-            .unwrap_or_default()
-            .try_into()
-            .unwrap_or([0; 6])
-            .into();
+        let mac = MacAddr::try_from(station.bssid.as_deref().unwrap_or_default())?;
 
         // Get IPv4 address of the station
         let ipv4: Ipv4Addr = arp.lookup_mac(mac)
             .unwrap_or(Ipv4Addr::new(0, 0, 0, 0));
 
         // Get signal strength of this station
-        let signal_strength: i8 = parse_i8(&station.signal.unwrap_or_default());
+        let signal_strength: i8 = parse_i8(&station.signal.clone().unwrap_or_default());
+
+        // Get transmit bitrate of this station
+        // Note: `Station` only keeps the 16-bit `NL80211_RATE_INFO_BITRATE`,
+        //  in units of 100 kbit/s, and not the receive bitrate (see
+        //  `StationEntry`, which has both)
+        let tx_bitrate_mbps = station.tx_bitrate.as_deref()
+            .and_then(|bitrate| bitrate.get(0..2)?.try_into().ok())
+            .map_or(0.0, |bitrate| u16::from_ne_bytes(bitrate) as f32 / 10.0);
 
         // Get connection time of this station
-        let connection_time: u32 = parse_u32(&station.connected_time.unwrap_or_default());
+        let connection_time: u32 = parse_u32(&station.connected_time.clone().unwrap_or_default());
 
-        Self {
+        // Get the time at which this station connected
+        // Note: the subtraction underflows if the clock reads earlier than
+        //  the connection time (e.g. before it is synchronized), in which
+        //  case the Unix epoch is used
+        let connected_since = SystemTime::now()
+            .checked_sub(Duration::from_secs(connection_time as u64))
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        Ok (Self {
             mac,
             ipv4,
+            ipv6: None,
             signal_strength,
+            signal_avg: signal_strength,
+            tx_bitrate_mbps,
+            rx_bitrate_mbps: 0.0,
+            rx_bytes: 0,
+            tx_bytes: 0,
             connection_time,
-        }
+            inactive_ms: 0,
+            authorized: false,
+            flags: StationFlags::empty(),
+            connected_since,
+            frequency_mhz,
+            channel: frequency_to_channel(frequency_mhz),
+            randomized: mac.is_locally_administered(),
+            hostname: None,
+            station: None,
+        })
+    }
+
+    /// Get the signal quality of this device as a percentage.
+    /// 
+    /// The quality increases linearly from 0% at -100 dBm to 100% at
+    /// -50 dBm (i.e. `2 * (dBm + 100)`), and is clamped outside of that range.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `u8` containing the signal quality, from `0` to `100`.
+    pub fn signal_quality(&self) -> u8 {
+        (2 * (self.signal_strength as i16 + 100)).clamp(0, 100) as u8
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+/// A lightweight summary of a connected client, without network addresses.
+pub struct ClientSummary {
+    /// MAC address of the client.
+    pub mac: MacAddr,
+
+    /// Signal strength of last received signal, in dBm.
+    pub signal_strength: i8,
+
+    /// Time since the client was connected, in seconds.
+    pub connection_time: u32,
+}
+
+impl ClientSummary {
+    /// Convert a `Station` into a `ClientSummary`, without checking the ARP cache.
+    /// 
+    /// # Parameters
+    /// - `station` (`&Station`): the station reported by `nl80211`
+    /// 
+    /// # Returns
+    /// A `ProtonResult<ClientSummary>` containing the summary of the
+    /// station, or `ProtonError::InvalidMacAddrLength` if the station's MAC
+    /// address is missing or malformed.
+    pub fn from_station(station: &Station) -> ProtonResult<Self> {
+        Ok (Self {
+            mac: MacAddr::try_from(station.bssid.as_deref().unwrap_or_default())?,
+            signal_strength: parse_i8(&station.signal.clone().unwrap_or_default()),
+            connection_time: parse_u32(&station.connected_time.clone().unwrap_or_default()),
+        })
     }
-}
\ No newline at end of file
+}
+
+/// Serialize a list of devices into a JSON array.
+/// 
+/// # Parameters
+/// - `devices` (`&[Device]`): the devices to serialize
+/// 
+/// # Returns
+/// A `ProtonResult<String>` containing the JSON array, if the devices
+/// could be serialized.
+pub fn devices_to_json(devices: &[Device]) -> ProtonResult<String> {
+    Ok (serde_json::to_string(devices)?)
+}
+
+/// Header row of `devices_to_csv`.
+const CSV_HEADER: &str = "mac,ipv4,signal_strength,connection_time,vendor,hostname";
+
+/// Serialize a list of devices into CSV, with a header row.
+/// 
+/// Columns are always in the order `mac`, `ipv4`, `signal_strength`,
+/// `connection_time`, `vendor`, `hostname`.  Unresolved hostnames are empty.
+/// The `vendor` column is always empty, since Proton has no OUI database to
+/// look vendors up in; it is kept so that spreadsheets built on this column
+/// order keep working once one is available.
+/// 
+/// # Parameters
+/// - `devices` (`&[Device]`): the devices to serialize
+/// 
+/// # Returns
+/// A `String` containing the CSV document, with one line per device.
+pub fn devices_to_csv(devices: &[Device]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push_str("\r\n");
+
+    for device in devices {
+        let hostname = device.hostname.as_deref().unwrap_or_default();
+
+        csv.push_str(&format!(
+            "{},{},{},{},,{}\r\n",
+            device.mac,
+            device.ipv4,
+            device.signal_strength,
+            device.connection_time,
+            csv_field(hostname),
+        ));
+    }
+
+    csv
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
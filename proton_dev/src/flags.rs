@@ -0,0 +1,102 @@
+//! Station flags reported by `nl80211`.
+
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// A set of station flags (`enum nl80211_sta_flags`), such as whether or not
+/// the station is authorized.
+pub struct StationFlags (u32);
+
+impl StationFlags {
+    /// The station is authorized to send and receive data frames.
+    pub const AUTHORIZED: Self = Self (1 << 1);
+
+    /// The station uses short preambles.
+    pub const SHORT_PREAMBLE: Self = Self (1 << 2);
+
+    /// The station supports WMM/WME (QoS).
+    pub const WME: Self = Self (1 << 3);
+
+    /// The station uses management frame protection.
+    pub const MFP: Self = Self (1 << 4);
+
+    /// The station is authenticated.
+    pub const AUTHENTICATED: Self = Self (1 << 5);
+
+    /// The station is a TDLS peer.
+    pub const TDLS_PEER: Self = Self (1 << 6);
+
+    /// The station is associated.
+    pub const ASSOCIATED: Self = Self (1 << 7);
+
+    /// Construct an empty set of station flags.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `StationFlags` with no flags set.
+    pub const fn empty() -> Self {
+        Self (0)
+    }
+
+    /// Parse station flags from an `NL80211_STA_INFO_STA_FLAGS` payload.
+    /// 
+    /// The payload is a `struct nl80211_sta_flag_update`, i.e. a mask of the
+    /// flags reported by the driver, followed by the flags that are set.
+    /// Flags outside of the mask are not set.
+    /// 
+    /// # Parameters
+    /// - `payload` (`&[u8]`): the attribute payload, in native byte order
+    /// 
+    /// # Returns
+    /// An `Option<StationFlags>` containing the flags, if the payload is at
+    /// least 8 bytes long.
+    pub fn from_flag_update(payload: &[u8]) -> Option<Self> {
+        let mask = u32::from_ne_bytes(payload.get(0..4)?.try_into().ok()?);
+        let set = u32::from_ne_bytes(payload.get(4..8)?.try_into().ok()?);
+
+        Some (Self (mask & set))
+    }
+
+    /// Get the raw bits of these flags.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `u32` with bit `n` set for each set flag `n` of `enum nl80211_sta_flags`.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Check if all of the given flags are set.
+    /// 
+    /// # Parameters
+    /// - `flags` (`StationFlags`): the flags to check
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not every flag in `flags` is set.
+    pub fn contains(&self, flags: StationFlags) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+
+    /// Check if the station is authorized.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not `StationFlags::AUTHORIZED` is set.
+    pub fn authorized(&self) -> bool {
+        self.contains(Self::AUTHORIZED)
+    }
+}
+
+impl std::ops::BitOr for StationFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self (self.0 | rhs.0)
+    }
+}
@@ -0,0 +1,88 @@
+//! Hostname-based access policy.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A policy that permits or denies devices by their resolved hostnames.
+/// 
+/// Patterns are globs, matched case-insensitively against the whole
+/// hostname: `*` matches any sequence of characters and `?` matches any
+/// single character (e.g. `*-iphone`).
+/// 
+/// **Note**: devices choose their own hostnames, so any device can claim a
+/// permitted name.  This policy is a convenience for operators who think in
+/// device names, not a security boundary.
+pub enum HostnamePolicy {
+    /// Permit only devices whose hostname matches a pattern.  Devices without
+    /// a resolved hostname are denied.
+    Allow (Vec<String>),
+
+    /// Deny devices whose hostname matches a pattern.  Devices without a
+    /// resolved hostname are permitted.
+    Deny (Vec<String>),
+}
+
+impl HostnamePolicy {
+    /// Check if this policy permits a device.
+    /// 
+    /// # Parameters
+    /// - `hostname` (`Option<&str>`): the resolved hostname of the device,
+    ///   if any
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the device is permitted.
+    pub fn permits(&self, hostname: Option<&str>) -> bool {
+        use HostnamePolicy::*;
+        match (self, hostname) {
+            (Allow (patterns), Some (hostname)) => matches_any(patterns, hostname),
+            (Allow (_), None) => false,
+            (Deny (patterns), Some (hostname)) => !matches_any(patterns, hostname),
+            (Deny (_), None) => true,
+        }
+    }
+}
+
+/// Check if a hostname matches any of a list of glob patterns.
+fn matches_any(patterns: &[String], hostname: &str) -> bool {
+    patterns.iter().any(|pattern| glob_match(pattern, hostname))
+}
+
+/// Match a hostname against a glob pattern, case-insensitively.
+/// 
+/// # Parameters
+/// - `pattern` (`&str`): the glob pattern, where `*` matches any sequence
+///   of characters and `?` matches any single character
+/// - `hostname` (`&str`): the hostname to match
+/// 
+/// # Returns
+/// A `bool` indicating whether or not the whole hostname matches the pattern.
+pub fn glob_match(pattern: &str, hostname: &str) -> bool {
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<char>>();
+    let hostname = hostname.to_lowercase().chars().collect::<Vec<char>>();
+
+    // Greedy matching, backtracking to the last `*` on a mismatch
+    let (mut p, mut h) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while h < hostname.len() {
+        match pattern.get(p) {
+            Some ('*') => {
+                star = Some ((p, h));
+                p += 1;
+            },
+            Some (c) if *c == '?' || *c == hostname[h] => {
+                p += 1;
+                h += 1;
+            },
+            _ => match star {
+                Some ((star_p, star_h)) => {
+                    star = Some ((star_p, star_h + 1));
+                    p = star_p + 1;
+                    h = star_h + 1;
+                },
+                None => return false,
+            },
+        }
+    }
+
+    // Only trailing stars may remain
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -3,12 +3,68 @@
 #![deny(warnings)]
 #![deny(missing_docs)]
 
+mod accounting;
 mod device;
+mod flags;
+mod hostname;
 mod manager;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod presence;
+mod reason;
+mod signal;
 mod socket;
+mod survey;
 
-pub use device::Device;
+pub use accounting::{
+    Accounting,
+    Counters,
+};
+
+pub use device::{
+    ClientSummary,
+    Device,
+    devices_to_csv,
+    devices_to_json,
+};
+
+pub use flags::StationFlags;
+
+pub use hostname::{
+    glob_match,
+    HostnamePolicy,
+};
 
 pub use manager::DeviceManager;
 
-pub use socket::NetworkSocket;
\ No newline at end of file
+#[cfg(feature = "metrics")]
+pub use metrics::Metrics;
+
+pub use presence::{
+    DEFAULT_ABSENCE_THRESHOLD,
+    Presence,
+    PresenceTracker,
+};
+
+pub use reason::ReasonCode;
+
+pub use signal::{
+    DEFAULT_SIGNAL_HISTORY_LEN,
+    SignalHistory,
+};
+
+pub use socket::{
+    NetworkSocket,
+    parse_bitrate,
+    parse_max_tx_power,
+    RetryPolicy,
+    ScanEvents,
+    StationEntry,
+};
+
+pub use survey::{
+    ApInfo,
+    ApSecurity,
+    best_channel,
+    frequency_to_channel,
+};
@@ -1,27 +1,80 @@
 //! Device discovery manager.
 
-use cidr::Ipv4Cidr;
+use std::{
+    io::ErrorKind,
+    net::{
+        IpAddr,
+        Ipv4Addr,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use cidr::{
+    Ipv4Cidr,
+    Ipv6Cidr,
+};
 
 use nl80211::{
     Interface,
     parse_string,
+    parse_u32,
     Socket,
 };
 
-use proton_arp::ArpManager;
+use pnet::datalink::interfaces;
+
+use tokio::time;
+
+use proton_arp::{
+    ArpManager,
+    NdpEntry,
+    ARP_READ_TIMEOUT,
+};
 
 use proton_err::{
     ProtonError,
     ProtonResult,
 };
 
-// use proton_mac::MacAddr;
+use proton_mac::MacAddr;
 
 use crate::{
+    Accounting,
+    ApInfo,
+    ClientSummary,
     Device,
+    HostnamePolicy,
     NetworkSocket,
+    ReasonCode,
+    RetryPolicy,
+    ScanEvents,
+    SignalHistory,
 };
 
+#[cfg(feature = "metrics")]
+use crate::Metrics;
+
+/// The 2.4 GHz band (`NL80211_BAND_2GHZ`).
+const NL80211_BAND_2GHZ: u16 = 0;
+
+/// The 5 GHz band (`NL80211_BAND_5GHZ`).
+const NL80211_BAND_5GHZ: u16 = 1;
+
+/// Number of attempts to trigger a site survey while the driver is busy.
+pub const SURVEY_TRIGGER_ATTEMPTS: usize = 5;
+
+/// Delay between attempts to trigger a site survey.
+pub static SURVEY_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Time to wait for the driver to complete a site survey.
+pub static SURVEY_SCAN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interval at which the end of a site survey is polled.
+pub static SURVEY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// A device manager abstraction.
 /// 
 /// This structure is responsible for performing network device discovery by
@@ -36,6 +89,28 @@ pub struct DeviceManager {
 
     /// ARP cache manager.
     arp_manager: ArpManager,
+
+    /// Retry policy of Netlink station and interface queries.
+    retry: RetryPolicy,
+
+    /// Whether or not `scan` resolves devices missing from the ARP cache.
+    resolve_missing: bool,
+
+    /// Signal strength history of connected devices, if smoothing is enabled.
+    signal_history: Option<SignalHistory>,
+
+    /// Whether or not `prune_inactive` deauthenticates inactive devices.
+    prune_deauth: bool,
+
+    /// Whether or not devices keep the `nl80211` station they were converted from.
+    keep_stations: bool,
+
+    /// Bandwidth accounting of connected devices.
+    accounting: Accounting,
+
+    /// Counters and gauges of this device manager.
+    #[cfg(feature = "metrics")]
+    metrics: Metrics,
 }
 
 impl DeviceManager {
@@ -44,7 +119,7 @@ impl DeviceManager {
     /// # Parameters
     /// - `range` (`Ipv4Cidr`): the CIDR range of the network
     /// - `wlifname` (`&str`): the name of the wireless interface
-    /// over which to scan for connected devices
+    ///   over which to scan for connected devices
     /// 
     /// # Returns
     /// The result type `ProtonResult<DeviceManager>` containing the device
@@ -54,9 +129,95 @@ impl DeviceManager {
             socket: Socket::connect()?,
             wlifname: wlifname.to_string(),
             arp_manager: ArpManager::new(range, wlifname),
+            retry: RetryPolicy::default(),
+            resolve_missing: false,
+            signal_history: None,
+            prune_deauth: false,
+            keep_stations: false,
+            accounting: Accounting::new(),
+            #[cfg(feature = "metrics")]
+            metrics: Metrics::default(),
         })
     }
 
+    /// Set the CIDR range of the network, clearing the ARP cache.
+    /// 
+    /// # Parameters
+    /// - `range` (`Ipv4Cidr`): the new CIDR range of the network
+    /// 
+    /// # Returns
+    /// None.
+    pub fn set_range(&mut self, range: Ipv4Cidr) {
+        self.arp_manager.set_range(range);
+    }
+
+    /// Set the retry policy of Netlink station and interface queries.
+    /// 
+    /// # Parameters
+    /// - `retry` (`RetryPolicy`): the new retry policy
+    /// 
+    /// # Returns
+    /// None.
+    pub fn set_retry_policy(&mut self, retry: RetryPolicy) {
+        self.retry = retry;
+    }
+
+    /// Set whether or not `scan` resolves devices missing from the ARP cache.
+    /// 
+    /// When enabled, a scan that finds stations without an IPv4 address
+    /// (e.g. clients that have just joined) follows up with a targeted
+    /// resolve, at the cost of a second ARP sweep.
+    /// 
+    /// # Parameters
+    /// - `resolve_missing` (`bool`): whether or not to resolve missing devices
+    /// 
+    /// # Returns
+    /// None.
+    pub fn set_resolve_missing(&mut self, resolve_missing: bool) {
+        self.resolve_missing = resolve_missing;
+    }
+
+    /// Set the number of signal strength samples averaged per device.
+    /// 
+    /// When enabled, each scan records the signal strength of every device
+    /// and reports the average of its last `samples` scans as
+    /// `Device::signal_avg`.  Devices that disconnect are forgotten.
+    /// 
+    /// # Parameters
+    /// - `samples` (`Option<usize>`): the number of samples to average, or
+    ///   `None` to disable smoothing
+    /// 
+    /// # Returns
+    /// None.
+    pub fn set_signal_smoothing(&mut self, samples: Option<usize>) {
+        self.signal_history = samples.map(SignalHistory::new);
+    }
+
+    /// Set whether or not `prune_inactive` deauthenticates inactive devices.
+    /// 
+    /// # Parameters
+    /// - `prune_deauth` (`bool`): whether or not to deauthenticate inactive
+    ///   devices (disabled by default)
+    /// 
+    /// # Returns
+    /// None.
+    pub fn set_prune_deauth(&mut self, prune_deauth: bool) {
+        self.prune_deauth = prune_deauth;
+    }
+
+    /// Set whether or not devices keep the `nl80211` station they were
+    /// converted from (see `Device::raw_station`).
+    /// 
+    /// # Parameters
+    /// - `keep_stations` (`bool`): whether or not to keep stations (disabled
+    ///   by default)
+    /// 
+    /// # Returns
+    /// None.
+    pub fn set_keep_stations(&mut self, keep_stations: bool) {
+        self.keep_stations = keep_stations;
+    }
+
     /// Get a list of connected devices.
     /// 
     /// # Parameters
@@ -64,28 +225,693 @@ impl DeviceManager {
     /// 
     /// # Returns
     /// The result type `ProtonResult<Vec<Device>>` containing a list of
-    /// connected devices.
+    /// connected devices.  Stations without a valid MAC address are skipped.
     pub async fn scan(&mut self) -> ProtonResult<Vec<Device>> {
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+
         // Perform an ARP scan of the network to get IPs
         self.arp_manager.scan().await?;
 
-        // Determine Wi-Fi device by name
-        let check_wifi_device = |iface: &Interface| parse_string(&iface.name.clone().unwrap_or_default()).trim_end_matches('\0') == self.wlifname;
+        #[cfg(feature = "metrics")]
+        self.metrics.record_scan(start.elapsed());
+
+        let mut devices = self.stations().await?;
+
+        // Resolve stations that did not reply to the ARP scan
+        if self.resolve_missing {
+            self.fill_missing(&mut devices).await?;
+        }
+
+        Ok (devices)
+    }
+
+    /// Get the counters and gauges of this device manager.
+    /// 
+    /// Client gauges are updated whenever connected stations are queried,
+    /// and the scan metrics whenever `DeviceManager::scan` completes.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `&Metrics` reference to the metrics.
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Get the bandwidth accounting of connected devices.
+    /// 
+    /// The counters are updated from the station byte totals whenever
+    /// connected stations are queried (e.g. by `DeviceManager::scan`), so
+    /// traffic between the last query and a disconnection is not counted.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `&Accounting` reference to the accounting.
+    pub fn accounting(&self) -> &Accounting {
+        &self.accounting
+    }
 
+    /// Get a list of connected devices, without scanning the network.
+    /// 
+    /// This skips the ARP scan, which dominates the latency of
+    /// `DeviceManager::scan` on large networks, so it suits callers that only
+    /// need signal strength and connection times.  IPv4 addresses are looked
+    /// up in the ARP cache, so they are unspecified (`0.0.0.0`) for devices
+    /// that did not reply to an earlier scan.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Vec<Device>>` containing a list of
+    /// connected devices.
+    pub async fn scan_stations_only(&mut self) -> ProtonResult<Vec<Device>> {
+        self.stations().await
+    }
+
+    /// Get a summary of each connected client, without scanning the network.
+    /// 
+    /// This only queries the `nl80211` station dump, so it is much cheaper
+    /// than `DeviceManager::scan`, but it does not include network addresses.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Vec<ClientSummary>>` containing a
+    /// summary of each connected client.  Stations without a valid MAC
+    /// address are skipped.
+    pub async fn clients(&mut self) -> ProtonResult<Vec<ClientSummary>> {
         // Get the Wi-Fi device
-        let interface = self.socket.get_interfaces_info()?
-            .into_iter()
-            .find(check_wifi_device)
+        let interface = self.get_interface().await?;
+        let index = interface.index
             .ok_or(ProtonError::CouldNotFindWirelessInterface)?;
 
         // Get all stations
-        let stations = self.socket.get_all_stations(&interface.index.unwrap())?;
+        let stations = self.retry.run(|| self.socket.get_all_stations(&index)).await?;
+
+        let clients = stations.iter()
+            .filter_map(|station| ClientSummary::from_station(station).ok())
+            .collect();
+
+        Ok (clients)
+    }
+
+    /// Get a single connected device, without scanning the network.
+    /// 
+    /// The IPv4 address of the device is looked up in the ARP cache, so it is
+    /// unspecified (`0.0.0.0`) if the device did not reply to the last scan.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address of the device
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Option<Device>>` containing the device,
+    /// or `None` if it is not currently associated.
+    pub async fn get_device(&mut self, mac: MacAddr) -> ProtonResult<Option<Device>> {
+        let device = self.stations().await?
+            .into_iter()
+            .find(|device| device.mac == mac);
+
+        Ok (device)
+    }
+
+    /// Resolve the IPv4 address of a single connected device.
+    /// 
+    /// This sends a targeted ARP resolve for the device and updates the
+    /// ARP cache with the replies.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address of the device
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Option<Ipv4Addr>>` containing the IPv4
+    /// address of the device, if it replied.
+    pub async fn resolve_one(&mut self, mac: MacAddr) -> ProtonResult<Option<Ipv4Addr>> {
+        self.arp_manager.resolve_mac(mac).await
+    }
+
+    /// Broadcast a gratuitous ARP announcement of an IPv4 address from the
+    /// wireless interface.
+    /// 
+    /// # Parameters
+    /// - `ipv4` (`Ipv4Addr`): the IPv4 address to announce
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Option<MacAddr>>` containing the MAC
+    /// address of another device using `ipv4`, if one replied (see
+    /// `ArpManager::announce`).
+    pub async fn announce(&mut self, ipv4: Ipv4Addr) -> ProtonResult<Option<MacAddr>> {
+        // Get the MAC address of the Wi-Fi device
+        let interface = self.get_interface().await?;
+        let mac = MacAddr::try_from(interface.mac.unwrap_or_default().as_slice())?;
+
+        self.arp_manager.announce(ipv4, mac).await
+    }
+
+    /// Get a list of connected devices, giving up after `timeout`.
+    /// 
+    /// The timeout bounds the whole operation: the ARP scan, including the
+    /// ARP reply listener (see `ArpManager::scan_with_timeout`), and the
+    /// station query that follows, including its retries.  The ARP scan is
+    /// given one read timeout (`ARP_READ_TIMEOUT`) less, so that its listener
+    /// can stop and the replies received so far are merged into the ARP
+    /// cache before the timeout is reported.
+    /// 
+    /// # Parameters
+    /// - `timeout` (`Duration`): the maximum duration of the scan
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Vec<Device>>` containing a list of
+    /// connected devices, or `ProtonError::ScanTimedOut` if the scan could
+    /// not complete in time.
+    pub async fn scan_with_timeout(&mut self, timeout: Duration) -> ProtonResult<Vec<Device>> {
+        let arp_timeout = timeout.saturating_sub(ARP_READ_TIMEOUT);
+
+        let scan = async {
+            // Perform an ARP scan of the network to get IPs
+            self.arp_manager.scan_with_timeout(arp_timeout).await?;
+
+            self.stations().await
+        };
+
+        time::timeout(timeout, scan).await
+            .map_err(|_| ProtonError::ScanTimedOut)?
+    }
+
+    /// Fill in the IPv4 addresses of devices missing from the ARP cache.
+    async fn fill_missing(&mut self, devices: &mut [Device]) -> ProtonResult<()> {
+        // A single resolve updates the cache for every device that replies
+        let missing = devices.iter()
+            .find(|device| device.ipv4.is_unspecified())
+            .map(|device| device.mac);
+
+        if let Some (mac) = missing {
+            self.resolve_one(mac).await?;
+
+            for device in devices.iter_mut().filter(|device| device.ipv4.is_unspecified()) {
+                if let Some (ipv4) = self.arp_manager.lookup_mac(device.mac) {
+                    device.ipv4 = ipv4;
+                }
+            }
+        }
+
+        Ok (())
+    }
+
+    /// Convert all stations connected to the wireless interface into devices.
+    /// 
+    /// Stations without a valid MAC address cannot be addressed, so they are
+    /// skipped rather than failing the whole conversion.
+    async fn stations(&mut self) -> ProtonResult<Vec<Device>> {
+        // Get the Wi-Fi device
+        let interface = self.get_interface().await?;
+
+        // Get all stations, with their statistics
+        let index = interface.index
+            .ok_or(ProtonError::InterfaceNotReady (self.wlifname.clone()))?;
+        let entries = self.retry.run(|| self.socket.get_station_entries(&index)).await?;
+
+        // Get the operating frequency of the Wi-Fi device
+        let frequency_mhz = parse_u32(&interface.frequency.unwrap_or_default());
 
         // Convert each station into a native device structure
-        let devices = stations.into_iter()
-            .map(|station| Device::from_station(station, &self.arp_manager))
-            .collect::<Vec<Device>>();
+        let convert = if self.keep_stations {
+            Device::from_station_full
+        } else {
+            Device::from_station
+        };
+        let mut devices = Vec::with_capacity(entries.len());
+        for entry in entries {
+            // Skip stations with a missing or malformed MAC address
+            let Ok (mut device) = convert(entry.station, &self.arp_manager, frequency_mhz) else {
+                continue;
+            };
+            device.inactive_ms = entry.inactive_ms.unwrap_or_default();
+            if let Some (tx_bitrate_mbps) = entry.tx_bitrate_mbps {
+                device.tx_bitrate_mbps = tx_bitrate_mbps;
+            }
+            device.rx_bitrate_mbps = entry.rx_bitrate_mbps.unwrap_or_default();
+            device.rx_bytes = entry.rx_bytes.unwrap_or_default();
+            device.tx_bytes = entry.tx_bytes.unwrap_or_default();
+            if let Some (flags) = entry.flags {
+                device.authorized = flags.authorized();
+                device.flags = flags;
+            }
+
+            devices.push(device);
+        }
+
+        let connected = devices.iter()
+            .map(|device| device.mac)
+            .collect::<Vec<MacAddr>>();
+
+        // Account for the traffic of each device since the last scan
+        self.accounting.retain(&connected);
+        for device in devices.iter() {
+            self.accounting.record_totals(device.mac, device.rx_bytes, device.tx_bytes);
+        }
+
+        // Smooth the signal strength of each device
+        if let Some (history) = &mut self.signal_history {
+            history.retain(&connected);
+
+            for device in devices.iter_mut() {
+                device.signal_avg = history.record(device.mac, device.signal_strength);
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        self.metrics.record_clients(&devices);
 
         Ok (devices)
     }
+
+    /// Resolve the hostnames of a list of devices over mDNS.
+    /// 
+    /// This waits at most `timeout` for responses, so a slow network cannot
+    /// stall the caller.  Devices that do not respond keep their current
+    /// hostname.
+    /// 
+    /// # Parameters
+    /// - `devices` (`&mut [Device]`): the devices to resolve
+    /// - `timeout` (`Duration`): the time to wait for responses
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<()>` indicating whether or not the
+    /// mDNS query could be sent.
+    pub async fn resolve_hostnames(&self, devices: &mut [Device], timeout: Duration) -> ProtonResult<()> {
+        // Collect the known IPv4 addresses
+        let ips = devices.iter()
+            .map(|device| device.ipv4)
+            .filter(|ipv4| !ipv4.is_unspecified())
+            .collect::<Vec<Ipv4Addr>>();
+
+        if ips.is_empty() {
+            return Ok (());
+        }
+
+        // Query their hostnames on the wireless interface
+        let hostnames = proton_mdns::resolve(&ips, self.interface_ipv4()?, timeout).await?;
+
+        // Merge the hostnames into the devices
+        for device in devices.iter_mut() {
+            if let Some (hostname) = hostnames.get(&device.ipv4) {
+                device.hostname = Some (hostname.clone());
+            }
+        }
+
+        Ok (())
+    }
+
+    /// Deauthenticate the devices that a hostname policy does not permit.
+    /// 
+    /// Hostnames must already be resolved (see
+    /// `DeviceManager::resolve_hostnames`).  Devices are matched to the
+    /// policy by hostname and deauthenticated by their current MAC address.
+    /// 
+    /// **Note**: devices choose their own hostnames, so this is a convenience
+    /// rather than a security measure.
+    /// 
+    /// # Parameters
+    /// - `devices` (`&[Device]`): the devices to check
+    /// - `policy` (`&HostnamePolicy`): the hostname policy
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Vec<MacAddr>>` containing the MAC
+    /// addresses of the denied devices, or a
+    /// `ProtonError::CouldNotDeauthenticateDevices` listing every denied
+    /// device that could not be deauthenticated.
+    pub async fn enforce_hostname_policy(&mut self, devices: &[Device], policy: &HostnamePolicy) -> ProtonResult<Vec<MacAddr>> {
+        let denied = devices.iter()
+            .filter(|device| !policy.permits(device.hostname.as_deref()))
+            .map(|device| device.mac)
+            .collect::<Vec<MacAddr>>();
+
+        // Deauthenticate each device, keeping track of failures
+        let mut failed = Vec::new();
+        for mac in denied.iter().copied() {
+            if self.deauth(mac, ReasonCode::Unspecified).await.is_err() {
+                failed.push(mac);
+            }
+        }
+
+        if !failed.is_empty() {
+            return Err (ProtonError::CouldNotDeauthenticateDevices (failed));
+        }
+
+        Ok (denied)
+    }
+
+    /// Resolve the IPv6 addresses of a list of devices from the neighbor cache.
+    /// 
+    /// Global addresses (within `prefix`) are preferred over link-local
+    /// addresses.  Devices missing from the neighbor cache keep their current
+    /// IPv6 address.
+    /// 
+    /// # Parameters
+    /// - `devices` (`&mut [Device]`): the devices to resolve
+    /// - `prefix` (`Ipv6Cidr`): the global prefix of the network
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<()>` indicating whether or not the
+    /// neighbor cache could be read.
+    pub async fn resolve_ipv6(&self, devices: &mut [Device], prefix: Ipv6Cidr) -> ProtonResult<()> {
+        // Discover the IPv6 neighbors of the interface
+        let neighbors = proton_arp::scan_ipv6(prefix, &self.wlifname).await?;
+
+        // Merge the addresses into the devices
+        for device in devices.iter_mut() {
+            let addresses = neighbors.iter()
+                .filter(|neighbor| neighbor.mac == device.mac);
+
+            let mut best: Option<&NdpEntry> = None;
+            for neighbor in addresses {
+                if best.is_none_or(|b| b.is_link_local() && !neighbor.is_link_local()) {
+                    best = Some (neighbor);
+                }
+            }
+
+            if let Some (neighbor) = best {
+                device.ipv6 = Some (neighbor.ipv6);
+            }
+        }
+
+        Ok (())
+    }
+
+    /// Deauthenticate a connected device.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address of the device
+    /// - `reason` (`ReasonCode`): the reason given to the device
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<()>` indicating whether or not the
+    /// device was deauthenticated.
+    pub async fn deauth(&mut self, mac: MacAddr, reason: ReasonCode) -> ProtonResult<()> {
+        // Get the Wi-Fi device
+        let interface = self.get_interface().await?;
+        let index = interface.index
+            .ok_or(ProtonError::CouldNotFindWirelessInterface)?;
+
+        self.socket.deauthenticate_by_mac(&index, mac, reason)?;
+
+        #[cfg(feature = "metrics")]
+        self.metrics.record_deauths(1);
+
+        Ok (())
+    }
+
+    /// Find the connected devices that have been inactive for longer than
+    /// `threshold`, freeing their association slots if enabled.
+    /// 
+    /// By default, inactive devices are only reported.  If
+    /// `DeviceManager::set_prune_deauth` is enabled, they are also
+    /// deauthenticated with `ReasonCode::InactivityTimeout`; a failure to
+    /// deauthenticate one device does not stop the others from being
+    /// deauthenticated.  The ARP cache is not refreshed, so IPv4 addresses
+    /// are those of the last scan.
+    /// 
+    /// # Parameters
+    /// - `threshold` (`Duration`): the maximum inactive time of a device
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<(Vec<Device>, Vec<MacAddr>)>` containing
+    /// the inactive devices and the MAC addresses of those that could not be
+    /// deauthenticated (always empty unless deauthentication is enabled).
+    pub async fn prune_inactive(&mut self, threshold: Duration) -> ProtonResult<(Vec<Device>, Vec<MacAddr>)> {
+        let inactive = self.stations().await?
+            .into_iter()
+            .filter(|device| device.inactive_ms as u128 > threshold.as_millis())
+            .collect::<Vec<Device>>();
+
+        // Deauthenticate each device, keeping track of failures
+        let mut failed = Vec::new();
+        if self.prune_deauth {
+            for mac in inactive.iter().map(|device| device.mac) {
+                if self.deauth(mac, ReasonCode::InactivityTimeout).await.is_err() {
+                    failed.push(mac);
+                }
+            }
+        }
+
+        Ok ((inactive, failed))
+    }
+
+    /// Deauthenticate all connected devices.
+    /// 
+    /// A failure to deauthenticate one device does not stop the others from
+    /// being deauthenticated.  Stations without a valid MAC address cannot
+    /// be addressed, so they are skipped.
+    /// 
+    /// # Parameters
+    /// - `reason` (`ReasonCode`): the reason given to the devices
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<(usize, Vec<MacAddr>)>` containing the
+    /// number of devices deauthenticated and the MAC addresses of those that
+    /// could not be deauthenticated.
+    pub async fn deauth_all(&mut self, reason: ReasonCode) -> ProtonResult<(usize, Vec<MacAddr>)> {
+        // Get the Wi-Fi device
+        let interface = self.get_interface().await?;
+        let index = interface.index
+            .ok_or(ProtonError::CouldNotFindWirelessInterface)?;
+
+        // Get all stations
+        let stations = self.retry.run(|| self.socket.get_all_stations(&index)).await?;
+
+        // Deauthenticate each station, keeping track of failures
+        let mut count = 0;
+        let mut failed = Vec::new();
+        for station in stations {
+            let Ok (mac) = MacAddr::try_from(station.bssid.unwrap_or_default().as_slice()) else {
+                continue;
+            };
+
+            match self.socket.deauthenticate_by_mac(&index, mac, reason) {
+                Ok (_) => count += 1,
+                Err (_) => failed.push(mac),
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        self.metrics.record_deauths(count);
+
+        Ok ((count, failed))
+    }
+
+    /// Survey nearby access points.
+    /// 
+    /// If the driver is busy with another scan (`EBUSY`), the scan is
+    /// retried up to `SURVEY_TRIGGER_ATTEMPTS` times; any other error is
+    /// returned immediately.  The results are read once the driver reports
+    /// the end of the scan, which must happen within `SURVEY_SCAN_TIMEOUT`.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Vec<ApInfo>>` containing a list of
+    /// nearby access points, `ProtonError::ScanAborted` if the driver
+    /// aborted the scan, or `ProtonError::ScanTimedOut` if it did not end
+    /// in time.
+    pub async fn survey(&mut self) -> ProtonResult<Vec<ApInfo>> {
+        // Get the Wi-Fi device
+        let interface = self.get_interface().await?;
+        let index = interface.index
+            .ok_or(ProtonError::CouldNotFindWirelessInterface)?;
+
+        // Listen for the end of the scan before triggering it, so that it
+        // cannot be missed
+        let mut events = ScanEvents::subscribe()?;
+
+        // Trigger the scan, retrying while another scan is in progress
+        let mut attempts = 1;
+        loop {
+            match self.socket.trigger_scan(&index) {
+                Ok (()) => break,
+                Err (e) if attempts < SURVEY_TRIGGER_ATTEMPTS && e.io_kind() == ErrorKind::ResourceBusy => {
+                    attempts += 1;
+                    time::sleep(SURVEY_RETRY_DELAY).await;
+                },
+                Err (e) => return Err (e),
+            }
+        }
+
+        // Ignore the end of the scan that kept the driver busy
+        events.discard();
+
+        // Wait for the driver to complete the scan
+        let deadline = Instant::now() + SURVEY_SCAN_TIMEOUT;
+        while !events.scan_done(&index)? {
+            if Instant::now() >= deadline {
+                return Err (ProtonError::ScanTimedOut);
+            }
+
+            time::sleep(SURVEY_POLL_INTERVAL).await;
+        }
+
+        self.socket.get_scan_results(&index)
+    }
+
+    /// Check if the wireless interface supports SAE (WPA3) authentication.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<bool>` indicating whether or not the
+    /// driver of the wireless interface supports SAE.
+    pub async fn supports_sae(&mut self) -> ProtonResult<bool> {
+        // Get the Wi-Fi device
+        let interface = self.get_interface().await?;
+
+        // Get the wireless PHY of the Wi-Fi device
+        let wiphy = interface.phy
+            .ok_or(ProtonError::CouldNotGetDeviceInformation)?;
+
+        self.socket.supports_sae(&wiphy)
+    }
+
+    /// Check if the wireless interface supports OWE (Enhanced Open) authentication.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<bool>` indicating whether or not the
+    /// driver of the wireless interface supports OWE.
+    pub async fn supports_owe(&mut self) -> ProtonResult<bool> {
+        // Get the Wi-Fi device
+        let interface = self.get_interface().await?;
+
+        // Get the wireless PHY of the Wi-Fi device
+        let wiphy = interface.phy
+            .ok_or(ProtonError::CouldNotGetDeviceInformation)?;
+
+        self.socket.supports_owe(&wiphy)
+    }
+
+    /// Check if the wireless interface supports the 2.4 GHz band.
+    /// 
+    /// If the driver does not report its bands, this assumes that the band
+    /// is supported.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<bool>` indicating whether or not the
+    /// radio of the wireless interface supports 2.4 GHz.
+    pub async fn supports_2_4_ghz(&mut self) -> ProtonResult<bool> {
+        self.supports_band(NL80211_BAND_2GHZ).await
+    }
+
+    /// Check if the wireless interface supports the 5 GHz band.
+    /// 
+    /// If the driver does not report its bands, this assumes that the band
+    /// is supported.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<bool>` indicating whether or not the
+    /// radio of the wireless interface supports 5 GHz.
+    pub async fn supports_5_ghz(&mut self) -> ProtonResult<bool> {
+        self.supports_band(NL80211_BAND_5GHZ).await
+    }
+
+    /// Check if the wireless interface supports an `nl80211` band.
+    async fn supports_band(&mut self, band: u16) -> ProtonResult<bool> {
+        // Get the Wi-Fi device
+        let interface = self.get_interface().await?;
+
+        // Get the wireless PHY of the Wi-Fi device
+        let wiphy = interface.phy
+            .ok_or(ProtonError::CouldNotGetDeviceInformation)?;
+
+        let bands = self.socket.get_bands(&wiphy)?;
+
+        Ok (bands.is_empty() || bands.contains(&band))
+    }
+
+    /// Set the transmit power of the wireless interface.
+    /// 
+    /// The power level is checked against the regulatory limit of the
+    /// operating channel, as reported by the wireless PHY.  If the driver
+    /// does not report one, it is left to reject the level itself.
+    /// 
+    /// # Parameters
+    /// - `dbm` (`u8`): the transmit power, in dBm
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<()>` indicating whether or not the
+    /// transmit power was set, or `ProtonError::TxPowerOutOfRange` if it
+    /// exceeds the regulatory limit.  Errors of the driver keep their `errno`.
+    pub async fn set_tx_power(&mut self, dbm: u8) -> ProtonResult<()> {
+        // Get the Wi-Fi device
+        let interface = self.get_interface().await?;
+
+        // Get the wireless PHY of the Wi-Fi device
+        let wiphy = interface.phy
+            .ok_or(ProtonError::CouldNotGetDeviceInformation)?;
+
+        // Check the regulatory limit of the operating channel
+        let mbm = dbm as u32 * 100;
+        let frequency_mhz = parse_u32(&interface.frequency.unwrap_or_default());
+        if let Some (max_mbm) = self.socket.get_max_tx_power(&wiphy, frequency_mhz)? {
+            if mbm > max_mbm {
+                return Err (ProtonError::TxPowerOutOfRange (dbm as u32));
+            }
+        }
+
+        self.socket.set_tx_power(&wiphy, mbm)
+    }
+
+    /// Get the Netlink interface information of the wireless interface.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Interface>` containing the interface
+    /// information, if the interface could be found.
+    async fn get_interface(&mut self) -> ProtonResult<Interface> {
+        // Determine Wi-Fi device by name
+        let check_wifi_device = |iface: &Interface| parse_string(&iface.name.clone().unwrap_or_default()).trim_end_matches('\0') == self.wlifname;
+
+        self.retry.run(|| Ok (self.socket.get_interfaces_info()?)).await?
+            .into_iter()
+            .find(check_wifi_device)
+            .ok_or(ProtonError::CouldNotFindWirelessInterface)
+    }
+
+    /// Get the IPv4 address of the wireless interface.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// The result type `ProtonResult<Ipv4Addr>` containing the address, or
+    /// `ProtonError::InterfaceNotReady` if the interface has none yet.
+    fn interface_ipv4(&self) -> ProtonResult<Ipv4Addr> {
+        let interface = interfaces()
+            .into_iter()
+            .find(|iface| iface.name == self.wlifname)
+            .ok_or(ProtonError::CouldNotFindInterface (self.wlifname.clone()))?;
+
+        interface.ips.iter()
+            .find_map(|ip| match ip.ip() {
+                IpAddr::V4 (ipv4) => Some (ipv4),
+                IpAddr::V6 (_) => None,
+            })
+            .ok_or(ProtonError::InterfaceNotReady (self.wlifname.clone()))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,107 @@
+//! Device manager metrics, rendered in the Prometheus text format.
+
+use std::{
+    fmt::Write,
+    time::Duration,
+};
+
+use proton_mac::MacAddr;
+
+use crate::Device;
+
+#[derive(Clone, Debug, Default)]
+/// Counters and gauges of a device manager.
+pub struct Metrics {
+    /// Number of connected clients, as of the last station query.
+    pub clients: usize,
+
+    /// Signal strength of each connected client, as of the last station
+    /// query, in dBm.
+    pub signal: Vec<(MacAddr, i8)>,
+
+    /// Number of completed network scans.
+    pub scans: u64,
+
+    /// Duration of the ARP scan of the last completed network scan.
+    pub scan_duration: Duration,
+
+    /// Number of clients deauthenticated.
+    pub deauths: u64,
+}
+
+impl Metrics {
+    /// Update the client gauges from a list of connected devices.
+    /// 
+    /// # Parameters
+    /// - `devices` (`&[Device]`): the connected devices
+    /// 
+    /// # Returns
+    /// None.
+    pub fn record_clients(&mut self, devices: &[Device]) {
+        self.clients = devices.len();
+        self.signal = devices.iter()
+            .map(|device| (device.mac, device.signal_strength))
+            .collect();
+    }
+
+    /// Record a completed network scan.
+    /// 
+    /// # Parameters
+    /// - `duration` (`Duration`): the duration of the scan
+    /// 
+    /// # Returns
+    /// None.
+    pub fn record_scan(&mut self, duration: Duration) {
+        self.scans += 1;
+        self.scan_duration = duration;
+    }
+
+    /// Record deauthenticated clients.
+    /// 
+    /// # Parameters
+    /// - `count` (`usize`): the number of clients deauthenticated
+    /// 
+    /// # Returns
+    /// None.
+    pub fn record_deauths(&mut self, count: usize) {
+        self.deauths += count as u64;
+    }
+
+    /// Render these metrics in the Prometheus text exposition format.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `String` containing one sample per line, with `# HELP` and `# TYPE`
+    /// comments, suitable for serving on a `/metrics` endpoint.
+    pub fn render_prometheus(&self) -> String {
+        let mut text = String::new();
+
+        // Note: it's okay to ignore the result of `writeln!` here because
+        //  writing to a `String` never fails
+        let _ = writeln!(text, "# HELP proton_clients Number of connected clients.");
+        let _ = writeln!(text, "# TYPE proton_clients gauge");
+        let _ = writeln!(text, "proton_clients {}", self.clients);
+
+        let _ = writeln!(text, "# HELP proton_client_signal_dbm Signal strength of a connected client, in dBm.");
+        let _ = writeln!(text, "# TYPE proton_client_signal_dbm gauge");
+        for (mac, signal) in &self.signal {
+            let _ = writeln!(text, "proton_client_signal_dbm{{mac=\"{}\"}} {}", mac, signal);
+        }
+
+        let _ = writeln!(text, "# HELP proton_scans_total Number of completed network scans.");
+        let _ = writeln!(text, "# TYPE proton_scans_total counter");
+        let _ = writeln!(text, "proton_scans_total {}", self.scans);
+
+        let _ = writeln!(text, "# HELP proton_scan_duration_seconds Duration of the last network scan, in seconds.");
+        let _ = writeln!(text, "# TYPE proton_scan_duration_seconds gauge");
+        let _ = writeln!(text, "proton_scan_duration_seconds {}", self.scan_duration.as_secs_f64());
+
+        let _ = writeln!(text, "# HELP proton_deauths_total Number of clients deauthenticated.");
+        let _ = writeln!(text, "# TYPE proton_deauths_total counter");
+        let _ = writeln!(text, "proton_deauths_total {}", self.deauths);
+
+        text
+    }
+}
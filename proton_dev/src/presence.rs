@@ -0,0 +1,182 @@
+//! Device presence tracking across scans.
+
+use std::{
+    collections::HashMap,
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
+
+use proton_mac::MacAddr;
+
+use crate::Device;
+
+/// Default time after which an absent device is forgotten (24 hours).
+pub const DEFAULT_ABSENCE_THRESHOLD: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The presence of a device across scans.
+pub struct Presence {
+    /// Time of the first scan in which the device was seen.
+    pub first_seen: SystemTime,
+
+    /// Time of the most recent scan in which the device was seen.
+    pub last_seen: SystemTime,
+
+    /// Number of scans in which the device was seen.
+    pub seen_count: u64,
+}
+
+#[derive(Clone, Debug)]
+/// A record of when devices were first and last seen, keyed by MAC address.
+/// 
+/// Devices that have not been seen for longer than the absence threshold
+/// are forgotten, so a device that returns afterwards starts over.
+pub struct PresenceTracker {
+    /// Time after which an absent device is forgotten.
+    absence_threshold: Duration,
+
+    /// Presence of each tracked device.
+    devices: HashMap<MacAddr, Presence>,
+}
+
+impl PresenceTracker {
+    /// Construct a new, empty presence tracker.
+    /// 
+    /// # Parameters
+    /// - `absence_threshold` (`Duration`): the time after which an absent
+    ///   device is forgotten
+    /// 
+    /// # Returns
+    /// A new `PresenceTracker` with no tracked devices.
+    pub fn new(absence_threshold: Duration) -> Self {
+        Self {
+            absence_threshold,
+            devices: HashMap::new(),
+        }
+    }
+
+    /// Record the devices of a scan, as of now.
+    /// 
+    /// # Parameters
+    /// - `devices` (`&[Device]`): the devices found by the scan
+    /// 
+    /// # Returns
+    /// A `Vec<MacAddr>` containing the MAC addresses of the devices that
+    /// were forgotten (see `PresenceTracker::record_at`).
+    pub fn record(&mut self, devices: &[Device]) -> Vec<MacAddr> {
+        let macs = devices.iter()
+            .map(|device| device.mac)
+            .collect::<Vec<MacAddr>>();
+
+        self.record_at(&macs, SystemTime::now())
+    }
+
+    /// Record the devices of a scan at a given time.
+    /// 
+    /// Devices that were last seen more than the absence threshold before
+    /// `now` are forgotten.
+    /// 
+    /// # Parameters
+    /// - `macs` (`&[MacAddr]`): the MAC addresses of the devices found by the scan
+    /// - `now` (`SystemTime`): the time of the scan
+    /// 
+    /// # Returns
+    /// A `Vec<MacAddr>` containing the MAC addresses of the devices that
+    /// were forgotten.
+    pub fn record_at(&mut self, macs: &[MacAddr], now: SystemTime) -> Vec<MacAddr> {
+        for mac in macs {
+            self.devices.entry(*mac)
+                .and_modify(|presence| {
+                    presence.last_seen = presence.last_seen.max(now);
+                    presence.seen_count += 1;
+                })
+                .or_insert(Presence {
+                    first_seen: now,
+                    last_seen: now,
+                    seen_count: 1,
+                });
+        }
+
+        // Forget devices that have been absent for too long
+        let absent = self.devices.iter()
+            .filter(|(_, presence)| now.duration_since(presence.last_seen).unwrap_or_default() > self.absence_threshold)
+            .map(|(mac, _)| *mac)
+            .collect::<Vec<MacAddr>>();
+
+        for mac in &absent {
+            self.devices.remove(mac);
+        }
+
+        absent
+    }
+
+    /// Get the presence of a device.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address of the device
+    /// 
+    /// # Returns
+    /// An `Option<&Presence>` containing the presence of the device, if it
+    /// is tracked.
+    pub fn get(&self, mac: MacAddr) -> Option<&Presence> {
+        self.devices.get(&mac)
+    }
+
+    /// Iterate over the tracked devices.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An iterator over the MAC address and presence of each tracked
+    /// device, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&MacAddr, &Presence)> {
+        self.devices.iter()
+    }
+
+    /// Get the devices that were seen since a given time.
+    /// 
+    /// # Parameters
+    /// - `since` (`SystemTime`): the earliest time of interest
+    ///   (e.g. midnight, for "devices on your network today")
+    /// 
+    /// # Returns
+    /// A `Vec<MacAddr>` containing the MAC addresses of the devices last
+    /// seen at or after `since`.
+    pub fn seen_since(&self, since: SystemTime) -> Vec<MacAddr> {
+        self.devices.iter()
+            .filter(|(_, presence)| presence.last_seen >= since)
+            .map(|(mac, _)| *mac)
+            .collect()
+    }
+
+    /// Get the number of tracked devices.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `usize` containing the number of tracked devices.
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Check if no devices are tracked.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the tracker is empty.
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+}
+
+impl Default for PresenceTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_ABSENCE_THRESHOLD)
+    }
+}
@@ -0,0 +1,46 @@
+//! IEEE 802.11 deauthentication reason codes.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// The reason given to a station when it is deauthenticated.
+pub enum ReasonCode {
+    #[default]
+    /// Unspecified reason.
+    Unspecified,
+
+    /// Previous authentication is no longer valid.
+    PreviousAuthInvalid,
+
+    /// The access point is leaving (or has left).
+    Leaving,
+
+    /// The station was inactive for too long.
+    InactivityTimeout,
+
+    /// The access point cannot handle any more associated stations.
+    ApFull,
+
+    /// The station sent a frame that requires association without
+    /// being associated.
+    ClassMismatch,
+}
+
+impl ReasonCode {
+    /// Get the IEEE 802.11 value of this reason code.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `u16` containing the reason code.
+    pub fn as_u16(&self) -> u16 {
+        use ReasonCode::*;
+        match self {
+            Unspecified => 1,
+            PreviousAuthInvalid => 2,
+            Leaving => 3,
+            InactivityTimeout => 4,
+            ApFull => 5,
+            ClassMismatch => 7,
+        }
+    }
+}
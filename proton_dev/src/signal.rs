@@ -0,0 +1,102 @@
+//! Per-device signal strength smoothing.
+
+use std::collections::{
+    HashMap,
+    VecDeque,
+};
+
+use proton_mac::MacAddr;
+
+/// Default number of signal strength samples kept per device.
+pub const DEFAULT_SIGNAL_HISTORY_LEN: usize = 8;
+
+#[derive(Clone, Debug)]
+/// A fixed-size history of signal strength samples for devices, keyed by
+/// MAC address.
+pub struct SignalHistory {
+    /// Maximum number of samples kept per device.
+    capacity: usize,
+
+    /// Most recent samples of each device, oldest first, in dBm.
+    samples: HashMap<MacAddr, VecDeque<i8>>,
+}
+
+impl SignalHistory {
+    /// Construct a new, empty signal history.
+    /// 
+    /// # Parameters
+    /// - `capacity` (`usize`): the maximum number of samples kept per device
+    ///   (at least 1)
+    /// 
+    /// # Returns
+    /// A new `SignalHistory` with no recorded devices.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            samples: HashMap::new(),
+        }
+    }
+
+    /// Record a signal strength sample for a device.
+    /// 
+    /// Once a device has `capacity` samples, its oldest sample is discarded.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address of the device
+    /// - `signal_strength` (`i8`): the signal strength sample, in dBm
+    /// 
+    /// # Returns
+    /// An `i8` containing the average signal strength of the device, in dBm.
+    pub fn record(&mut self, mac: MacAddr, signal_strength: i8) -> i8 {
+        let samples = self.samples.entry(mac).or_default();
+
+        if samples.len() == self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back(signal_strength);
+
+        average(samples)
+    }
+
+    /// Get the average signal strength of a device.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address of the device
+    /// 
+    /// # Returns
+    /// An `Option<i8>` containing the average signal strength of the device,
+    /// in dBm, if it has any samples.
+    pub fn average(&self, mac: MacAddr) -> Option<i8> {
+        self.samples.get(&mac).map(average)
+    }
+
+    /// Discard the samples of devices that are no longer connected.
+    /// 
+    /// # Parameters
+    /// - `connected` (`&[MacAddr]`): the MAC addresses of connected devices
+    /// 
+    /// # Returns
+    /// None.
+    pub fn retain(&mut self, connected: &[MacAddr]) {
+        self.samples.retain(|mac, _| connected.contains(mac));
+    }
+}
+
+impl Default for SignalHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIGNAL_HISTORY_LEN)
+    }
+}
+
+/// Compute the rounded average of a non-empty list of samples.
+fn average(samples: &VecDeque<i8>) -> i8 {
+    let sum: i32 = samples.iter()
+        .map(|sample| *sample as i32)
+        .sum();
+    let len = samples.len() as i32;
+
+    // Round half away from zero
+    let rounded = (2 * sum + sum.signum() * len) / (2 * len);
+
+    rounded as i8
+}
@@ -1,30 +1,203 @@
 //! Socket utilities for device discovery.
 
+use std::{
+    io::{
+        self,
+        ErrorKind,
+    },
+    mem,
+    os::fd::AsRawFd,
+    time::Duration,
+};
+
 use neli::{
     consts::{
+        NlFamily,
         NlmF,
         Nlmsg,
     },
+    err::Nlmsgerr,
     genl::Genlmsghdr,
     nl::Nlmsghdr,
     nlattr::Nlattr,
+    socket::NlSocket,
+    Nl,
+    StreamReadBuffer,
+    MAX_NL_LENGTH,
 };
 
 use nl80211::{
+    parse_u32,
     Nl80211Attr,
+    Nl80211Bss,
     Nl80211Cmd,
+    Nl80211StaInfo,
     ParseNlAttr,
     Socket,
     Station,
     NL_80211_GENL_VERSION,
 };
 
+use tokio::time;
+
 use proton_err::{
     ProtonError,
     ProtonResult,
 };
 
-// use proton_mac::MacAddr;
+use proton_mac::MacAddr;
+
+use crate::{
+    ApInfo,
+    ReasonCode,
+    StationFlags,
+};
+
+/// Name of the `nl80211` Generic Netlink family.
+const NL80211_GENL_NAME: &str = "nl80211";
+
+/// Name of the `nl80211` multicast group of scan events
+/// (`NL80211_MULTICAST_GROUP_SCAN`).
+const NL80211_MULTICAST_GROUP_SCAN: &str = "scan";
+
+/// Wiphy feature flag indicating SAE (WPA3) support (`NL80211_FEATURE_SAE`).
+const NL80211_FEATURE_SAE: u32 = 1 << 5;
+
+/// Flag of a nested Netlink attribute type (`NLA_F_NESTED`).
+const NLA_F_NESTED: u16 = 1 << 15;
+
+/// Flag of a Netlink attribute type in network byte order (`NLA_F_NET_BYTEORDER`).
+const NLA_F_NET_BYTEORDER: u16 = 1 << 14;
+
+/// Cipher suite selector of BIP-CMAC-128 (`00-0F-AC:6`), which protects
+/// management frames as required by OWE.
+const CIPHER_SUITE_BIP_CMAC_128: u32 = 0x000FAC06;
+
+/// Rate information attribute of a 16-bit bitrate, in units of 100 kbit/s
+/// (`NL80211_RATE_INFO_BITRATE`).
+const NL80211_RATE_INFO_BITRATE: u16 = 1;
+
+/// Rate information attribute of a 32-bit bitrate, in units of 100 kbit/s
+/// (`NL80211_RATE_INFO_BITRATE32`).
+const NL80211_RATE_INFO_BITRATE32: u16 = 5;
+
+/// Band attribute of the channels of a band (`NL80211_BAND_ATTR_FREQS`).
+const NL80211_BAND_ATTR_FREQS: u16 = 1;
+
+/// Channel attribute of the center frequency, in MHz
+/// (`NL80211_FREQUENCY_ATTR_FREQ`).
+const NL80211_FREQUENCY_ATTR_FREQ: u16 = 1;
+
+/// Channel attribute of the maximum regulatory transmit power, in mBm
+/// (`NL80211_FREQUENCY_ATTR_MAX_TX_POWER`).
+const NL80211_FREQUENCY_ATTR_MAX_TX_POWER: u16 = 6;
+
+/// Transmit power setting for a fixed power level (`NL80211_TX_POWER_FIXED`).
+const NL80211_TX_POWER_FIXED: u32 = 2;
+
+/// IEEE 802.11 management frame subtype of a deauthentication frame.
+const MGMT_SUBTYPE_DEAUTH: u8 = 12;
+
+/// Default number of attempts of a Netlink operation.
+pub const DEFAULT_NETLINK_ATTEMPTS: usize = 3;
+
+/// Default delay before the first retry of a Netlink operation.
+pub static DEFAULT_NETLINK_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A bounded retry policy with exponential backoff for Netlink operations.
+/// 
+/// Drivers occasionally fail a request while they are busy (e.g. with
+/// `EBUSY` during a scan), in which case a short wait usually fixes it.
+/// Only transient errors are retried (see `ProtonError::is_transient`).
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub attempts: usize,
+
+    /// Delay before the first retry, doubled after each retry.
+    pub delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Construct a new retry policy.
+    /// 
+    /// # Parameters
+    /// - `attempts` (`usize`): the total number of attempts (at least 1)
+    /// - `delay` (`Duration`): the delay before the first retry
+    /// 
+    /// # Returns
+    /// A new `RetryPolicy`.
+    pub fn new(attempts: usize, delay: Duration) -> Self {
+        Self {
+            attempts: attempts.max(1),
+            delay,
+        }
+    }
+
+    /// Run an operation until it succeeds, fails permanently, or all
+    /// attempts are used up.
+    /// 
+    /// # Parameters
+    /// - `operation` (`FnMut() -> ProtonResult<T>`): the operation to run
+    /// 
+    /// # Returns
+    /// A `ProtonResult<T>` containing the first successful result, the first
+    /// error that is not transient, or the error of the last attempt.
+    pub async fn run<T, F>(&self, mut operation: F) -> ProtonResult<T>
+        where F: FnMut() -> ProtonResult<T>
+    {
+        let mut delay = self.delay;
+        let mut attempt = 1;
+
+        loop {
+            match operation() {
+                Ok (result) => return Ok (result),
+                Err (e) if attempt >= self.attempts || !e.is_transient() => return Err (e),
+                Err (_) => {
+                    // Back off before the next attempt
+                    time::sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                    attempt += 1;
+                },
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_NETLINK_ATTEMPTS, DEFAULT_NETLINK_RETRY_DELAY)
+    }
+}
+
+#[derive(Clone, Debug)]
+/// A station connected to an AP, with the statistics that `Station` does
+/// not parse.
+pub struct StationEntry {
+    /// The station reported by `nl80211`.
+    pub station: Station,
+
+    /// Time since the station was last active, in milliseconds, if the
+    /// driver reports it.
+    pub inactive_ms: Option<u32>,
+
+    /// Flags of the station, if the driver reports them.
+    pub flags: Option<StationFlags>,
+
+    /// Transmit bitrate of the station, in Mbit/s, if the driver reports it.
+    pub tx_bitrate_mbps: Option<f32>,
+
+    /// Receive bitrate of the station, in Mbit/s, if the driver reports it.
+    pub rx_bitrate_mbps: Option<f32>,
+
+    /// Total bytes received from the station since it connected, if the
+    /// driver reports it.
+    pub rx_bytes: Option<u64>,
+
+    /// Total bytes transmitted to the station since it connected, if the
+    /// driver reports it.
+    pub tx_bytes: Option<u64>,
+}
 
 /// A wireless AP with a number of connected stations.
 pub trait NetworkSocket {
@@ -35,14 +208,515 @@ pub trait NetworkSocket {
     /// 
     /// # Returns
     /// `ProtonResult<Vec<Station>>` containing a list of network stations.
-    fn get_all_stations(&mut self, nlif_index: &[u8]) -> ProtonResult<Vec<Station>>;
+    fn get_all_stations(&mut self, nlif_index: &[u8]) -> ProtonResult<Vec<Station>> {
+        let entries = self.get_station_entries(nlif_index)?;
+
+        Ok (entries.into_iter().map(|entry| entry.station).collect())
+    }
+
+    /// Get all stations connected to this AP, with their statistics.
+    /// 
+    /// This reads every statistic from a single station dump.
+    /// 
+    /// # Parameters
+    /// - `nlif_index` (`&[u8]`): a Netlink network interface index
+    /// 
+    /// # Returns
+    /// `ProtonResult<Vec<StationEntry>>` containing a list of network stations.
+    fn get_station_entries(&mut self, nlif_index: &[u8]) -> ProtonResult<Vec<StationEntry>>;
+
+    /// Check if a wireless PHY supports SAE (WPA3) authentication.
+    /// 
+    /// # Parameters
+    /// - `wiphy` (`&[u8]`): a Netlink wireless PHY index
+    /// 
+    /// # Returns
+    /// `ProtonResult<bool>` indicating whether or not SAE is supported.
+    fn supports_sae(&mut self, wiphy: &[u8]) -> ProtonResult<bool>;
+
+    /// Check if a wireless PHY supports OWE (Enhanced Open) authentication.
+    /// 
+    /// OWE requires protected management frames, so this checks that the
+    /// PHY supports the BIP-CMAC-128 cipher suite.
+    /// 
+    /// # Parameters
+    /// - `wiphy` (`&[u8]`): a Netlink wireless PHY index
+    /// 
+    /// # Returns
+    /// `ProtonResult<bool>` indicating whether or not OWE is supported.
+    fn supports_owe(&mut self, wiphy: &[u8]) -> ProtonResult<bool>;
+
+    /// Get the frequency bands supported by a wireless PHY.
+    /// 
+    /// # Parameters
+    /// - `wiphy` (`&[u8]`): a Netlink wireless PHY index
+    /// 
+    /// # Returns
+    /// `ProtonResult<Vec<u16>>` containing the supported bands, as
+    /// `enum nl80211_band` values (e.g. `NL80211_BAND_2GHZ`).  This is empty
+    /// if the driver does not report its bands.
+    fn get_bands(&mut self, wiphy: &[u8]) -> ProtonResult<Vec<u16>>;
+
+    /// Trigger a scan for nearby access points.
+    /// 
+    /// # Parameters
+    /// - `nlif_index` (`&[u8]`): a Netlink network interface index
+    /// 
+    /// # Returns
+    /// `ProtonResult<()>` indicating whether or not the driver started the
+    /// scan.  If it did not, the error keeps the `errno` of the driver, which
+    /// is `EBUSY` if a scan is already in progress.
+    fn trigger_scan(&mut self, nlif_index: &[u8]) -> ProtonResult<()>;
+
+    /// Get the results of the last scan for nearby access points.
+    /// 
+    /// # Parameters
+    /// - `nlif_index` (`&[u8]`): a Netlink network interface index
+    /// 
+    /// # Returns
+    /// `ProtonResult<Vec<ApInfo>>` containing a list of nearby access points.
+    fn get_scan_results(&mut self, nlif_index: &[u8]) -> ProtonResult<Vec<ApInfo>>;
+
+    /// Get the maximum transmit power that regulations allow on a channel of
+    /// a wireless PHY.
+    /// 
+    /// # Parameters
+    /// - `wiphy` (`&[u8]`): a Netlink wireless PHY index
+    /// - `frequency_mhz` (`u32`): the center frequency of the channel, in MHz
+    /// 
+    /// # Returns
+    /// `ProtonResult<Option<u32>>` containing the maximum transmit power, in
+    /// mBm (hundredths of a dBm), or `None` if the driver does not report it.
+    fn get_max_tx_power(&mut self, wiphy: &[u8], frequency_mhz: u32) -> ProtonResult<Option<u32>>;
+
+    /// Set the transmit power of a wireless PHY.
+    /// 
+    /// # Parameters
+    /// - `wiphy` (`&[u8]`): a Netlink wireless PHY index
+    /// - `mbm` (`u32`): the transmit power, in mBm (hundredths of a dBm)
+    /// 
+    /// # Returns
+    /// `ProtonResult<()>` indicating whether or not the driver accepted
+    /// the transmit power.  If it did not, the error keeps the `errno` of
+    /// the driver (see `ProtonError::from_errno`).
+    fn set_tx_power(&mut self, wiphy: &[u8], mbm: u32) -> ProtonResult<()>;
+
+    /// Deauthenticate a station connected to this AP.
+    /// 
+    /// # Parameters
+    /// - `nlif_index` (`&[u8]`): a Netlink network interface index
+    /// - `mac` (`MacAddr`): the MAC address of the station
+    /// - `reason` (`ReasonCode`): the reason given to the station
+    /// 
+    /// # Returns
+    /// `ProtonResult<()>` indicating whether or not the station
+    /// was deauthenticated.
+    fn deauthenticate_by_mac(&mut self, nlif_index: &[u8], mac: MacAddr, reason: ReasonCode) -> ProtonResult<()>;
+}
+
+/// A subscription to the scan events of `nl80211`.
+/// 
+/// The driver announces the end of a scan with `NL80211_CMD_NEW_SCAN_RESULTS`
+/// (or `NL80211_CMD_SCAN_ABORTED`) on the `scan` multicast group, so waiting
+/// for these events avoids guessing how long a scan takes.
+pub struct ScanEvents {
+    /// Non-blocking Netlink socket in the `scan` multicast group.
+    sock: NlSocket,
+}
+
+impl ScanEvents {
+    /// Subscribe to the scan events of `nl80211`.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// `ProtonResult<ScanEvents>` containing the subscription, if the
+    /// multicast group could be joined.
+    pub fn subscribe() -> ProtonResult<Self> {
+        let mut sock = NlSocket::connect(NlFamily::Generic, None, None, true)?;
+        let group = sock.resolve_nl_mcast_group(NL80211_GENL_NAME, NL80211_MULTICAST_GROUP_SCAN)?;
+        add_mcast_membership(&sock, group)?;
+
+        // Events are polled, so reading them must never block
+        sock.nonblock()?;
+
+        Ok (Self {
+            sock,
+        })
+    }
+
+    /// Discard the events received so far (e.g. the end of a scan that
+    /// was in progress before a new one was triggered).
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// None.
+    pub fn discard(&mut self) {
+        while recv_messages(&self.sock).is_ok() {}
+    }
+
+    /// Check if a scan on a network interface has ended, without blocking.
+    /// 
+    /// # Parameters
+    /// - `nlif_index` (`&[u8]`): a Netlink network interface index
+    /// 
+    /// # Returns
+    /// `ProtonResult<bool>` indicating whether or not the scan results are
+    /// ready, or `ProtonError::ScanAborted` if the driver aborted the scan.
+    pub fn scan_done(&mut self, nlif_index: &[u8]) -> ProtonResult<bool> {
+        loop {
+            let messages = match recv_messages(&self.sock) {
+                Ok (messages) => messages,
+
+                // No more events are queued
+                Err (e) if e.io_kind() == ErrorKind::WouldBlock => return Ok (false),
+                Err (e) => return Err (e),
+            };
+
+            for message in messages {
+                let event: Genlmsghdr<Nl80211Cmd, Nl80211Attr> = decode(&message.nl_payload)?;
+
+                // Skip the scans of other interfaces
+                let ifindex = event.get_attr_handle()
+                    .get_attribute(Nl80211Attr::AttrIfindex)
+                    .map(|ifindex| ifindex.payload.clone());
+
+                if ifindex.as_deref() != Some (nlif_index) {
+                    continue;
+                }
+
+                match event.cmd {
+                    Nl80211Cmd::CmdNewScanResults => return Ok (true),
+                    Nl80211Cmd::CmdScanAborted => return Err (ProtonError::ScanAborted),
+                    _ => {},
+                }
+            }
+        }
+    }
+}
+
+/// Join a Netlink multicast group.
+/// 
+/// `NlSocket::set_mcast_groups` folds the groups into a bitmask, which
+/// `NETLINK_ADD_MEMBERSHIP` would read as a group ID, so the ID is set here.
+/// 
+/// # Parameters
+/// - `sock` (`&NlSocket`): the Netlink socket
+/// - `group` (`u32`): the ID of the multicast group
+/// 
+/// # Returns
+/// `ProtonResult<()>` indicating whether or not the group was joined.
+fn add_mcast_membership(sock: &NlSocket, group: u32) -> ProtonResult<()> {
+    // Note: the option value is only read during the call
+    let result = unsafe {
+        libc::setsockopt(
+            sock.as_raw_fd(),
+            libc::SOL_NETLINK,
+            libc::NETLINK_ADD_MEMBERSHIP,
+            &group as *const u32 as *const libc::c_void,
+            mem::size_of::<u32>() as libc::socklen_t,
+        )
+    };
+
+    match result {
+        0 => Ok (()),
+        _ => Err (ProtonError::from_io(io::Error::last_os_error())),
+    }
+}
+
+/// Construct an `nl80211` Netlink message.
+/// 
+/// # Parameters
+/// - `family_id` (`u16`): the Generic Netlink Family ID of `nl80211`
+/// - `cmd` (`Nl80211Cmd`): the `nl80211` command
+/// - `attrs` (`Vec<(Nl80211Attr, Vec<u8>)>`): the command attributes
+/// - `flags` (`Vec<NlmF>`): the Netlink flags
+/// 
+/// # Returns
+/// `ProtonResult<Nlmsghdr<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>>` containing
+/// the Netlink message, if its attributes could be constructed.
+fn nl80211_message(
+    family_id: u16,
+    cmd: Nl80211Cmd,
+    attrs: Vec<(Nl80211Attr, Vec<u8>)>,
+    flags: Vec<NlmF>,
+) -> ProtonResult<Nlmsghdr<u16, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>> {
+    // Set Generic Netlink attributes
+    let mut nlattrs: Vec<Nlattr<Nl80211Attr, Vec<u8>>> = vec![];
+    for (attr, payload) in attrs {
+        nlattrs.push(Nlattr::new(None, attr, payload)?);
+    }
+
+    // Construct the Generic Netlink header
+    let genlhdr = Genlmsghdr::new(
+        cmd,
+        NL_80211_GENL_VERSION,
+        nlattrs,
+    )?;
+
+    // Construct the Netlink header
+    Ok (Nlmsghdr::new(
+        None,      // Netlink header length
+        family_id, // Generic Netlink Family ID
+        flags,     // Netlink flags
+        None,      // Sequence number
+        None,      // Netlink port ID
+        genlhdr,   // Netlink header payload
+    ))
+}
+
+/// Receive the Netlink messages of one datagram.
+/// 
+/// Unlike `NlSocket::recv_nl`, which reports socket errors as text, this
+/// keeps the I/O error (e.g. `EAGAIN` if no message is queued on a
+/// non-blocking socket).
+/// 
+/// # Parameters
+/// - `sock` (`&NlSocket`): the Netlink socket
+/// 
+/// # Returns
+/// `ProtonResult<Vec<Nlmsghdr<Nlmsg, Vec<u8>>>>` containing each message of
+/// the datagram, with its payload left undecoded.
+fn recv_messages(sock: &NlSocket) -> ProtonResult<Vec<Nlmsghdr<Nlmsg, Vec<u8>>>> {
+    let mut datagram = vec![0; MAX_NL_LENGTH];
+    let len = sock.recv(&mut datagram, 0)?;
+
+    if len == 0 {
+        return Err (ProtonError::NoResponseFromNetlink);
+    }
+    datagram.truncate(len);
+
+    let mut buffer = StreamReadBuffer::new(datagram);
+    let mut messages = Vec::new();
+
+    while !buffer.at_end() {
+        messages.push(Nlmsghdr::deserialize(&mut buffer)?);
+    }
+
+    Ok (messages)
+}
+
+/// Decode the payload of a Netlink message.
+/// 
+/// # Parameters
+/// - `payload` (`&[u8]`): the payload, in native byte order
+/// 
+/// # Returns
+/// `ProtonResult<P>` containing the decoded payload, if it is well-formed.
+fn decode<P: Nl>(payload: &[u8]) -> ProtonResult<P> {
+    let mut buffer = StreamReadBuffer::new(payload);
+    buffer.set_size_hint(payload.len());
+
+    Ok (P::deserialize(&mut buffer)?)
+}
+
+/// Receive the responses to an `nl80211` request.
+/// 
+/// The responses end with the end of a dump (`NLMSG_DONE`), with an
+/// acknowledgement, or after a single response that is not part of a dump.
+/// An error message (`struct nlmsgerr`) ends them with the `errno` of the
+/// kernel, so that an error (e.g. `EBUSY` while the driver is busy) is kept
+/// and can be retried (see `RetryPolicy`).
+/// 
+/// # Parameters
+/// - `sock` (`&NlSocket`): the `nl80211` socket
+/// 
+/// # Returns
+/// `ProtonResult<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>>` containing the
+/// responses, or the error corresponding to the `errno` of the kernel (see
+/// `ProtonError::from_errno`).
+fn recv_responses(sock: &NlSocket) -> ProtonResult<Vec<Genlmsghdr<Nl80211Cmd, Nl80211Attr>>> {
+    let mut responses = Vec::new();
+
+    loop {
+        for message in recv_messages(sock)? {
+            match message.nl_type {
+                // An acknowledgement is an error message without an error
+                Nlmsg::Error => return match decode::<Nlmsgerr<Nlmsg>>(&message.nl_payload)?.error {
+                    0 => Ok (responses),
+                    errno => Err (ProtonError::from_errno(errno)),
+                },
+                Nlmsg::Done => return Ok (responses),
+                _ => {
+                    responses.push(decode(&message.nl_payload)?);
+
+                    if !message.nl_flags.contains(&NlmF::Multi) {
+                        return Ok (responses);
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Get the attributes nested in a Netlink attribute payload.
+/// 
+/// # Parameters
+/// - `payload` (`&[u8]`): the payload of the nesting attribute
+/// 
+/// # Returns
+/// A `Vec<(u16, &[u8])>` containing the type, without flags, and the
+/// payload of each nested attribute.  Parsing stops at the first malformed
+/// attribute.
+fn nested_attrs(payload: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut attrs = Vec::new();
+    let mut rest = payload;
+
+    // Each attribute starts with a 16-bit length (including the header) and a
+    // 16-bit type, and is padded to a multiple of 4 bytes
+    while rest.len() >= 4 {
+        let len = u16::from_ne_bytes([rest[0], rest[1]]) as usize;
+        let nla_type = u16::from_ne_bytes([rest[2], rest[3]]);
+
+        if len < 4 || len > rest.len() {
+            break;
+        }
+        attrs.push((nla_type & !(NLA_F_NESTED | NLA_F_NET_BYTEORDER), &rest[4..len]));
+
+        rest = &rest[((len + 3) & !3).min(rest.len())..];
+    }
+
+    attrs
+}
+
+/// Get the types of the attributes nested in a Netlink attribute payload.
+/// 
+/// # Parameters
+/// - `payload` (`&[u8]`): the payload of the nesting attribute
+/// 
+/// # Returns
+/// A `Vec<u16>` containing the type of each nested attribute, without
+/// flags.  Parsing stops at the first malformed attribute.
+fn nested_attr_types(payload: &[u8]) -> Vec<u16> {
+    nested_attrs(payload).into_iter()
+        .map(|(nla_type, _)| nla_type)
+        .collect()
+}
+
+/// Parse the bitrate of a station from its rate information.
+/// 
+/// The payload is that of `NL80211_STA_INFO_TX_BITRATE` or
+/// `NL80211_STA_INFO_RX_BITRATE`, i.e. nested `enum nl80211_rate_info`
+/// attributes.  The 32-bit `NL80211_RATE_INFO_BITRATE32` is preferred, since
+/// the 16-bit `NL80211_RATE_INFO_BITRATE` saturates above 6553.5 Mbit/s.
+/// 
+/// # Parameters
+/// - `rate_info` (`&[u8]`): the nested attributes, in native byte order
+/// 
+/// # Returns
+/// An `Option<f32>` containing the bitrate in Mbit/s, if the driver reports
+/// either attribute.
+pub fn parse_bitrate(rate_info: &[u8]) -> Option<f32> {
+    let attrs = nested_attrs(rate_info);
+    let find = |nla_type: u16| attrs.iter()
+        .find(|(t, _)| *t == nla_type)
+        .map(|(_, payload)| *payload);
+
+    // Both attributes are in units of 100 kbit/s
+    let bitrate = match find(NL80211_RATE_INFO_BITRATE32) {
+        Some (payload) => u32::from_ne_bytes(payload.get(0..4)?.try_into().ok()?),
+        None => u16::from_ne_bytes(find(NL80211_RATE_INFO_BITRATE)?.get(0..2)?.try_into().ok()?) as u32,
+    };
+
+    Some (bitrate as f32 / 10.0)
+}
+
+/// Parse the maximum regulatory transmit power of a channel from the bands
+/// of a wireless PHY.
+/// 
+/// The payload is that of `NL80211_ATTR_WIPHY_BANDS`, i.e. one nested
+/// attribute per band, whose `NL80211_BAND_ATTR_FREQS` lists the channels of
+/// the band.  Each channel has a center frequency
+/// (`NL80211_FREQUENCY_ATTR_FREQ`) and, unless it is disabled, a maximum
+/// transmit power (`NL80211_FREQUENCY_ATTR_MAX_TX_POWER`).
+/// 
+/// # Parameters
+/// - `bands` (`&[u8]`): the nested attributes, in native byte order
+/// - `frequency_mhz` (`u32`): the center frequency of the channel, in MHz
+/// 
+/// # Returns
+/// An `Option<u32>` containing the maximum transmit power of the channel, in
+/// mBm (hundredths of a dBm), if the channel reports one.
+pub fn parse_max_tx_power(bands: &[u8], frequency_mhz: u32) -> Option<u32> {
+    let u32_attr = |attrs: &[(u16, &[u8])], nla_type: u16| attrs.iter()
+        .find(|(t, _)| *t == nla_type)
+        .and_then(|(_, payload)| Some (u32::from_ne_bytes(payload.get(0..4)?.try_into().ok()?)));
+
+    nested_attrs(bands).into_iter()
+        .flat_map(|(_, band)| nested_attrs(band))
+        .filter(|(nla_type, _)| *nla_type == NL80211_BAND_ATTR_FREQS)
+        .flat_map(|(_, channels)| nested_attrs(channels))
+        .map(|(_, channel)| nested_attrs(channel))
+        .find(|channel| u32_attr(channel, NL80211_FREQUENCY_ATTR_FREQ) == Some (frequency_mhz))
+        .and_then(|channel| u32_attr(&channel, NL80211_FREQUENCY_ATTR_MAX_TX_POWER))
+}
+
+/// Receive the acknowledgement of a request.
+/// 
+/// Unlike `NlSocket::recv_ack`, which reports every error as a missing
+/// acknowledgement, this keeps the `errno` returned by the kernel.
+/// 
+/// # Parameters
+/// - `socket` (`&mut Socket`): the `nl80211` socket
+/// 
+/// # Returns
+/// `ProtonResult<()>` indicating whether or not the request succeeded.  If
+/// it did not, the error corresponds to its `errno` (see
+/// `ProtonError::from_errno`).
+fn recv_ack(socket: &mut Socket) -> ProtonResult<()> {
+    recv_responses(&socket.sock)?;
+
+    Ok (())
+}
+
+/// Get an attribute of a wireless PHY.
+/// 
+/// # Parameters
+/// - `socket` (`&mut Socket`): the `nl80211` socket
+/// - `wiphy` (`&[u8]`): a Netlink wireless PHY index
+/// - `attr` (`Nl80211Attr`): the attribute to get
+/// 
+/// # Returns
+/// `ProtonResult<Option<Vec<u8>>>` containing the payload of the attribute,
+/// or `None` if the driver does not report it.
+fn get_wiphy_attr(
+    socket: &mut Socket,
+    wiphy: &[u8],
+    attr: Nl80211Attr,
+) -> ProtonResult<Option<Vec<u8>>> {
+    // Construct the Netlink header
+    let nlhdr = nl80211_message(
+        socket.family_id,
+        Nl80211Cmd::CmdGetWiphy,
+        vec![(Nl80211Attr::AttrWiphy, wiphy.to_owned())],
+        vec![NlmF::Request],
+    )?;
+
+    // Get the Netlink socket
+    let nl80211sock = &mut socket.sock;
+
+    // Send header to the Netlink socket
+    nl80211sock.send_nl(nlhdr)?;
+
+    // Read the attribute back from the Netlink socket
+    let value = recv_responses(nl80211sock)?.iter()
+        .find_map(|response| {
+            response.get_attr_handle()
+                .get_attribute(attr.clone())
+                .map(|value| value.payload.clone())
+        });
+
+    Ok (value)
 }
 
 impl NetworkSocket for Socket {
-    fn get_all_stations(
+    fn get_station_entries(
         &mut self,
         nlif_index: &[u8],
-    ) -> ProtonResult<Vec<Station>> {
+    ) -> ProtonResult<Vec<StationEntry>> {
         // Get the Netlink socket
         let nl80211sock = &mut self.sock;
 
@@ -88,101 +762,202 @@ impl NetworkSocket for Socket {
 
         // Read results back from the Netlink socket
         let mut results = Vec::new();
-        let mut iter = nl80211sock.iter::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>();
 
-        while let Some(Ok(response)) = iter.next() {
-            match response.nl_type {
-                Nlmsg::Error => return Err (ProtonError::CouldNotGetDeviceInformation),
-                Nlmsg::Done => break,
-                _ => {
-                    let handle = response.nl_payload.get_attr_handle();
-                    results.push(Station::default().parse(handle));
-                },
+        for response in recv_responses(nl80211sock)? {
+            let handle = response.get_attr_handle();
+
+            // Parse the statistics that `Station` does not, from
+            // the nested station information
+            let info = handle.get_attribute(Nl80211Attr::AttrStaInfo)
+                .and_then(|info| info.get_nested_attributes::<Nl80211StaInfo>().ok());
+            let inactive_ms = info.as_ref()
+                .and_then(|info| info.get_attribute(Nl80211StaInfo::StaInfoInactiveTime))
+                .map(|inactive| parse_u32(&inactive.payload));
+            let flags = info.as_ref()
+                .and_then(|info| info.get_attribute(Nl80211StaInfo::StaInfoStaFlags))
+                .and_then(|flags| StationFlags::from_flag_update(&flags.payload));
+            let tx_bitrate_mbps = info.as_ref()
+                .and_then(|info| info.get_attribute(Nl80211StaInfo::StaInfoTxBitrate))
+                .and_then(|rate_info| parse_bitrate(&rate_info.payload));
+            let rx_bitrate_mbps = info.as_ref()
+                .and_then(|info| info.get_attribute(Nl80211StaInfo::StaInfoRxBitrate))
+                .and_then(|rate_info| parse_bitrate(&rate_info.payload));
+
+            // Prefer the 64-bit byte counters, since the 32-bit ones wrap
+            // around after 4 GiB
+            let bytes = |bytes64: Nl80211StaInfo, bytes32: Nl80211StaInfo| {
+                let info = info.as_ref()?;
+
+                match info.get_attribute(bytes64) {
+                    Some (bytes) => Some (u64::from_ne_bytes(bytes.payload.get(0..8)?.try_into().ok()?)),
+                    None => Some (u32::from_ne_bytes(info.get_attribute(bytes32)?.payload.get(0..4)?.try_into().ok()?) as u64),
+                }
             };
+            let rx_bytes = bytes(Nl80211StaInfo::StaInfoRxBytes64, Nl80211StaInfo::StaInfoRxBytes);
+            let tx_bytes = bytes(Nl80211StaInfo::StaInfoTxBytes64, Nl80211StaInfo::StaInfoTxBytes);
+
+            results.push(StationEntry {
+                station: Station::default().parse(handle),
+                inactive_ms,
+                flags,
+                tx_bitrate_mbps,
+                rx_bitrate_mbps,
+                rx_bytes,
+                tx_bytes,
+            });
         }
 
         Ok (results)
     }
 
-    // fn deauthenticate_by_mac(
-    //     &mut self,
-    //     mac: MacAddr,
-    // ) -> ProtonResult<()> {
-    //     // Get the Netlink socket
-    //     let nl80211sock = &mut self.sock;
-
-    //     // Convert the MAC address into a vector of octets
-    //     let mac_vec: Vec<u8> = vec![
-    //         mac.0,
-    //         mac.1,
-    //         mac.2,
-    //         mac.3,
-    //         mac.4,
-    //         mac.5,
-    //     ];
-
-    //     // Set Generic Netlink attributes
-    //     let mut attrs: Vec<Nlattr<Nl80211Attr, Vec<u8>>> = vec![];
-
-    //     // Set MAC attribute (MAC address)
-    //     let mac_attr = Nlattr::new(
-    //         None,
-    //         Nl80211Attr::AttrMac,
-    //         mac_vec,
-    //     )?;
-    //     attrs.push(mac_attr);
-
-    //     // Set Reason Code attribute (reason for deauthentication)
-    //     let rc_attr = Nlattr::new(
-    //         None,
-    //         Nl80211Attr::AttrReasonCode,
-    //         vec![1, 0], // unspecified reason
-    //     )?;
-    //     attrs.push(rc_attr);
-
-    //     // Construct the Generic Netlink header
-    //     let genlhdr = Genlmsghdr::new(
-    //         Nl80211Cmd::CmdDeauthenticate,
-    //         NL_80211_GENL_VERSION,
-    //         attrs,
-    //     )?;
-        
-    //     // Set the Netlink header length
-    //     let len = None;
+    fn supports_sae(
+        &mut self,
+        wiphy: &[u8],
+    ) -> ProtonResult<bool> {
+        let flags = get_wiphy_attr(self, wiphy, Nl80211Attr::AttrFeatureFlags)?;
 
-    //     // Set the Generic Netlink Family ID
-    //     let nl_type = self.family_id;
+        Ok (flags.is_some_and(|flags| parse_u32(&flags) & NL80211_FEATURE_SAE != 0))
+    }
 
-    //     // Set the Netlink flags
-    //     let flags = vec![NlmF::Request];
+    fn supports_owe(
+        &mut self,
+        wiphy: &[u8],
+    ) -> ProtonResult<bool> {
+        let suites = get_wiphy_attr(self, wiphy, Nl80211Attr::AttrCipherSuites)?;
+
+        // The cipher suites are an array of 32-bit selectors
+        Ok (suites.is_some_and(|suites| {
+            suites.chunks_exact(4).any(|suite| parse_u32(&suite.to_vec()) == CIPHER_SUITE_BIP_CMAC_128)
+        }))
+    }
 
-    //     // Set the sequence number
-    //     let seq = None;
+    fn get_bands(
+        &mut self,
+        wiphy: &[u8],
+    ) -> ProtonResult<Vec<u16>> {
+        let bands = get_wiphy_attr(self, wiphy, Nl80211Attr::AttrWiphyBands)?;
 
-    //     // Set the Netlink port ID
-    //     let pid = None;
+        // Each supported band is a nested attribute whose type is the band
+        Ok (bands.map(|bands| nested_attr_types(&bands)).unwrap_or_default())
+    }
 
-    //     // Set the Netlink header payload (contains Generic Netlink header)
-    //     let payload = genlhdr;
+    fn trigger_scan(
+        &mut self,
+        nlif_index: &[u8],
+    ) -> ProtonResult<()> {
+        // Construct the Netlink header
+        let nlhdr = nl80211_message(
+            self.family_id,
+            Nl80211Cmd::CmdTriggerScan,
+            vec![(Nl80211Attr::AttrIfindex, nlif_index.to_owned())],
+            vec![NlmF::Request, NlmF::Ack],
+        )?;
 
-    //     // Construct the Netlink header
-    //     let nlhdr = Nlmsghdr::new(len, nl_type, flags, seq, pid, payload);
+        // Send header to the Netlink socket
+        self.sock.send_nl(nlhdr)?;
 
-    //     // Send header to the Netlink socket
-    //     nl80211sock.send_nl(nlhdr)?;
+        // The driver acknowledges the request, or returns an error
+        // if it is busy (e.g. a scan is already in progress)
+        recv_ack(self)
+    }
 
-    //     // Read results back from the Netlink socket
-    //     let mut iter = nl80211sock.iter::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>();
+    fn get_scan_results(
+        &mut self,
+        nlif_index: &[u8],
+    ) -> ProtonResult<Vec<ApInfo>> {
+        // Construct the Netlink header
+        let nlhdr = nl80211_message(
+            self.family_id,
+            Nl80211Cmd::CmdGetScan,
+            vec![(Nl80211Attr::AttrIfindex, nlif_index.to_owned())],
+            vec![NlmF::Request, NlmF::Dump],
+        )?;
+
+        // Get the Netlink socket
+        let nl80211sock = &mut self.sock;
 
-    //     if let Some (Ok (response)) = iter.next() {
-    //         dbg!(&response.nl_payload);
+        // Send header to the Netlink socket
+        nl80211sock.send_nl(nlhdr)?;
 
-    //         match response.nl_type {
-    //             Nlmsg::Done => Ok (()),
-    //             _ => Err (ProtonError::CouldNotDeauthenticateDevice (mac)),
-    //         }
-    //     } else {
-    //         Err (ProtonError::NoResponseFromNetlink)
-    //     }
-    // }
+        // Read results back from the Netlink socket
+        let mut results = Vec::new();
+
+        for response in recv_responses(nl80211sock)? {
+            let handle = response.get_attr_handle();
+
+            // Each result holds one BSS as nested attributes
+            if let Some (bss) = handle.get_attribute(Nl80211Attr::AttrBss) {
+                let bss_handle = bss.get_nested_attributes::<Nl80211Bss>()?;
+                results.extend(ApInfo::from_bss(&bss_handle));
+            }
+        }
+
+        Ok (results)
+    }
+
+    fn get_max_tx_power(
+        &mut self,
+        wiphy: &[u8],
+        frequency_mhz: u32,
+    ) -> ProtonResult<Option<u32>> {
+        let bands = get_wiphy_attr(self, wiphy, Nl80211Attr::AttrWiphyBands)?;
+
+        Ok (bands.and_then(|bands| parse_max_tx_power(&bands, frequency_mhz)))
+    }
+
+    fn set_tx_power(
+        &mut self,
+        wiphy: &[u8],
+        mbm: u32,
+    ) -> ProtonResult<()> {
+        // Construct the Netlink header
+        let nlhdr = nl80211_message(
+            self.family_id,
+            Nl80211Cmd::CmdSetWiphy,
+            vec![
+                (Nl80211Attr::AttrWiphy, wiphy.to_owned()),
+                (Nl80211Attr::AttrWiphyTxPowerSetting, NL80211_TX_POWER_FIXED.to_ne_bytes().to_vec()),
+                (Nl80211Attr::AttrWiphyTxPowerLevel, mbm.to_ne_bytes().to_vec()),
+            ],
+            vec![NlmF::Request, NlmF::Ack],
+        )?;
+
+        // Send header to the Netlink socket
+        self.sock.send_nl(nlhdr)?;
+
+        // The driver acknowledges the request, or returns an error
+        // (e.g. `EINVAL` if the power level is outside of its supported range)
+        recv_ack(self)
+    }
+
+    fn deauthenticate_by_mac(
+        &mut self,
+        nlif_index: &[u8],
+        mac: MacAddr,
+        reason: ReasonCode,
+    ) -> ProtonResult<()> {
+        // Construct the Netlink header
+        let nlhdr = nl80211_message(
+            self.family_id,
+            Nl80211Cmd::CmdDelStation,
+            vec![
+                (Nl80211Attr::AttrIfindex, nlif_index.to_owned()),
+                (Nl80211Attr::AttrMac, mac.octets().to_vec()),
+                (Nl80211Attr::AttrMgmtSubtype, vec![MGMT_SUBTYPE_DEAUTH]),
+                (Nl80211Attr::AttrReasonCode, reason.as_u16().to_ne_bytes().to_vec()),
+            ],
+            vec![NlmF::Request, NlmF::Ack],
+        )?;
+
+        // Get the Netlink socket
+        let nl80211sock = &mut self.sock;
+
+        // Send header to the Netlink socket
+        nl80211sock.send_nl(nlhdr)?;
+
+        // The driver acknowledges the request, or returns an error
+        // if the station could not be deauthenticated
+        nl80211sock.recv_ack()
+            .map_err(|_| ProtonError::CouldNotDeauthenticateDevice (mac))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,269 @@
+//! Site survey of nearby access points.
+
+use neli::nlattr::AttrHandle;
+
+use nl80211::{
+    parse_u32,
+    Nl80211Bss,
+};
+
+use serde::Serialize;
+
+use proton_mac::MacAddr;
+
+/// Information element ID of the SSID.
+const IE_SSID: u8 = 0;
+
+/// Information element ID of the Robust Security Network (WPA2/WPA3).
+const IE_RSN: u8 = 48;
+
+/// Information element ID of vendor-specific data (used by WPA).
+const IE_VENDOR: u8 = 221;
+
+/// Vendor-specific OUI and type identifying a WPA information element.
+const WPA_OUI_TYPE: [u8; 4] = [0x00, 0x50, 0xf2, 0x01];
+
+/// Capability bit indicating that the network requires privacy (WEP).
+const CAPABILITY_PRIVACY: u16 = 1 << 4;
+
+/// IEEE 802.11 OUI used by RSN authentication key management suites.
+const RSN_OUI: [u8; 3] = [0x00, 0x0f, 0xac];
+
+/// RSN AKM suite type for pre-shared keys (WPA2 Personal).
+const AKM_PSK: u8 = 2;
+
+/// RSN AKM suite type for SAE (WPA3 Personal).
+const AKM_SAE: u8 = 8;
+
+/// RSN AKM suite type for Opportunistic Wireless Encryption (Enhanced Open).
+const AKM_OWE: u8 = 18;
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+/// The security mode advertised by a nearby access point.
+pub enum ApSecurity {
+    /// No encryption.
+    Open,
+
+    /// Wired Equivalent Privacy (insecure).
+    Wep,
+
+    /// Wi-Fi Protected Access (original).
+    Wpa,
+
+    /// WPA2 Personal.
+    Wpa2,
+
+    /// WPA3 Personal.
+    Wpa3,
+
+    /// WPA2/WPA3 transitional mode.
+    Wpa2Wpa3,
+
+    /// Opportunistic Wireless Encryption (Enhanced Open).
+    Owe,
+}
+
+#[derive(Serialize, Clone, Debug)]
+/// Information about a nearby access point, found by a site survey.
+pub struct ApInfo {
+    /// SSID of the access point.
+    pub ssid: String,
+
+    /// BSSID (MAC address) of the access point.
+    pub bssid: MacAddr,
+
+    /// Operating frequency of the access point, in MHz.
+    pub frequency_mhz: u32,
+
+    /// Operating channel of the access point.
+    pub channel: u8,
+
+    /// Signal strength of the access point, in dBm.
+    pub signal_strength: i32,
+
+    /// Security mode advertised by the access point.
+    pub security: ApSecurity,
+}
+
+impl ApInfo {
+    /// Convert a set of `nl80211` BSS attributes into an `ApInfo`.
+    /// 
+    /// # Parameters
+    /// - `handle` (`&AttrHandle<Nl80211Bss>`): the nested BSS attributes
+    /// 
+    /// # Returns
+    /// An `Option<ApInfo>` containing the access point information, if the
+    /// BSS attributes contained a BSSID.
+    pub fn from_bss(handle: &AttrHandle<Nl80211Bss>) -> Option<Self> {
+        // Get hardware address of the access point
+        let octets: [u8; 6] = handle.get_attribute(Nl80211Bss::BssBssid)?
+            .payload
+            .as_slice()
+            .try_into()
+            .ok()?;
+        let bssid: MacAddr = octets.into();
+
+        // Get operating frequency of the access point
+        let frequency_mhz = handle.get_attribute(Nl80211Bss::BssFrequency)
+            .map(|attr| parse_u32(&attr.payload))
+            .unwrap_or_default();
+
+        // Get signal strength of the access point (reported in mBm)
+        let signal_strength = handle.get_attribute(Nl80211Bss::BssSignalMbm)
+            .map(|attr| parse_u32(&attr.payload) as i32 / 100)
+            .unwrap_or_default();
+
+        // Get capabilities of the access point
+        let capability = handle.get_attribute(Nl80211Bss::BssCapability)
+            .and_then(|attr| attr.payload.get(..2).map(|c| u16::from_le_bytes([c[0], c[1]])))
+            .unwrap_or_default();
+
+        // Get information elements of the access point
+        let ies = handle.get_attribute(Nl80211Bss::BssInformationElements)
+            .map(|attr| attr.payload.clone())
+            .unwrap_or_default();
+
+        Some (Self {
+            ssid: parse_ssid(&ies),
+            bssid,
+            frequency_mhz,
+            channel: frequency_to_channel(frequency_mhz),
+            signal_strength,
+            security: parse_security(&ies, capability),
+        })
+    }
+}
+
+/// Convert an operating frequency into a Wi-Fi channel number.
+/// 
+/// # Parameters
+/// - `frequency_mhz` (`u32`): the operating frequency, in MHz
+/// 
+/// # Returns
+/// A `u8` containing the channel number, or `0` if the frequency
+/// is not a known Wi-Fi channel.
+pub fn frequency_to_channel(frequency_mhz: u32) -> u8 {
+    match frequency_mhz {
+        2484 => 14,
+        2412..=2472 => ((frequency_mhz - 2407) / 5) as u8,
+        5955..=7115 => ((frequency_mhz - 5950) / 5) as u8,
+        5160..=5885 => ((frequency_mhz - 5000) / 5) as u8,
+        _ => 0,
+    }
+}
+
+/// Choose the least congested channel out of a list of candidates.
+/// 
+/// Each candidate is scored by the signal strength of every surveyed access
+/// point whose channel overlaps it, so that a strong neighbor costs more
+/// than a distant one.  On 2.4 GHz, channels closer than 5 channels apart
+/// overlap; on 5 GHz, only identical channels overlap.
+/// 
+/// Channel numbers are only unique within a band, so `aps` should only hold
+/// access points on the band of the candidates (see `ApInfo::frequency_mhz`).
+/// 
+/// # Parameters
+/// - `aps` (`&[ApInfo]`): the surveyed access points
+/// - `candidates` (`&[u8]`): the channels to choose from
+/// 
+/// # Returns
+/// An `Option<u8>` containing the least congested channel, or `None` if
+/// there were no candidates.  Ties go to the earliest candidate.
+pub fn best_channel(aps: &[ApInfo], candidates: &[u8]) -> Option<u8> {
+    // Check if two channels overlap
+    let overlaps = |a: u8, b: u8| if a <= 14 && b <= 14 {
+        a.abs_diff(b) < 5
+    } else {
+        a == b
+    };
+
+    // Score a channel by the congestion of its overlapping neighbors
+    let score = |channel: u8| -> i64 {
+        aps.iter()
+            .filter(|ap| overlaps(ap.channel, channel))
+            .map(|ap| (ap.signal_strength as i64 + 100).max(1))
+            .sum()
+    };
+
+    candidates.iter()
+        .copied()
+        .min_by_key(|channel| score(*channel))
+}
+
+/// Iterate over the information elements in a buffer.
+fn information_elements(ies: &[u8]) -> impl Iterator<Item = (u8, &[u8])> {
+    let mut rest = ies;
+
+    std::iter::from_fn(move || {
+        let (&id, tail) = rest.split_first()?;
+        let (&len, tail) = tail.split_first()?;
+        let data = tail.get(..len as usize)?;
+        rest = &tail[len as usize..];
+
+        Some ((id, data))
+    })
+}
+
+/// Parse the SSID out of a list of information elements.
+fn parse_ssid(ies: &[u8]) -> String {
+    information_elements(ies)
+        .find(|(id, _)| *id == IE_SSID)
+        .map(|(_, data)| String::from_utf8_lossy(data).to_string())
+        .unwrap_or_default()
+}
+
+/// Determine the security mode from a list of information elements.
+fn parse_security(ies: &[u8], capability: u16) -> ApSecurity {
+    // Check for an RSN element (WPA2, WPA3, OWE)
+    if let Some ((_, rsn)) = information_elements(ies).find(|(id, _)| *id == IE_RSN) {
+        let akms = parse_rsn_akms(rsn);
+        let psk = akms.contains(&AKM_PSK);
+        let sae = akms.contains(&AKM_SAE);
+
+        return match (psk, sae) {
+            (true, true) => ApSecurity::Wpa2Wpa3,
+            (false, true) => ApSecurity::Wpa3,
+            _ if akms.contains(&AKM_OWE) => ApSecurity::Owe,
+            _ => ApSecurity::Wpa2,
+        };
+    }
+
+    // Check for a WPA vendor element
+    if information_elements(ies).any(|(id, data)| id == IE_VENDOR && data.starts_with(&WPA_OUI_TYPE)) {
+        return ApSecurity::Wpa;
+    }
+
+    // Fall back to the privacy capability
+    if capability & CAPABILITY_PRIVACY != 0 {
+        ApSecurity::Wep
+    } else {
+        ApSecurity::Open
+    }
+}
+
+/// Parse the authentication key management suite types out of an RSN element.
+fn parse_rsn_akms(rsn: &[u8]) -> Vec<u8> {
+    // Skip version (2 octets) and group cipher suite (4 octets)
+    let mut offset = 6;
+
+    // Skip pairwise cipher suites
+    let pairwise_count = match rsn.get(offset..offset + 2) {
+        Some (count) => u16::from_le_bytes([count[0], count[1]]) as usize,
+        None => return Vec::new(),
+    };
+    offset += 2 + 4 * pairwise_count;
+
+    // Read AKM suites
+    let akm_count = match rsn.get(offset..offset + 2) {
+        Some (count) => u16::from_le_bytes([count[0], count[1]]) as usize,
+        None => return Vec::new(),
+    };
+    offset += 2;
+
+    rsn.get(offset..offset + 4 * akm_count)
+        .unwrap_or_default()
+        .chunks_exact(4)
+        .filter(|suite| suite[..3] == RSN_OUI)
+        .map(|suite| suite[3])
+        .collect()
+}
@@ -0,0 +1,58 @@
+//! Testing per-device bandwidth accounting.
+
+use proton_dev::{
+    Accounting,
+    Counters,
+};
+
+use proton_mac::MacAddr;
+
+#[test]
+fn accumulate_counters() {
+    let mac = MacAddr (0x12, 0x34, 0x56, 0x78, 0x90, 0xab);
+    let mut accounting = Accounting::new();
+
+    accounting.record(mac, 100, 10);
+    accounting.record(mac, 50, 5);
+
+    assert_eq!(accounting.snapshot(), vec![(mac, Counters { rx_bytes: 150, tx_bytes: 15 })]);
+}
+
+#[test]
+fn counters_saturate() {
+    let mac = MacAddr (0x12, 0x34, 0x56, 0x78, 0x90, 0xab);
+    let mut accounting = Accounting::new();
+
+    accounting.record(mac, u64::MAX, 0);
+    accounting.record(mac, 1, 0);
+
+    assert_eq!(accounting.snapshot()[0].1.rx_bytes, u64::MAX);
+}
+
+#[test]
+fn record_station_totals() {
+    let mac = MacAddr (0x12, 0x34, 0x56, 0x78, 0x90, 0xab);
+    let mut accounting = Accounting::new();
+
+    accounting.record_totals(mac, 100, 10);
+    accounting.record_totals(mac, 150, 15);
+    accounting.record_totals(mac, 150, 15);
+
+    assert_eq!(accounting.snapshot(), vec![(mac, Counters { rx_bytes: 150, tx_bytes: 15 })]);
+}
+
+#[test]
+fn record_reset_station_totals() {
+    let mac = MacAddr (0x12, 0x34, 0x56, 0x78, 0x90, 0xab);
+    let mut accounting = Accounting::new();
+
+    // The station reconnects between scans
+    accounting.record_totals(mac, 100, 10);
+    accounting.record_totals(mac, 40, 4);
+    assert_eq!(accounting.snapshot()[0].1, Counters { rx_bytes: 140, tx_bytes: 14 });
+
+    // The station disconnects, then reconnects with larger totals
+    accounting.retain(&[]);
+    accounting.record_totals(mac, 60, 6);
+    assert_eq!(accounting.snapshot()[0].1, Counters { rx_bytes: 200, tx_bytes: 20 });
+}
@@ -0,0 +1,54 @@
+//! Testing station bitrate parsing.
+
+use proton_dev::parse_bitrate;
+
+/// Build a Netlink attribute, padded to a multiple of 4 bytes.
+fn nlattr(nla_type: u16, payload: &[u8]) -> Vec<u8> {
+    let mut attr = Vec::new();
+    attr.extend_from_slice(&(4 + payload.len() as u16).to_ne_bytes());
+    attr.extend_from_slice(&nla_type.to_ne_bytes());
+    attr.extend_from_slice(payload);
+    attr.resize((attr.len() + 3) & !3, 0);
+
+    attr
+}
+
+#[test]
+fn parse_vht_rate_info() {
+    // 866.7 Mbit/s (VHT-MCS 9, 80 MHz, 2 spatial streams), as reported by
+    // `iw station dump`: BITRATE, BITRATE32, VHT_MCS, VHT_NSS, 80_MHZ_WIDTH
+    let rate_info = [
+        nlattr(1, &8667u16.to_ne_bytes()),
+        nlattr(5, &8667u32.to_ne_bytes()),
+        nlattr(6, &[9]),
+        nlattr(7, &[2]),
+        nlattr(8, &[]),
+    ].concat();
+
+    assert_eq!(parse_bitrate(&rate_info), Some (866.7));
+}
+
+#[test]
+fn parse_legacy_rate_info() {
+    // 54 Mbit/s (802.11g), which older drivers report without BITRATE32
+    let rate_info = nlattr(1, &540u16.to_ne_bytes());
+
+    assert_eq!(parse_bitrate(&rate_info), Some (54.0));
+}
+
+#[test]
+fn prefer_32_bit_bitrate() {
+    // BITRATE saturates at 6553.5 Mbit/s, while BITRATE32 does not
+    let rate_info = [
+        nlattr(1, &u16::MAX.to_ne_bytes()),
+        nlattr(5, &96_077u32.to_ne_bytes()),
+    ].concat();
+
+    assert_eq!(parse_bitrate(&rate_info), Some (9607.7));
+}
+
+#[test]
+fn missing_bitrate() {
+    assert_eq!(parse_bitrate(&nlattr(6, &[9])), None);
+    assert_eq!(parse_bitrate(&[]), None);
+}
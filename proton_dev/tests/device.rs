@@ -0,0 +1,103 @@
+//! Testing connected device serialization.
+
+use std::{
+    net::Ipv4Addr,
+    time::SystemTime,
+};
+
+use serde_json::Value;
+
+use proton_dev::{
+    Device,
+    devices_to_csv,
+    devices_to_json,
+    StationFlags,
+};
+
+/// Construct a connected device.
+fn device(hostname: Option<&str>) -> Device {
+    Device {
+        mac: [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into(),
+        ipv4: Ipv4Addr::new(192, 168, 0, 2),
+        ipv6: None,
+        signal_strength: -42,
+        signal_avg: -42,
+        tx_bitrate_mbps: 72.2,
+        rx_bitrate_mbps: 65.0,
+        rx_bytes: 1_048_576,
+        tx_bytes: 4_194_304,
+        connection_time: 60,
+        inactive_ms: 1500,
+        authorized: true,
+        flags: StationFlags::AUTHORIZED | StationFlags::AUTHENTICATED | StationFlags::ASSOCIATED,
+        connected_since: SystemTime::now(),
+        frequency_mhz: 2437,
+        channel: 6,
+        randomized: false,
+        hostname: hostname.map(str::to_string),
+        station: None,
+    }
+}
+
+#[test]
+fn serialize_devices() {
+    let device = device(Some ("laptop"));
+
+    let json: Value = serde_json::from_str(&devices_to_json(&[device]).unwrap()).unwrap();
+    let devices = json.as_array().unwrap();
+
+    assert_eq!(devices.len(), 1);
+    for key in ["mac", "ipv4", "ipv6", "signal_strength", "signal_avg", "tx_bitrate_mbps", "rx_bitrate_mbps", "rx_bytes", "tx_bytes", "connection_time", "inactive_ms", "connected_since", "frequency_mhz", "channel", "randomized", "hostname"] {
+        assert!(devices[0].get(key).is_some(), "missing key {}", key);
+    }
+    assert_eq!(devices[0]["ipv4"], "192.168.0.2");
+    assert_eq!(devices[0]["hostname"], "laptop");
+}
+
+#[test]
+fn serialize_no_devices() {
+    assert_eq!(devices_to_json(&[]).unwrap(), "[]");
+}
+
+#[test]
+fn export_csv() {
+    let csv = devices_to_csv(&[device(Some ("laptop")), device(None)]);
+    let lines = csv.lines().collect::<Vec<&str>>();
+
+    assert_eq!(lines, [
+        "mac,ipv4,signal_strength,connection_time,vendor,hostname",
+        "12:34:56:78:90:ab,192.168.0.2,-42,60,,laptop",
+        "12:34:56:78:90:ab,192.168.0.2,-42,60,,",
+    ]);
+}
+
+#[test]
+fn export_csv_quotes_fields() {
+    let csv = devices_to_csv(&[device(Some ("Bob's \"laptop\", upstairs"))]);
+
+    assert!(csv.ends_with(",\"Bob's \"\"laptop\"\", upstairs\"\r\n"));
+}
+
+#[test]
+fn export_no_devices_csv() {
+    assert_eq!(devices_to_csv(&[]), "mac,ipv4,signal_strength,connection_time,vendor,hostname\r\n");
+}
+
+#[test]
+fn signal_quality_curve() {
+    let mut device = device(None);
+
+    let quality = |device: &mut Device, signal_strength: i8| {
+        device.signal_strength = signal_strength;
+        device.signal_quality()
+    };
+
+    assert_eq!(quality(&mut device, -50), 100);
+    assert_eq!(quality(&mut device, -75), 50);
+    assert_eq!(quality(&mut device, -100), 0);
+
+    // Clamped outside of the curve
+    assert_eq!(quality(&mut device, -30), 100);
+    assert_eq!(quality(&mut device, -128), 0);
+    assert_eq!(quality(&mut device, 127), 100);
+}
@@ -0,0 +1,40 @@
+//! Testing station flag parsing.
+
+use proton_dev::StationFlags;
+
+/// Build a `struct nl80211_sta_flag_update` payload.
+fn flag_update(mask: StationFlags, set: StationFlags) -> Vec<u8> {
+    [mask.bits().to_ne_bytes(), set.bits().to_ne_bytes()].concat()
+}
+
+#[test]
+fn parse_authorized_station() {
+    let reported = StationFlags::AUTHORIZED | StationFlags::AUTHENTICATED | StationFlags::ASSOCIATED;
+    let flags = StationFlags::from_flag_update(&flag_update(reported, reported)).unwrap();
+
+    assert!(flags.authorized());
+    assert!(flags.contains(StationFlags::AUTHENTICATED | StationFlags::ASSOCIATED));
+    assert!(!flags.contains(StationFlags::WME));
+}
+
+#[test]
+fn parse_station_mid_handshake() {
+    let reported = StationFlags::AUTHORIZED | StationFlags::AUTHENTICATED | StationFlags::ASSOCIATED;
+    let set = StationFlags::AUTHENTICATED | StationFlags::ASSOCIATED;
+    let flags = StationFlags::from_flag_update(&flag_update(reported, set)).unwrap();
+
+    assert!(!flags.authorized());
+    assert!(flags.contains(set));
+}
+
+#[test]
+fn ignore_flags_outside_mask() {
+    let flags = StationFlags::from_flag_update(&flag_update(StationFlags::WME, StationFlags::AUTHORIZED)).unwrap();
+
+    assert_eq!(flags, StationFlags::empty());
+}
+
+#[test]
+fn reject_short_payload() {
+    assert_eq!(StationFlags::from_flag_update(&[0xff; 7]), None);
+}
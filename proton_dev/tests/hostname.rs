@@ -0,0 +1,36 @@
+//! Testing hostname-based access policy.
+
+use proton_dev::{
+    glob_match,
+    HostnamePolicy,
+};
+
+#[test]
+fn match_globs() {
+    assert!(glob_match("*-iphone", "Alice-iPhone"));
+    assert!(glob_match("printer-?", "printer-2"));
+    assert!(glob_match("*", ""));
+    assert!(glob_match("*lap*top*", "my-laptop-top"));
+    assert!(!glob_match("*-iphone", "alice-ipad"));
+    assert!(!glob_match("printer-?", "printer-10"));
+    assert!(!glob_match("tv", "tv-living-room"));
+}
+
+#[test]
+fn allow_list_denies_unresolved() {
+    let policy = HostnamePolicy::Allow (vec!["*-iphone".to_string(), "desk-*".to_string()]);
+
+    assert!(policy.permits(Some ("bob-iphone")));
+    assert!(policy.permits(Some ("DESK-01")));
+    assert!(!policy.permits(Some ("smart-tv")));
+    assert!(!policy.permits(None));
+}
+
+#[test]
+fn deny_list_permits_unresolved() {
+    let policy = HostnamePolicy::Deny (vec!["*-tv".to_string()]);
+
+    assert!(!policy.permits(Some ("living-room-tv")));
+    assert!(policy.permits(Some ("bob-iphone")));
+    assert!(policy.permits(None));
+}
@@ -0,0 +1,32 @@
+//! Testing device manager metrics.
+
+#![cfg(feature = "metrics")]
+
+use std::time::Duration;
+
+use proton_dev::Metrics;
+
+#[test]
+fn render_prometheus() {
+    let mut metrics = Metrics {
+        clients: 1,
+        signal: vec![([0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into(), -42)],
+        ..Default::default()
+    };
+    metrics.record_scan(Duration::from_millis(2_500));
+    metrics.record_deauths(2);
+
+    let text = metrics.render_prometheus();
+    let samples = text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<&str>>();
+
+    assert_eq!(samples, vec![
+        "proton_clients 1",
+        "proton_client_signal_dbm{mac=\"12:34:56:78:90:ab\"} -42",
+        "proton_scans_total 1",
+        "proton_scan_duration_seconds 2.5",
+        "proton_deauths_total 2",
+    ]);
+    assert!(text.contains("# TYPE proton_deauths_total counter\n"));
+}
@@ -0,0 +1,58 @@
+//! Testing device presence tracking.
+
+use std::time::{
+    Duration,
+    SystemTime,
+};
+
+use proton_dev::PresenceTracker;
+
+use proton_mac::MacAddr;
+
+/// The MAC address of a connected device.
+const MAC: [u8; 6] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab];
+
+/// The MAC address of another connected device.
+const OTHER_MAC: [u8; 6] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xac];
+
+/// Get the time `secs` seconds after the start of the test.
+fn at(secs: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 + secs)
+}
+
+#[test]
+fn accumulate_first_and_last_seen() {
+    let mut tracker = PresenceTracker::new(Duration::from_secs(3_600));
+    let mac = MacAddr::from(MAC);
+
+    tracker.record_at(&[mac], at(0));
+    tracker.record_at(&[mac, OTHER_MAC.into()], at(60));
+    tracker.record_at(&[mac], at(120));
+
+    let presence = tracker.get(mac).unwrap();
+    assert_eq!(presence.first_seen, at(0));
+    assert_eq!(presence.last_seen, at(120));
+    assert_eq!(presence.seen_count, 3);
+
+    let other = tracker.get(OTHER_MAC.into()).unwrap();
+    assert_eq!(other.first_seen, at(60));
+    assert_eq!(other.seen_count, 1);
+
+    assert_eq!(tracker.seen_since(at(100)), vec![mac]);
+}
+
+#[test]
+fn evict_absent_devices() {
+    let mut tracker = PresenceTracker::new(Duration::from_secs(60));
+    let mac = MacAddr::from(MAC);
+
+    tracker.record_at(&[mac, OTHER_MAC.into()], at(0));
+    assert!(tracker.record_at(&[mac], at(60)).is_empty());
+
+    assert_eq!(tracker.record_at(&[mac], at(61)), vec![OTHER_MAC.into()]);
+    assert_eq!(tracker.len(), 1);
+
+    // A returning device starts over
+    tracker.record_at(&[OTHER_MAC.into()], at(62));
+    assert_eq!(tracker.get(OTHER_MAC.into()).unwrap().first_seen, at(62));
+}
@@ -0,0 +1,75 @@
+//! Testing the Netlink retry policy.
+
+use std::time::Duration;
+
+use proton_dev::RetryPolicy;
+
+use proton_err::ProtonError;
+
+#[tokio::test]
+async fn retry_until_success() {
+    let policy = RetryPolicy::new(3, Duration::from_millis(1));
+    let mut calls = 0;
+
+    let result = policy.run(|| {
+        calls += 1;
+        if calls < 3 {
+            Err (ProtonError::NoResponseFromNetlink)
+        } else {
+            Ok (calls)
+        }
+    }).await;
+
+    assert_eq!(result.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn retry_busy_driver() {
+    let policy = RetryPolicy::new(3, Duration::from_millis(1));
+    let mut calls = 0;
+
+    // A dump that fails with `-EBUSY` until the driver is ready
+    let result = policy.run(|| {
+        calls += 1;
+        if calls < 2 {
+            Err (ProtonError::from_errno(-16))
+        } else {
+            Ok (calls)
+        }
+    }).await;
+
+    assert_eq!(result.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn give_up_after_all_attempts() {
+    let policy = RetryPolicy::new(2, Duration::from_millis(1));
+    let mut calls = 0;
+
+    let result: Result<(), ProtonError> = policy.run(|| {
+        calls += 1;
+        Err (ProtonError::NoResponseFromNetlink)
+    }).await;
+
+    assert!(matches!(result, Err (ProtonError::NoResponseFromNetlink)));
+    assert_eq!(calls, 2);
+}
+
+#[tokio::test]
+async fn do_not_retry_permanent_errors() {
+    let policy = RetryPolicy::new(3, Duration::from_millis(1));
+    let mut calls = 0;
+
+    let result: Result<(), ProtonError> = policy.run(|| {
+        calls += 1;
+        Err (ProtonError::CouldNotFindWirelessInterface)
+    }).await;
+
+    assert!(matches!(result, Err (ProtonError::CouldNotFindWirelessInterface)));
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn always_attempt_once() {
+    assert_eq!(RetryPolicy::new(0, Duration::ZERO).attempts, 1);
+}
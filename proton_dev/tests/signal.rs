@@ -0,0 +1,73 @@
+//! Testing signal strength smoothing.
+
+use proton_dev::SignalHistory;
+
+use proton_mac::MacAddr;
+
+/// The MAC address of a connected device.
+const MAC: [u8; 6] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab];
+
+/// The MAC address of another connected device.
+const OTHER_MAC: [u8; 6] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xac];
+
+#[test]
+fn average_samples() {
+    let mut history = SignalHistory::new(4);
+
+    assert_eq!(history.record(MAC.into(), -40), -40);
+    assert_eq!(history.record(MAC.into(), -50), -45);
+    assert_eq!(history.record(MAC.into(), -45), -45);
+    assert_eq!(history.average(MAC.into()), Some (-45));
+}
+
+#[test]
+fn round_half_away_from_zero() {
+    let mut history = SignalHistory::new(2);
+
+    history.record(MAC.into(), -42);
+
+    assert_eq!(history.record(MAC.into(), -43), -43);
+}
+
+#[test]
+fn discard_oldest_sample() {
+    let mut history = SignalHistory::new(2);
+
+    history.record(MAC.into(), -90);
+    history.record(MAC.into(), -40);
+
+    // The -90 dBm sample no longer counts
+    assert_eq!(history.record(MAC.into(), -40), -40);
+}
+
+#[test]
+fn devices_are_independent() {
+    let mut history = SignalHistory::new(4);
+
+    history.record(MAC.into(), -40);
+    history.record(OTHER_MAC.into(), -80);
+
+    assert_eq!(history.average(MAC.into()), Some (-40));
+    assert_eq!(history.average(OTHER_MAC.into()), Some (-80));
+}
+
+#[test]
+fn forget_disconnected_devices() {
+    let mut history = SignalHistory::new(4);
+
+    history.record(MAC.into(), -40);
+    history.record(OTHER_MAC.into(), -80);
+    history.retain(&[MacAddr::from(MAC)]);
+
+    assert_eq!(history.average(MAC.into()), Some (-40));
+    assert_eq!(history.average(OTHER_MAC.into()), None);
+}
+
+#[test]
+fn capacity_is_at_least_one() {
+    let mut history = SignalHistory::new(0);
+
+    history.record(MAC.into(), -90);
+
+    assert_eq!(history.record(MAC.into(), -40), -40);
+}
@@ -0,0 +1,49 @@
+//! Testing the site survey utilities.
+
+use proton_dev::{
+    ApInfo,
+    ApSecurity,
+    best_channel,
+    frequency_to_channel,
+};
+
+/// Construct a surveyed access point on the given channel.
+fn ap(channel: u8, signal_strength: i32) -> ApInfo {
+    ApInfo {
+        ssid: "neighbor".to_string(),
+        bssid: [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into(),
+        frequency_mhz: 0,
+        channel,
+        signal_strength,
+        security: ApSecurity::Wpa2,
+    }
+}
+
+#[test]
+fn convert_frequency_to_channel() {
+    assert_eq!(frequency_to_channel(2412), 1);
+    assert_eq!(frequency_to_channel(2484), 14);
+    assert_eq!(frequency_to_channel(5180), 36);
+    assert_eq!(frequency_to_channel(1234), 0);
+}
+
+#[test]
+fn best_channel_avoids_neighbors() {
+    // Crowd channels 1 and 6
+    let aps = vec![ap(1, -40), ap(2, -60), ap(6, -50)];
+
+    assert_eq!(best_channel(&aps, &[1, 6, 11]), Some (11));
+}
+
+#[test]
+fn best_channel_prefers_weaker_neighbors() {
+    // A distant neighbor on channel 1 and a close neighbor on channel 6
+    let aps = vec![ap(1, -90), ap(6, -30), ap(11, -30)];
+
+    assert_eq!(best_channel(&aps, &[1, 6, 11]), Some (1));
+}
+
+#[test]
+fn best_channel_without_candidates() {
+    assert_eq!(best_channel(&[], &[]), None);
+}
@@ -0,0 +1,62 @@
+//! Testing regulatory transmit power parsing.
+
+use proton_dev::parse_max_tx_power;
+
+/// Build a Netlink attribute, padded to a multiple of 4 bytes.
+fn nlattr(nla_type: u16, payload: &[u8]) -> Vec<u8> {
+    let mut attr = Vec::new();
+    attr.extend_from_slice(&(4 + payload.len() as u16).to_ne_bytes());
+    attr.extend_from_slice(&nla_type.to_ne_bytes());
+    attr.extend_from_slice(payload);
+    attr.resize((attr.len() + 3) & !3, 0);
+
+    attr
+}
+
+/// Build a channel of `NL80211_BAND_ATTR_FREQS`.
+fn channel(index: u16, frequency_mhz: u32, max_mbm: Option<u32>) -> Vec<u8> {
+    let mut attrs = nlattr(1, &frequency_mhz.to_ne_bytes());
+    match max_mbm {
+        Some (max_mbm) => attrs.extend(nlattr(6, &max_mbm.to_ne_bytes())),
+        // Disabled channels have no transmit power
+        None => attrs.extend(nlattr(2, &[])),
+    }
+
+    nlattr(index, &attrs)
+}
+
+/// Build the bands of a dual-band PHY, as reported by `iw phy`.
+fn bands() -> Vec<u8> {
+    let two_four = [
+        channel(0, 2412, Some (2000)),
+        channel(1, 2437, Some (2000)),
+        channel(2, 2484, None),
+    ].concat();
+    let five = [
+        channel(0, 5180, Some (2300)),
+        channel(1, 5500, Some (2400)),
+    ].concat();
+
+    [
+        // Band attributes other than the channels are skipped
+        nlattr(0, &[nlattr(3, &[0; 16]), nlattr(1, &two_four)].concat()),
+        nlattr(1, &nlattr(1, &five)),
+    ].concat()
+}
+
+#[test]
+fn max_tx_power_of_channel() {
+    assert_eq!(parse_max_tx_power(&bands(), 2437), Some (2000));
+    assert_eq!(parse_max_tx_power(&bands(), 5500), Some (2400));
+}
+
+#[test]
+fn no_max_tx_power_of_disabled_channel() {
+    assert_eq!(parse_max_tx_power(&bands(), 2484), None);
+}
+
+#[test]
+fn no_max_tx_power_of_unknown_channel() {
+    assert_eq!(parse_max_tx_power(&bands(), 5745), None);
+    assert_eq!(parse_max_tx_power(&[], 2437), None);
+}
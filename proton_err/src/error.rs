@@ -2,6 +2,11 @@
 
 use std::{
     error::Error,
+    io::{
+        self,
+        ErrorKind,
+    },
+    net::Ipv4Addr,
     fmt::{
         Display,
         Debug,
@@ -10,7 +15,10 @@ use std::{
     },
 };
 
-use proton_mac::MacAddr;
+use proton_mac::{
+    InvalidMacAddrLength,
+    MacAddr,
+};
 
 #[derive(Debug)]
 /// An error that occurred within the Proton library.
@@ -24,9 +32,18 @@ pub enum ProtonError {
     /// The program could not find any wireless network interfaces.
     CouldNotFindWirelessInterface,
 
+    /// The program could not find a network interface with this name.
+    CouldNotFindInterface (String),
+
     /// Could not get device information.
     CouldNotGetDeviceInformation,
 
+    /// A network scan did not complete in time.
+    ScanTimedOut,
+
+    /// The driver aborted a scan for nearby access points.
+    ScanAborted,
+
     /// Netlink gave no response.
     NoResponseFromNetlink,
 
@@ -36,9 +53,39 @@ pub enum ProtonError {
     /// Could not deauthenticate device by MAC address.
     CouldNotDeauthenticateDevice (MacAddr),
 
+    /// Could not deauthenticate some devices by MAC address.
+    CouldNotDeauthenticateDevices (Vec<MacAddr>),
+
     /// Could not parse into CIDR range.
     CouldNotParseAsCidr (String),
 
+    /// No known device has this IPv4 address.
+    UnknownHost (Ipv4Addr),
+
+    /// No known device has this MAC address.
+    UnknownMac (MacAddr),
+
+    /// A MAC address must be 6 bytes long, but had this many bytes.
+    InvalidMacAddrLength (usize),
+
+    /// The network interface did not get a MAC and an IPv4 address in time.
+    InterfaceNotReady (String),
+
+    /// The network interface has no default route.
+    NoDefaultRoute (String),
+
+    /// The default gateway did not answer an ARP request.
+    CouldNotResolveGateway (Ipv4Addr),
+
+    /// VLAN ID is outside of the usable range.
+    VlanIdOutOfRange (u16),
+
+    /// Ethernet frame is too short to be valid.
+    FrameTooShort (usize),
+
+    /// Transmit power (in dBm) is outside of the supported range.
+    TxPowerOutOfRange (u32),
+
     /// Root permissions required.
     MustHaveRootPermissions,
 
@@ -51,27 +98,203 @@ pub enum ProtonError {
         gateway: String,
     },
 
+    /// Another device answered the announcement of the gateway address of
+    /// the hotspot.
+    GatewayConflict {
+        /// The IPv4 address of the gateway.
+        gateway: Ipv4Addr,
+
+        /// The MAC address of the other device.
+        mac: MacAddr,
+    },
+
+    /// The wireless driver does not support the requested security mode.
+    SecurityNotSupported (String),
+
+    /// The wireless radio does not support the requested frequency band.
+    BandNotSupported (String),
+
+    /// The requested security mode is broken and will not be used.
+    InsecureSecurity (String),
+
+    /// An open hotspot was given a password, which it would ignore.
+    OpenWithPassword,
+
+    /// Could not parse into a security mode.
+    UnknownSecurity (String),
+
+    /// Could not parse into a frequency band.
+    UnknownBand (String),
+
+    /// IPv6 prefix must contain the IPv6 gateway.
+    PrefixMustContainGateway {
+        /// Provided IPv6 prefix.
+        prefix: String,
+
+        /// Provided gateway IPv6 address.
+        gateway: String,
+    },
+
+    /// The channel is not legal on the frequency band.
+    ChannelNotInBand {
+        /// Provided channel.
+        channel: u8,
+
+        /// Frequency band of the hotspot.
+        band: String,
+    },
+
+    /// No channel of the frequency band can be selected.
+    NoCandidateChannel (String),
+
+    /// WPA passphrase must be 8 to 63 characters long.
+    InvalidPassphraseLength (usize),
+
+    /// An I/O error, with its kind and message.
+    Io (ErrorKind, String),
+
     /// An error that could not be converted to a native error.
     Other (String),
 }
 
+impl ProtonError {
+    /// Convert an I/O error into a native error, preserving its kind.
+    /// 
+    /// Permission errors become `ProtonError::MustHaveRootPermissions`; all
+    /// other errors become `ProtonError::Io`.
+    /// 
+    /// # Parameters
+    /// - `e` (`io::Error`): the I/O error
+    /// 
+    /// # Returns
+    /// The corresponding `ProtonError`.
+    pub fn from_io(e: io::Error) -> Self {
+        match e.kind() {
+            ErrorKind::PermissionDenied => ProtonError::MustHaveRootPermissions,
+            kind => ProtonError::Io (kind, e.to_string()),
+        }
+    }
+
+    /// Convert an `errno` value (e.g. from a Netlink error message) into a
+    /// native error, preserving its kind.
+    /// 
+    /// # Parameters
+    /// - `errno` (`i32`): the error number, either positive or negated as in
+    ///   Netlink error messages
+    /// 
+    /// # Returns
+    /// The corresponding `ProtonError` (see `ProtonError::from_io`).
+    pub fn from_errno(errno: i32) -> Self {
+        Self::from_io(io::Error::from_raw_os_error(errno.saturating_abs()))
+    }
+
+    /// Check if this error is transient, i.e. the operation that failed may
+    /// succeed if it is retried shortly.
+    /// 
+    /// Busy or interrupted I/O (e.g. `EBUSY` or `EAGAIN` while a driver is
+    /// scanning) and missing Netlink responses are transient.  Errors that
+    /// would recur on every attempt (e.g. a missing interface or missing
+    /// root permissions) are not.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the error is transient.
+    pub fn is_transient(&self) -> bool {
+        use ProtonError::*;
+        match self {
+            Io (kind, _) => matches!(
+                kind,
+                ErrorKind::ResourceBusy | ErrorKind::WouldBlock | ErrorKind::Interrupted,
+            ),
+            NoResponseFromNetlink => true,
+            _ => false,
+        }
+    }
+
+    /// Get the I/O error kind that best describes this error.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `ErrorKind` describing this error (`ErrorKind::Other` if no
+    /// other kind applies).
+    pub fn io_kind(&self) -> ErrorKind {
+        use ProtonError::*;
+        match self {
+            Io (kind, _) => *kind,
+            MustHaveRootPermissions => ErrorKind::PermissionDenied,
+            GatewayConflict { .. } => ErrorKind::AddrInUse,
+            ScanTimedOut
+                | InterfaceNotReady (_) => ErrorKind::TimedOut,
+            CouldNotFindInterface (_)
+                | CouldNotFindWirelessInterface
+                | UnknownHost (_)
+                | UnknownMac (_) => ErrorKind::NotFound,
+            CouldNotParseAsCidr (_)
+                | InsecureSecurity (_)
+                | OpenWithPassword
+                | ChannelNotInBand { .. }
+                | UnknownSecurity (_)
+                | UnknownBand (_) => ErrorKind::InvalidInput,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
 impl Display for ProtonError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         use ProtonError::*;
         let error = match self {
-            MustBeEthernetInterface => "must be Ethernet interface",
+            MustBeEthernetInterface => "must be Ethernet interface (the interface opened as a different data link channel type)",
             MustHaveRootPermissions => "must execute with root permissions",
             HotspotNotInitialized => "hotspot not initialized",
             CouldNotFindWirelessInterface => "could not find wireless interface",
+            CouldNotFindInterface (ifname) => &format!("could not find network interface {}", ifname),
             CouldNotGetDeviceInformation => "could not get wireless device information",
+            ScanTimedOut => "network scan timed out",
+            ScanAborted => "scan for nearby access points was aborted",
             NoResponseFromNetlink => "no response from Netlink",
             CouldNotParseAsCidr (cidr) => &format!("could not parse '{}' into a valid CIDR range", cidr),
+            UnknownHost (ipv4) => &format!("no known device has IPv4 address {}", ipv4),
+            UnknownMac (mac) => &format!("no known device has MAC address {}", mac),
+            InterfaceNotReady (ifname) => &format!("network interface {} did not get a MAC and IPv4 address in time", ifname),
+            NoDefaultRoute (ifname) => &format!("network interface {} has no default route", ifname),
+            CouldNotResolveGateway (gateway) => &format!("could not resolve MAC address of gateway {}", gateway),
+            VlanIdOutOfRange (id) => &format!("VLAN ID {} is outside of the usable range", id),
+            FrameTooShort (len) => &format!("Ethernet frame of {} bytes is too short", len),
             CouldNotDeauthenticateDevice (mac) => &format!("could not deauthenticate device with MAC address {}", mac),
+            CouldNotDeauthenticateDevices (macs) => &format!("could not deauthenticate devices with MAC addresses {:?}", macs),
             CouldNotActivateHotspot => "could not activate hotspot",
+            TxPowerOutOfRange (dbm) => &format!("transmit power {} dBm is outside of the supported range", dbm),
             CidrMustContainGateway {
                 cidr,
                 gateway,
             } => &format!("provided CIDR range {} does not contain gateway IPv4 {}", cidr, gateway),
+            GatewayConflict {
+                gateway,
+                mac,
+            } => &format!("gateway IPv4 {} is already in use by device with MAC address {}", gateway, mac),
+            PrefixMustContainGateway {
+                prefix,
+                gateway,
+            } => &format!("provided IPv6 prefix {} does not contain gateway IPv6 {}", prefix, gateway),
+            ChannelNotInBand {
+                channel,
+                band,
+            } => &format!("channel {} is not a legal channel on the {} band", channel, band),
+            NoCandidateChannel (band) => &format!("no channel of the {} band can be selected", band),
+            SecurityNotSupported (security) => &format!("wireless driver does not support {} security", security),
+            BandNotSupported (band) => &format!("wireless radio does not support the {} band", band),
+            InsecureSecurity (security) => &format!("{} security is broken and not supported (use WPA2 or WPA3 instead)", security),
+            OpenWithPassword => "open hotspots do not use a password (clear the password, choose a secured mode, or allow insecure configurations)",
+            UnknownSecurity (security) => &format!("unknown security mode '{}' (expected one of: open, none, owe, enhanced-open, wpa3-owe, wpa2, wpa-psk, psk, wpa3, sae, wpa2-wpa3, wpa2/wpa3)", security),
+            UnknownBand (band) => &format!("unknown band '{}' (expected one of: 2.4, 2.4ghz, bg, b, g, 5, 5ghz, a)", band),
+            InvalidPassphraseLength (len) => &format!("passphrase of {} characters must be 8 to 63 characters long", len),
+            InvalidMacAddrLength (len) => &format!("MAC address must be 6 bytes long, not {}", len),
+            Io (_, message) => message.as_str(),
             Other (t) => t.as_str(),
         };
 
@@ -80,9 +303,25 @@ impl Display for ProtonError {
 }
 
 impl<T> From<T> for ProtonError
-    where T: Error
+    where T: Error + 'static
 {
     fn from(e: T) -> ProtonError {
+        // Keep the kind of I/O errors
+        let e = match (Box::new(e) as Box<dyn Error>).downcast::<io::Error>() {
+            Ok (io_error) => return ProtonError::from_io(*io_error),
+            Err (e) => e,
+        };
+
+        // Keep the kind of wrapped I/O errors
+        if let Some (errno) = os_error(e.as_ref()) {
+            return ProtonError::from_errno(errno);
+        }
+
+        // Keep the length of invalid MAC addresses
+        if let Some (InvalidMacAddrLength (len)) = e.downcast_ref::<InvalidMacAddrLength>() {
+            return ProtonError::InvalidMacAddrLength (*len);
+        }
+
         let string = if let Some (err) = e.source() {
             err.to_string()
         } else {
@@ -91,4 +330,31 @@ impl<T> From<T> for ProtonError
 
         ProtonError::Other (string)
     }
+}
+
+/// Find the OS error number of an error or of one of its sources.
+/// 
+/// # Parameters
+/// - `e` (`&(dyn Error + 'static)`): the error
+/// 
+/// # Returns
+/// An `Option<i32>` containing the OS error number, if one was found.
+fn os_error(e: &(dyn Error + 'static)) -> Option<i32> {
+    let mut source = Some (e);
+
+    while let Some (e) = source {
+        if let Some (errno) = e.downcast_ref::<io::Error>().and_then(io::Error::raw_os_error) {
+            return Some (errno);
+        }
+
+        source = e.source();
+    }
+
+    None
+}
+
+impl From<ProtonError> for io::Error {
+    fn from(e: ProtonError) -> io::Error {
+        io::Error::new(e.io_kind(), e.to_string())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,136 @@
+//! Testing I/O error interoperability.
+
+use std::{
+    error::Error,
+    fmt::{
+        self,
+        Display,
+        Formatter,
+    },
+    fs,
+    io::{
+        self,
+        ErrorKind,
+    },
+};
+
+use proton_err::{
+    ProtonError,
+    ProtonResult,
+};
+
+#[derive(Debug)]
+/// An error that wraps an I/O error as its source.
+struct Wrapped (io::Error);
+
+impl Display for Wrapped {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for Wrapped {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some (&self.0)
+    }
+}
+
+#[derive(Debug)]
+/// An error that only keeps the message of an I/O error.
+struct Message (String);
+
+impl Display for Message {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for Message {}
+
+/// Read a file, converting errors with the `?` operator.
+fn read(path: &str) -> ProtonResult<String> {
+    Ok (fs::read_to_string(path)?)
+}
+
+#[test]
+fn permission_denied_requires_root() {
+    let e = ProtonError::from_io(io::Error::new(ErrorKind::PermissionDenied, "denied"));
+
+    assert!(matches!(e, ProtonError::MustHaveRootPermissions));
+}
+
+#[test]
+fn question_mark_keeps_permission_denied() {
+    let e: ProtonError = io::Error::new(ErrorKind::PermissionDenied, "denied").into();
+
+    assert!(matches!(e, ProtonError::MustHaveRootPermissions));
+}
+
+#[test]
+fn question_mark_keeps_kind() {
+    let e = read("/nonexistent/proton").unwrap_err();
+
+    assert!(matches!(e, ProtonError::Io (ErrorKind::NotFound, _)));
+    assert_eq!(e.io_kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn into_io_error() {
+    let e: io::Error = ProtonError::MustHaveRootPermissions.into();
+    assert_eq!(e.kind(), ErrorKind::PermissionDenied);
+    assert_eq!(e.to_string(), "must execute with root permissions");
+
+    let e: io::Error = ProtonError::ScanTimedOut.into();
+    assert_eq!(e.kind(), ErrorKind::TimedOut);
+
+    let e: io::Error = ProtonError::HotspotNotInitialized.into();
+    assert_eq!(e.kind(), ErrorKind::Other);
+}
+
+#[test]
+fn io_round_trip() {
+    let original = io::Error::new(ErrorKind::AddrInUse, "address in use");
+    let e: io::Error = ProtonError::from_io(original).into();
+
+    assert_eq!(e.kind(), ErrorKind::AddrInUse);
+    assert_eq!(e.to_string(), "address in use");
+}
+
+#[test]
+fn busy_errors_are_transient() {
+    // `EBUSY` and `EAGAIN`
+    assert!(ProtonError::from_io(io::Error::from_raw_os_error(16)).is_transient());
+    assert!(ProtonError::from_io(io::Error::from_raw_os_error(11)).is_transient());
+    assert!(ProtonError::NoResponseFromNetlink.is_transient());
+
+    assert!(!ProtonError::CouldNotFindWirelessInterface.is_transient());
+    assert!(!ProtonError::from_io(io::Error::new(ErrorKind::NotFound, "missing")).is_transient());
+}
+
+#[test]
+fn negated_errno_keeps_kind() {
+    // `-EBUSY`, as in a Netlink error message
+    let e = ProtonError::from_errno(-16);
+
+    assert!(matches!(e, ProtonError::Io (ErrorKind::ResourceBusy, _)));
+    assert!(e.is_transient());
+
+    // `-EPERM`
+    assert!(matches!(ProtonError::from_errno(-1), ProtonError::MustHaveRootPermissions));
+}
+
+#[test]
+fn question_mark_keeps_wrapped_os_error() {
+    let e: ProtonError = Wrapped (io::Error::from_raw_os_error(16)).into();
+
+    assert!(matches!(e, ProtonError::Io (ErrorKind::ResourceBusy, _)));
+    assert!(e.is_transient());
+}
+
+#[test]
+fn question_mark_ignores_os_error_messages() {
+    let e: ProtonError = Message (io::Error::from_raw_os_error(16).to_string()).into();
+
+    assert!(!matches!(e, ProtonError::Io (..)));
+    assert!(!e.is_transient());
+}
@@ -0,0 +1,20 @@
+//! Testing MAC address error conversion.
+
+use proton_err::{
+    ProtonError,
+    ProtonResult,
+};
+
+use proton_mac::MacAddr;
+
+/// Convert a byte slice into a MAC address with the `?` operator.
+fn parse(bytes: &[u8]) -> ProtonResult<MacAddr> {
+    Ok (bytes.try_into()?)
+}
+
+#[test]
+fn question_mark_keeps_mac_length() {
+    assert!(matches!(parse(&[0; 5]), Err (ProtonError::InvalidMacAddrLength (5))));
+    assert!(matches!(parse(&[0; 7]), Err (ProtonError::InvalidMacAddrLength (7))));
+    assert!(parse(&[0; 6]).is_ok());
+}
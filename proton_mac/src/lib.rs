@@ -1,5 +1,18 @@
 //! MAC address data structure for the Proton access point management library.
+//! 
+//! This crate is `no_std` and only depends on `serde` (without its `std`
+//! feature) and `bytemuck`, so it can be used in firmware.  The conversion
+//! from `pnet`'s MAC address type requires the default `pnet` feature, and
+//! `MacAddr::ct_eq` uses `subtle` with the `subtle` feature.
+
+#![no_std]
 
 mod mac;
+mod prefix;
+
+pub use mac::{
+    InvalidMacAddrLength,
+    MacAddr,
+};
 
-pub use mac::MacAddr;
\ No newline at end of file
+pub use prefix::MacPrefix;
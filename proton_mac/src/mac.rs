@@ -1,18 +1,134 @@
 //! MAC address type.
 
-use std::fmt::{
-    Display,
-    Debug,
-    Formatter,
-    Result,
+use core::{
+    array::IntoIter,
+    error::Error,
+    fmt::{
+        Display,
+        Debug,
+        Formatter,
+        Result,
+    },
 };
 
-use serde::Serialize;
+#[cfg(not(feature = "subtle"))]
+use core::hint;
 
-#[derive(Serialize, PartialEq, Eq, Clone, Copy)]
+use bytemuck::{
+    bytes_of,
+    Pod,
+    Zeroable,
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[cfg(feature = "subtle")]
+use subtle::ConstantTimeEq;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
 /// A hardware (MAC) address consisting of six octets.
 pub struct MacAddr (pub u8, pub u8, pub u8, pub u8, pub u8, pub u8);
 
+impl MacAddr {
+    /// Get the octets of this MAC address.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `[u8; 6]` containing the octets, in transmission order.
+    pub fn octets(&self) -> [u8; 6] {
+        [self.0, self.1, self.2, self.3, self.4, self.5]
+    }
+
+    /// Compare this MAC address to another in constant time.
+    /// 
+    /// Unlike `==`, which stops at the first differing octet, this always
+    /// compares every octet, so the time taken does not reveal how much of
+    /// the address matched.  Use this when checking addresses against an
+    /// access list in an authentication path; `==` is fine elsewhere.
+    /// 
+    /// With the `subtle` feature, the octets are compared with
+    /// `subtle::ConstantTimeEq`.  Without it, the differences of all octets
+    /// are combined before they are checked, behind `core::hint::black_box`,
+    /// which is only a best-effort barrier against the compiler adding an
+    /// early exit, so enable `subtle` where timing matters.
+    /// 
+    /// # Parameters
+    /// - `other` (`&MacAddr`): the other MAC address
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the addresses are equal.
+    pub fn ct_eq(&self, other: &MacAddr) -> bool {
+        #[cfg(feature = "subtle")]
+        {
+            self.octets().as_slice()
+                .ct_eq(other.octets().as_slice())
+                .into()
+        }
+
+        #[cfg(not(feature = "subtle"))]
+        {
+            let diff = self.octets()
+                .iter()
+                .zip(other.octets().iter())
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b));
+
+            // Discourage the compiler from short-circuiting the fold
+            hint::black_box(diff) == 0
+        }
+    }
+
+    /// Check if this MAC address is locally administered.
+    /// 
+    /// Locally administered addresses are not assigned by the manufacturer,
+    /// and are what devices use when they randomize their MAC address.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the locally administered bit
+    /// (the second-least significant bit of the first octet) is set.
+    pub fn is_locally_administered(&self) -> bool {
+        self.0 & 0x02 != 0
+    }
+
+    /// Check if this MAC address matches a base address under a mask.
+    /// 
+    /// # Parameters
+    /// - `base` (`MacAddr`): the base address to match against
+    /// - `mask` (`MacAddr`): the bits of the addresses to compare
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not `self & mask == base & mask`.
+    pub fn matches(&self, base: MacAddr, mask: MacAddr) -> bool {
+        let mask = u64::from(mask);
+
+        u64::from(*self) & mask == u64::from(base) & mask
+    }
+}
+
+impl AsRef<[u8]> for MacAddr {
+    fn as_ref(&self) -> &[u8] {
+        // `Pod` guarantees that the six `u8` fields are laid out like `[u8; 6]`
+        bytes_of(self)
+    }
+}
+
+impl IntoIterator for MacAddr {
+    type Item = u8;
+    type IntoIter = IntoIter<u8, 6>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.octets().into_iter()
+    }
+}
+
 impl Display for MacAddr {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(
@@ -47,6 +163,56 @@ impl From<[u8; 6]> for MacAddr {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// An error converting a byte slice that is not exactly 6 bytes long into a
+/// MAC address, holding the length of the slice.
+pub struct InvalidMacAddrLength (pub usize);
+
+impl Display for InvalidMacAddrLength {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "MAC address must be 6 bytes long, not {}", self.0)
+    }
+}
+
+impl Error for InvalidMacAddrLength {}
+
+impl TryFrom<&[u8]> for MacAddr {
+    type Error = InvalidMacAddrLength;
+
+    fn try_from(bytes: &[u8]) -> core::result::Result<Self, Self::Error> {
+        let octets: [u8; 6] = bytes.try_into()
+            .map_err(|_| InvalidMacAddrLength (bytes.len()))?;
+
+        Ok (octets.into())
+    }
+}
+
+impl From<u64> for MacAddr {
+    /// Construct a MAC address from the low 48 bits of an integer.
+    fn from(value: u64) -> Self {
+        let bytes = value.to_be_bytes();
+
+        Self (
+            bytes[2],
+            bytes[3],
+            bytes[4],
+            bytes[5],
+            bytes[6],
+            bytes[7],
+        )
+    }
+}
+
+impl From<MacAddr> for u64 {
+    fn from(mac: MacAddr) -> Self {
+        let mut bytes = [0u8; 8];
+        bytes[2..].copy_from_slice(&mac.octets());
+
+        u64::from_be_bytes(bytes)
+    }
+}
+
+#[cfg(feature = "pnet")]
 impl From<pnet::datalink::MacAddr> for MacAddr {
     fn from(mac: pnet::datalink::MacAddr) -> Self {
         Self (
@@ -58,4 +224,18 @@ impl From<pnet::datalink::MacAddr> for MacAddr {
             mac.5,
         )
     }
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "pnet")]
+impl From<MacAddr> for pnet::datalink::MacAddr {
+    fn from(mac: MacAddr) -> Self {
+        Self (
+            mac.0,
+            mac.1,
+            mac.2,
+            mac.3,
+            mac.4,
+            mac.5,
+        )
+    }
+}
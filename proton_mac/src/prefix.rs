@@ -0,0 +1,70 @@
+//! MAC address prefix type.
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::MacAddr;
+
+/// Number of bits in a MAC address.
+const MAC_ADDR_BITS: u8 = 48;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+/// A range of MAC addresses sharing the bits selected by a mask.
+/// 
+/// This is the hardware address analogue of a CIDR range.  For example, a
+/// 24-bit prefix matches every address of an organizationally unique
+/// identifier (OUI).
+pub struct MacPrefix {
+    /// The base address of the range.
+    pub base: MacAddr,
+
+    /// The bits of an address that must match the base address.
+    pub mask: MacAddr,
+}
+
+impl MacPrefix {
+    /// Construct a new MAC address prefix from a base address and a mask.
+    /// 
+    /// # Parameters
+    /// - `base` (`MacAddr`): the base address of the range
+    /// - `mask` (`MacAddr`): the bits of an address that must match
+    /// 
+    /// # Returns
+    /// A new `MacPrefix`.
+    pub fn new(base: MacAddr, mask: MacAddr) -> Self {
+        Self {
+            base,
+            mask,
+        }
+    }
+
+    /// Construct a new MAC address prefix from a base address and a prefix length.
+    /// 
+    /// # Parameters
+    /// - `base` (`MacAddr`): the base address of the range
+    /// - `length` (`u8`): the number of leading bits that must match,
+    ///   saturating at 48
+    /// 
+    /// # Returns
+    /// A new `MacPrefix`.
+    pub fn with_length(base: MacAddr, length: u8) -> Self {
+        let length = length.min(MAC_ADDR_BITS);
+        let all = (1u64 << MAC_ADDR_BITS) - 1;
+        let mask = all & !(all >> length);
+
+        Self::new(base, mask.into())
+    }
+
+    /// Check if this range contains a MAC address.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address to check
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the address is in this range.
+    pub fn contains(&self, mac: MacAddr) -> bool {
+        mac.matches(self.base, self.mask)
+    }
+}
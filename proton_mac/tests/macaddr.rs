@@ -1,8 +1,12 @@
 //! Testing the MAC address implementation.
 
+#[cfg(feature = "pnet")]
 use pnet::datalink::MacAddr as PnetMac;
 
-use proton_mac::MacAddr;
+use proton_mac::{
+    InvalidMacAddrLength,
+    MacAddr,
+};
 
 #[test]
 fn display_mac_addr() {
@@ -27,6 +31,7 @@ fn debug_mac_addr() {
 }
 
 #[test]
+#[cfg(feature = "pnet")]
 fn from_pnet_mac() {
     // Construct a `libpnet` MAC address
     let mac: PnetMac = PnetMac::new(0x12, 0x34, 0x56, 0x78, 0x90, 0xab);
@@ -37,4 +42,89 @@ fn from_pnet_mac() {
     let ref_mac: MacAddr = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into();
 
     assert_eq!(native_mac, ref_mac);
-}
\ No newline at end of file
+}
+
+#[test]
+fn mac_addr_octets() {
+    // Construct a MAC address
+    let mac: MacAddr = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into();
+
+    assert_eq!(mac.octets(), [0x12, 0x34, 0x56, 0x78, 0x90, 0xab]);
+}
+
+#[test]
+fn mac_addr_as_ref() {
+    // Construct a MAC address
+    let mac: MacAddr = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into();
+
+    // Borrow the octets as a slice
+    let octets: &[u8] = mac.as_ref();
+
+    assert_eq!(octets, &[0x12, 0x34, 0x56, 0x78, 0x90, 0xab]);
+}
+
+#[test]
+fn mac_addr_into_iter() {
+    // Construct a MAC address
+    let mac: MacAddr = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into();
+
+    // Collect the octets into a vector
+    let octets = mac.into_iter().collect::<Vec<u8>>();
+
+    assert_eq!(octets, vec![0x12, 0x34, 0x56, 0x78, 0x90, 0xab]);
+}
+
+#[test]
+fn locally_administered_mac_addr() {
+    // A randomized MAC address has the locally administered bit set
+    let random: MacAddr = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into();
+
+    // A manufacturer-assigned MAC address does not
+    let universal: MacAddr = [0x00, 0x1b, 0x63, 0x78, 0x90, 0xab].into();
+
+    assert!(random.is_locally_administered());
+    assert!(!universal.is_locally_administered());
+}
+
+#[test]
+fn constant_time_eq() {
+    let mac: MacAddr = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into();
+
+    assert!(mac.ct_eq(&mac));
+    assert!(mac.ct_eq(&[0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into()));
+
+    // A difference in any octet (or bit) is detected
+    for octet in 0..6 {
+        let mut octets = mac.octets();
+        octets[octet] ^= 0x01;
+
+        assert!(!mac.ct_eq(&octets.into()));
+        assert_eq!(mac.ct_eq(&octets.into()), mac == octets.into());
+    }
+}
+
+#[test]
+fn try_from_six_bytes() {
+    let bytes: &[u8] = &[0x12, 0x34, 0x56, 0x78, 0x90, 0xab];
+
+    assert_eq!(MacAddr::try_from(bytes), Ok (MacAddr (0x12, 0x34, 0x56, 0x78, 0x90, 0xab)));
+}
+
+#[test]
+fn try_from_five_bytes() {
+    let bytes: &[u8] = &[0x12, 0x34, 0x56, 0x78, 0x90];
+
+    assert_eq!(MacAddr::try_from(bytes), Err (InvalidMacAddrLength (5)));
+}
+
+#[test]
+fn try_from_seven_bytes() {
+    let bytes: &[u8] = &[0x12, 0x34, 0x56, 0x78, 0x90, 0xab, 0xcd];
+
+    assert_eq!(MacAddr::try_from(bytes), Err (InvalidMacAddrLength (7)));
+}
+
+#[test]
+fn try_from_empty() {
+    assert_eq!(MacAddr::try_from(&[][..]), Err (InvalidMacAddrLength (0)));
+}
@@ -0,0 +1,42 @@
+//! Testing MAC address masks and prefixes.
+
+use proton_mac::{
+    MacAddr,
+    MacPrefix,
+};
+
+#[test]
+fn full_mask_matches_exactly() {
+    let base: MacAddr = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into();
+    let mask: MacAddr = [0xff; 6].into();
+
+    assert!(base.matches(base, mask));
+    assert!(!MacAddr::from([0x12, 0x34, 0x56, 0x78, 0x90, 0xac]).matches(base, mask));
+    assert_eq!(MacPrefix::with_length(base, 48), MacPrefix::new(base, mask));
+}
+
+#[test]
+fn oui_prefix() {
+    let prefix = MacPrefix::with_length([0x12, 0x34, 0x56, 0x00, 0x00, 0x00].into(), 24);
+
+    assert_eq!(prefix.mask, [0xff, 0xff, 0xff, 0x00, 0x00, 0x00].into());
+    assert!(prefix.contains([0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into()));
+    assert!(!prefix.contains([0x12, 0x34, 0x57, 0x78, 0x90, 0xab].into()));
+}
+
+#[test]
+fn arbitrary_prefix_length() {
+    let prefix = MacPrefix::with_length([0x12, 0x34, 0x50, 0x00, 0x00, 0x00].into(), 20);
+
+    assert!(prefix.contains([0x12, 0x34, 0x5f, 0xff, 0xff, 0xff].into()));
+    assert!(!prefix.contains([0x12, 0x34, 0x60, 0x00, 0x00, 0x00].into()));
+    assert!(MacPrefix::with_length([0x00; 6].into(), 0).contains([0xff; 6].into()));
+}
+
+#[test]
+fn convert_to_and_from_u64() {
+    let mac: MacAddr = [0x12, 0x34, 0x56, 0x78, 0x90, 0xab].into();
+
+    assert_eq!(u64::from(mac), 0x1234_5678_90ab);
+    assert_eq!(MacAddr::from(0x1234_5678_90ab_u64), mac);
+}
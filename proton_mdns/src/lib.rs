@@ -0,0 +1,19 @@
+//! Multicast DNS (mDNS) hostname resolution for the Proton access point management library.
+
+#![deny(warnings)]
+#![deny(missing_docs)]
+
+mod mdns;
+
+use std::time::Duration;
+
+pub use mdns::{
+    parse_response,
+    queries,
+    query,
+    resolve,
+    MAX_QUERY_LEN,
+};
+
+/// The default time to wait for mDNS responses (1 second).
+pub static DEFAULT_MDNS_TIMEOUT: Duration = Duration::from_secs(1);
@@ -0,0 +1,279 @@
+//! Reverse hostname lookups over multicast DNS.
+
+use std::{
+    collections::HashMap,
+    net::{
+        Ipv4Addr,
+        SocketAddrV4,
+    },
+    time::Duration,
+};
+
+use socket2::{
+    Domain,
+    Protocol,
+    Socket,
+    Type,
+};
+
+use tokio::{
+    net::UdpSocket,
+    time::{
+        self,
+        Instant,
+    },
+};
+
+use proton_err::ProtonResult;
+
+/// The mDNS multicast group and port.
+const MDNS_ADDR: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(224, 0, 0, 251), 5353);
+
+/// DNS record type of a domain name pointer.
+const TYPE_PTR: u16 = 12;
+
+/// DNS record class of the Internet, with the unicast-response bit set.
+const CLASS_IN_QU: u16 = 0x8001;
+
+/// Mask to remove the cache-flush bit from an mDNS record class.
+const CLASS_MASK: u16 = 0x7fff;
+
+/// DNS record class of the Internet.
+const CLASS_IN: u16 = 1;
+
+/// Size of a DNS message header.
+const HEADER_LEN: usize = 12;
+
+/// Maximum size of a received mDNS message.
+const MAX_MESSAGE_LEN: usize = 9000;
+
+/// Maximum size of a sent mDNS query, so that it fits in a single Ethernet
+/// frame (a 1500-byte MTU, less the IPv4 and UDP headers).
+pub const MAX_QUERY_LEN: usize = 1472;
+
+/// Resolve the hostnames of a list of IPv4 addresses over mDNS.
+/// 
+/// This sends reverse (PTR) queries for all addresses, in as few messages
+/// as fit the MTU (see `queries`), and collects responses until the timeout
+/// elapses, so a slow or silent network can never stall the caller for
+/// longer than `timeout`.  Addresses that do not respond are omitted from
+/// the result.
+/// 
+/// # Parameters
+/// - `ips` (`&[Ipv4Addr]`): the IPv4 addresses to resolve
+/// - `interface` (`Ipv4Addr`): the IPv4 address of the network interface to
+///   query on (otherwise the kernel sends multicast over the default route)
+/// - `timeout` (`Duration`): the time to wait for responses
+/// 
+/// # Returns
+/// A `ProtonResult<HashMap<Ipv4Addr, String>>` containing the hostname of
+/// each address that responded, without the `.local` suffix.
+pub async fn resolve(ips: &[Ipv4Addr], interface: Ipv4Addr, timeout: Duration) -> ProtonResult<HashMap<Ipv4Addr, String>> {
+    let mut hostnames = HashMap::new();
+
+    if ips.is_empty() {
+        return Ok (hostnames);
+    }
+
+    // Send the queries from an ephemeral port of the interface, so
+    // responders on its link reply directly
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some (Protocol::UDP))?;
+    socket.set_multicast_if_v4(&interface)?;
+    socket.bind(&SocketAddrV4::new(interface, 0).into())?;
+    socket.set_nonblocking(true)?;
+
+    let socket = UdpSocket::from_std(socket.into())?;
+    for query in queries(ips) {
+        socket.send_to(&query, MDNS_ADDR).await?;
+    }
+
+    // Collect responses until the deadline
+    let deadline = Instant::now() + timeout;
+    let mut buffer = vec![0u8; MAX_MESSAGE_LEN];
+
+    while let Ok (received) = time::timeout_at(deadline, socket.recv_from(&mut buffer)).await {
+        let (len, _) = received?;
+
+        for (ipv4, hostname) in parse_response(&buffer[..len]) {
+            if ips.contains(&ipv4) {
+                hostnames.insert(ipv4, hostname);
+            }
+        }
+    }
+
+    Ok (hostnames)
+}
+
+/// Construct the mDNS queries for the reverse (PTR) records of IPv4
+/// addresses, splitting the addresses so that each query fits in
+/// `MAX_QUERY_LEN` bytes.
+/// 
+/// # Parameters
+/// - `ips` (`&[Ipv4Addr]`): the IPv4 addresses to query
+/// 
+/// # Returns
+/// A `Vec<Vec<u8>>` containing the DNS messages, which together ask for
+/// every address in order.
+pub fn queries(ips: &[Ipv4Addr]) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    let mut start = 0;
+    let mut len = HEADER_LEN;
+
+    for (i, ipv4) in ips.iter().enumerate() {
+        // Each question is the encoded name (its dotted length plus the
+        // first length byte and the root label), a type, and a class
+        let question_len = reverse_name(*ipv4).len() + 2 + 4;
+
+        if len + question_len > MAX_QUERY_LEN && i > start {
+            messages.push(query(&ips[start..i]));
+            start = i;
+            len = HEADER_LEN;
+        }
+        len += question_len;
+    }
+
+    if start < ips.len() {
+        messages.push(query(&ips[start..]));
+    }
+
+    messages
+}
+
+/// Construct an mDNS query for the reverse (PTR) records of IPv4 addresses.
+/// 
+/// This puts every address in a single message, which may exceed the MTU
+/// (see `queries`).
+/// 
+/// # Parameters
+/// - `ips` (`&[Ipv4Addr]`): the IPv4 addresses to query
+/// 
+/// # Returns
+/// A `Vec<u8>` containing the DNS message.
+pub fn query(ips: &[Ipv4Addr]) -> Vec<u8> {
+    let mut message = Vec::new();
+
+    // Header: ID, flags, and record counts (only questions)
+    message.extend_from_slice(&[0, 0, 0, 0]);
+    message.extend_from_slice(&(ips.len() as u16).to_be_bytes());
+    message.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+    // Questions: one PTR question per address
+    for ipv4 in ips {
+        for label in reverse_name(*ipv4).split('.') {
+            message.push(label.len() as u8);
+            message.extend_from_slice(label.as_bytes());
+        }
+        message.push(0);
+
+        message.extend_from_slice(&TYPE_PTR.to_be_bytes());
+        message.extend_from_slice(&CLASS_IN_QU.to_be_bytes());
+    }
+
+    message
+}
+
+/// Parse the reverse (PTR) records out of an mDNS response.
+/// 
+/// # Parameters
+/// - `message` (`&[u8]`): the DNS message
+/// 
+/// # Returns
+/// A `Vec<(Ipv4Addr, String)>` containing each address and its hostname,
+/// without the `.local` suffix.  Malformed messages yield no records.
+pub fn parse_response(message: &[u8]) -> Vec<(Ipv4Addr, String)> {
+    parse_records(message).unwrap_or_default()
+}
+
+/// Parse the reverse (PTR) records out of a DNS message.
+fn parse_records(message: &[u8]) -> Option<Vec<(Ipv4Addr, String)>> {
+    let count = |offset: usize| -> Option<usize> {
+        let bytes = message.get(offset..offset + 2)?;
+        Some (u16::from_be_bytes([bytes[0], bytes[1]]) as usize)
+    };
+
+    let questions = count(4)?;
+    let records = count(6)? + count(8)? + count(10)?;
+    let mut offset = HEADER_LEN;
+
+    // Skip questions
+    for _ in 0..questions {
+        let (_, end) = read_name(message, offset)?;
+        offset = end + 4;
+    }
+
+    // Read answer, authority, and additional records
+    let mut results = Vec::new();
+    for _ in 0..records {
+        let (name, end) = read_name(message, offset)?;
+        let fixed = message.get(end..end + 10)?;
+        let rtype = u16::from_be_bytes([fixed[0], fixed[1]]);
+        let class = u16::from_be_bytes([fixed[2], fixed[3]]) & CLASS_MASK;
+        let rdlength = u16::from_be_bytes([fixed[8], fixed[9]]) as usize;
+        let rdata = end + 10;
+        offset = rdata + rdlength;
+
+        if rtype != TYPE_PTR || class != CLASS_IN {
+            continue;
+        }
+
+        if let Some (ipv4) = parse_reverse_name(&name) {
+            let (hostname, _) = read_name(message, rdata)?;
+            let hostname = hostname.trim_end_matches(".local").to_string();
+            results.push((ipv4, hostname));
+        }
+    }
+
+    Some (results)
+}
+
+/// Read a (possibly compressed) domain name from a DNS message.
+/// 
+/// Returns the dotted name and the offset just past the name at its
+/// original position.
+fn read_name(message: &[u8], offset: usize) -> Option<(String, usize)> {
+    let mut labels: Vec<String> = Vec::new();
+    let mut position = offset;
+    let mut end = None;
+
+    // Bound the number of compression pointers followed to avoid loops
+    for _ in 0..message.len() {
+        let len = *message.get(position)? as usize;
+
+        if len == 0 {
+            return Some ((labels.join("."), end.unwrap_or(position + 1)));
+        }
+
+        if len & 0xc0 == 0xc0 {
+            let low = *message.get(position + 1)? as usize;
+            end.get_or_insert(position + 2);
+            position = ((len & 0x3f) << 8) | low;
+            continue;
+        }
+
+        let label = message.get(position + 1..position + 1 + len)?;
+        labels.push(String::from_utf8_lossy(label).to_string());
+        position += 1 + len;
+    }
+
+    None
+}
+
+/// Construct the reverse lookup name of an IPv4 address.
+fn reverse_name(ipv4: Ipv4Addr) -> String {
+    let [a, b, c, d] = ipv4.octets();
+
+    format!("{}.{}.{}.{}.in-addr.arpa", d, c, b, a)
+}
+
+/// Parse an IPv4 address out of its reverse lookup name.
+fn parse_reverse_name(name: &str) -> Option<Ipv4Addr> {
+    let octets = name.strip_suffix(".in-addr.arpa")?
+        .split('.')
+        .map(|octet| octet.parse::<u8>().ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    match octets.as_slice() {
+        [d, c, b, a] => Some (Ipv4Addr::new(*a, *b, *c, *d)),
+        _ => None,
+    }
+}
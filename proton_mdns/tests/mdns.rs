@@ -0,0 +1,99 @@
+//! Testing the mDNS message encoding and decoding.
+
+use std::net::Ipv4Addr;
+
+use proton_mdns::{
+    parse_response,
+    queries,
+    query,
+    MAX_QUERY_LEN,
+};
+
+/// Encode a domain name as DNS labels.
+fn name(name: &str) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    for label in name.split('.') {
+        encoded.push(label.len() as u8);
+        encoded.extend_from_slice(label.as_bytes());
+    }
+    encoded.push(0);
+
+    encoded
+}
+
+#[test]
+fn query_contains_reverse_name() {
+    let message = query(&[Ipv4Addr::new(192, 168, 0, 5)]);
+
+    // One question
+    assert_eq!(&message[4..6], &[0, 1]);
+
+    // Reverse name, PTR type, and IN class with the unicast-response bit
+    let mut question = name("5.0.168.192.in-addr.arpa");
+    question.extend_from_slice(&[0, 12, 0x80, 1]);
+
+    assert_eq!(&message[12..], question.as_slice());
+}
+
+#[test]
+fn split_queries_by_mtu() {
+    // A /24 does not fit in a single query
+    let ips = (1..=254).map(|host| Ipv4Addr::new(192, 168, 0, host)).collect::<Vec<Ipv4Addr>>();
+    let messages = queries(&ips);
+
+    assert!(messages.len() > 1);
+    assert!(messages.iter().all(|message| message.len() <= MAX_QUERY_LEN));
+
+    // Every address is asked for once, in order
+    let mut start = 0;
+    for message in messages.iter() {
+        let questions = u16::from_be_bytes([message[4], message[5]]) as usize;
+        assert_eq!(*message, query(&ips[start..start + questions]));
+        start += questions;
+    }
+    assert_eq!(start, ips.len());
+
+    // A few addresses fit in a single query
+    assert_eq!(queries(&ips[..4]), vec![query(&ips[..4])]);
+    assert!(queries(&[]).is_empty());
+}
+
+#[test]
+fn parse_ptr_response() {
+    // Header with one answer
+    let mut message = vec![0, 0, 0x84, 0, 0, 0, 0, 1, 0, 0, 0, 0];
+
+    // Answer: PTR record with the cache-flush bit set
+    let hostname = name("Johns-iPhone.local");
+    message.extend(name("5.0.168.192.in-addr.arpa"));
+    message.extend_from_slice(&[0, 12, 0x80, 1, 0, 0, 0, 120]);
+    message.extend_from_slice(&(hostname.len() as u16).to_be_bytes());
+    message.extend(hostname);
+
+    assert_eq!(
+        parse_response(&message),
+        vec![(Ipv4Addr::new(192, 168, 0, 5), "Johns-iPhone".to_string())],
+    );
+}
+
+#[test]
+fn parse_compressed_response() {
+    // Header with one answer
+    let mut message = vec![0, 0, 0x84, 0, 0, 0, 0, 1, 0, 0, 0, 0];
+
+    // Answer: PTR record whose hostname points back into the owner name
+    message.extend(name("5.0.168.192.in-addr.arpa"));
+    message.extend_from_slice(&[0, 12, 0, 1, 0, 0, 0, 120, 0, 6]);
+    message.extend_from_slice(&[3, b'a', b'b', b'c', 0xc0, 12]);
+
+    assert_eq!(
+        parse_response(&message),
+        vec![(Ipv4Addr::new(192, 168, 0, 5), "abc.5.0.168.192.in-addr.arpa".to_string())],
+    );
+}
+
+#[test]
+fn parse_malformed_response() {
+    assert!(parse_response(&[0, 0, 0x84, 0, 0, 0, 0, 1]).is_empty());
+    assert!(parse_response(&[0, 0, 0x84, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0xc0, 12]).is_empty());
+}
@@ -0,0 +1,184 @@
+//! Frame debugging utilities.
+
+use std::fmt::Write;
+
+use pnet::packet::{
+    arp::{
+        ArpOperations,
+        ArpPacket,
+    },
+    ethernet::{
+        EtherTypes,
+        EthernetPacket,
+    },
+    ip::IpNextHeaderProtocols,
+    ipv4::Ipv4Packet,
+    ipv6::Ipv6Packet,
+    tcp::TcpPacket,
+    udp::UdpPacket,
+    Packet,
+};
+
+use crate::untag_frame;
+
+/// Number of bytes on each line of a hex dump.
+const HEX_DUMP_WIDTH: usize = 16;
+
+/// Format a byte slice as a hex dump.
+/// 
+/// Each line holds 16 bytes as an 8-digit hexadecimal offset, the bytes in
+/// hexadecimal (in two groups of 8), and their printable ASCII characters,
+/// in the format of `hexdump -C`:
+/// 
+/// ```text
+/// 00000000  ff ff ff ff ff ff 02 00  00 00 00 01 08 06 00 01  |................|
+/// ```
+/// 
+/// This format is stable, so dumps of captures can be diffed.
+/// 
+/// # Parameters
+/// - `bytes` (`&[u8]`): the bytes to dump
+/// 
+/// # Returns
+/// A `String` containing the hex dump, with one line per 16 bytes (each
+/// ending in a newline), or an empty string if `bytes` is empty.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::new();
+
+    for (line, chunk) in bytes.chunks(HEX_DUMP_WIDTH).enumerate() {
+        // Offset
+        let _ = write!(dump, "{:08x} ", line * HEX_DUMP_WIDTH);
+
+        // Hexadecimal bytes, padded on the last line
+        for i in 0..HEX_DUMP_WIDTH {
+            if i % 8 == 0 {
+                dump.push(' ');
+            }
+
+            match chunk.get(i) {
+                Some (byte) => { let _ = write!(dump, "{:02x} ", byte); },
+                None => dump.push_str("   "),
+            }
+        }
+
+        // Printable ASCII characters
+        let ascii = chunk.iter()
+            .map(|byte| if byte.is_ascii_graphic() || *byte == b' ' { *byte as char } else { '.' })
+            .collect::<String>();
+        let _ = writeln!(dump, " |{}|", ascii);
+    }
+
+    dump
+}
+
+/// Describe an Ethernet frame in a single human-readable line.
+/// 
+/// ARP, IPv4 (with TCP and UDP ports), IPv6, and 802.1Q-tagged frames are
+/// summarized; other frames are described by their EtherType.  For example:
+/// 
+/// ```text
+/// 02:00:00:00:00:01 > ff:ff:ff:ff:ff:ff, ARP who-has 192.168.0.5 tell 192.168.0.1
+/// ```
+/// 
+/// # Parameters
+/// - `frame` (`&[u8]`): the Ethernet frame
+/// 
+/// # Returns
+/// A `String` describing the frame.
+pub fn describe_frame(frame: &[u8]) -> String {
+    let eth_frame = match EthernetPacket::new(frame) {
+        Some (eth_frame) => eth_frame,
+        None => return format!("truncated frame ({} bytes)", frame.len()),
+    };
+
+    let addresses = format!("{} > {}", eth_frame.get_source(), eth_frame.get_destination());
+
+    // Describe the tag, then the frame within it
+    if let (Some (tag), untagged) = untag_frame(frame) {
+        let inner = match EthernetPacket::new(&untagged) {
+            Some (untagged) => describe_payload(&untagged),
+            None => "truncated".to_string(),
+        };
+
+        return format!("{}, 802.1Q vlan {} priority {}, {}", addresses, tag.id, tag.priority, inner);
+    }
+
+    format!("{}, {}", addresses, describe_payload(&eth_frame))
+}
+
+/// Describe the payload of an Ethernet frame.
+fn describe_payload(eth_frame: &EthernetPacket) -> String {
+    let payload = eth_frame.payload();
+
+    match eth_frame.get_ethertype() {
+        EtherTypes::Arp => match ArpPacket::new(payload) {
+            Some (arp) => describe_arp(&arp),
+            None => "ARP, truncated".to_string(),
+        },
+        EtherTypes::Ipv4 => match Ipv4Packet::new(payload) {
+            Some (ipv4) => describe_ipv4(&ipv4),
+            None => "IPv4, truncated".to_string(),
+        },
+        EtherTypes::Ipv6 => match Ipv6Packet::new(payload) {
+            Some (ipv6) => format!(
+                "IPv6 {} > {}, next header {}, length {}",
+                ipv6.get_source(),
+                ipv6.get_destination(),
+                ipv6.get_next_header().0,
+                ipv6.get_payload_length(),
+            ),
+            None => "IPv6, truncated".to_string(),
+        },
+        ethertype => format!("ethertype 0x{:04x}, length {}", ethertype.0, payload.len()),
+    }
+}
+
+/// Describe an ARP packet.
+fn describe_arp(arp: &ArpPacket) -> String {
+    match arp.get_operation() {
+        ArpOperations::Request => format!(
+            "ARP who-has {} tell {}",
+            arp.get_target_proto_addr(),
+            arp.get_sender_proto_addr(),
+        ),
+        ArpOperations::Reply => format!(
+            "ARP {} is-at {}",
+            arp.get_sender_proto_addr(),
+            arp.get_sender_hw_addr(),
+        ),
+        operation => format!("ARP operation {}", operation.0),
+    }
+}
+
+/// Describe an IPv4 packet.
+fn describe_ipv4(ipv4: &Ipv4Packet) -> String {
+    let (source, destination) = (ipv4.get_source(), ipv4.get_destination());
+    let length = ipv4.get_total_length();
+
+    match ipv4.get_next_level_protocol() {
+        IpNextHeaderProtocols::Tcp => match TcpPacket::new(ipv4.payload()) {
+            Some (tcp) => format!(
+                "IPv4 {}:{} > {}:{}, TCP, length {}",
+                source,
+                tcp.get_source(),
+                destination,
+                tcp.get_destination(),
+                length,
+            ),
+            None => format!("IPv4 {} > {}, TCP, truncated", source, destination),
+        },
+        IpNextHeaderProtocols::Udp => match UdpPacket::new(ipv4.payload()) {
+            Some (udp) => format!(
+                "IPv4 {}:{} > {}:{}, UDP, length {}",
+                source,
+                udp.get_source(),
+                destination,
+                udp.get_destination(),
+                length,
+            ),
+            None => format!("IPv4 {} > {}, UDP, truncated", source, destination),
+        },
+        IpNextHeaderProtocols::Icmp => format!("IPv4 {} > {}, ICMP, length {}", source, destination, length),
+        protocol => format!("IPv4 {} > {}, protocol {}, length {}", source, destination, protocol.0, length),
+    }
+}
@@ -1,8 +1,26 @@
 //! Network Interface Card (NIC) abstraction for the Proton access point management library.
 
-#[deny(warnings)]
-#[deny(missing_docs)]
+#![deny(warnings)]
+#![deny(missing_docs)]
 
+mod debug;
 mod nif;
+mod route;
+mod vlan;
 
-pub use nif::NetworkInterface;
\ No newline at end of file
+pub use debug::{
+    describe_frame,
+    hex_dump,
+};
+
+pub use nif::NetworkInterface;
+
+pub use route::parse_default_route;
+
+pub use vlan::{
+    MAX_VLAN_ID,
+    MIN_VLAN_ID,
+    tag_frame,
+    untag_frame,
+    VlanTag,
+};
@@ -1,11 +1,15 @@
 //! Network interface abstraction.
 
 use std::{
+    fs,
+    io,
     net::{
         IpAddr,
         Ipv4Addr,
+        Ipv6Addr,
     },
     sync::Arc,
+    time::Duration,
 };
 
 use pnet::{
@@ -13,26 +17,77 @@ use pnet::{
         self,
         Channel,
         channel,
+        Config,
         DataLinkSender,
         DataLinkReceiver,
         interfaces,
         MacAddr,
     },
     packet::{
-        ethernet::EthernetPacket,
+        arp::{
+            ArpHardwareTypes,
+            ArpOperations,
+            ArpPacket,
+            MutableArpPacket,
+        },
+        ethernet::{
+            EtherTypes,
+            EthernetPacket,
+            MutableEthernetPacket,
+        },
+        MutablePacket,
         Packet,
     },
 };
 
-use tokio::sync::Mutex;
+use tokio::{
+    sync::{
+        Mutex,
+        OwnedMutexGuard,
+    },
+    task,
+    time::{
+        self,
+        Instant,
+    },
+};
 
 use proton_err::{
     ProtonError,
     ProtonResult,
 };
 
+use crate::{
+    parse_default_route,
+    tag_frame,
+    untag_frame,
+    VlanTag,
+};
+
+/// Path of the kernel IPv4 routing table.
+const ROUTE_TABLE_PATH: &str = "/proc/net/route";
+
+/// Directory containing the kernel's view of each network interface.
+const SYS_CLASS_NET_PATH: &str = "/sys/class/net";
+
+/// Delay between checks of `NetworkInterface::wait_ready`.
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time to wait for the default gateway to answer an ARP request.
+const GATEWAY_RESOLVE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Read timeout of the channel opened to resolve the default gateway.
+const GATEWAY_READ_TIMEOUT: Duration = Duration::from_millis(100);
+
 #[derive(Clone)]
 /// An OSI Layer 2 (Data Link Layer) network interface.
+/// 
+/// **Note**: cloning a `NetworkInterface` does *not* open a new channel.  All
+/// clones share the same transmitter and receiver, so frames received by one
+/// clone are never seen by another.  This is intended when one clone only
+/// sends and another only receives (as in the ARP scan), but two clones that
+/// both receive will split the incoming frames between them.  Use
+/// `NetworkInterface::try_clone` for an independent channel.
 pub struct NetworkInterface {
     /// Transmitter line (talks to network interface).
     tx: Arc<Mutex<Box<dyn DataLinkSender>>>,
@@ -40,11 +95,20 @@ pub struct NetworkInterface {
     /// Receiver line (listens to network interface).
     rx: Arc<Mutex<Box<dyn DataLinkReceiver>>>,
 
+    /// Name of the interface.
+    pub name: String,
+
     /// MAC address of the interface.
     pub mac: Option<MacAddr>,
 
     /// IPv4 address of the interface.
     pub ipv4: Option<Ipv4Addr>,
+
+    /// IPv6 addresses of the interface (link-local and global).
+    pub ipv6: Vec<Ipv6Addr>,
+
+    /// Configuration of the Data Link Layer channel.
+    config: Config,
 }
 
 impl<'a> NetworkInterface {
@@ -55,13 +119,38 @@ impl<'a> NetworkInterface {
     ///
     /// # Returns
     /// `ProtonResult<Self>`, containing the network interface, if it was found.
+    /// If there is no interface with this name, this returns
+    /// `ProtonError::CouldNotFindInterface`.  If the interface exists but does
+    /// not open as an Ethernet channel (e.g. some monitor-mode or TUN devices),
+    /// this returns `ProtonError::MustBeEthernetInterface`.
     pub fn new(iface_name: &str) -> ProtonResult<Self> {
+        Self::open_with_config(iface_name, Config::default())
+    }
+
+    /// Constructs a new network interface with a custom channel configuration.
+    /// 
+    /// The fields of `Config` that matter most for scanning are:
+    /// - `read_timeout`: without one (the default), a read blocks until a
+    ///   frame arrives; with one, `recv` yields `None` once it expires
+    /// - `read_buffer_size` and `write_buffer_size`: the default 4 KiB
+    ///   buffers may drop frames on a busy interface during large scans
+    /// - `promiscuous`: whether or not to receive frames addressed to other
+    ///   hosts (enabled by default)
+    /// 
+    /// # Parameters
+    /// - `iface_name` (`&str`): the name of the network interface
+    /// - `config` (`Config`): the configuration of the Data Link Layer channel
+    /// 
+    /// # Returns
+    /// `ProtonResult<Self>`, containing the network interface, if it was found.
+    /// See `NetworkInterface::new` for the possible errors.
+    pub fn open_with_config(iface_name: &str, config: Config) -> ProtonResult<Self> {
         // Get network interface by name
         let all_interfaces = interfaces();
         let check_wireless = |iface: &datalink::NetworkInterface| iface.name == iface_name;
         let interface = all_interfaces.into_iter()
             .find(check_wireless)
-            .ok_or(ProtonError::CouldNotFindWirelessInterface)?;
+            .ok_or(ProtonError::CouldNotFindInterface (iface_name.to_string()))?;
 
         // Get MAC (hardware) address
         let mac = interface.mac;
@@ -76,10 +165,20 @@ impl<'a> NetworkInterface {
                 unreachable!()
             });
 
+        // Get IPv6 (protocol) addresses
+        let ipv6 = interface.ips
+            .iter()
+            .filter_map(|i| if let IpAddr::V6 (ipv6) = i.ip() {
+                Some (ipv6)
+            } else {
+                None
+            })
+            .collect::<Vec<Ipv6Addr>>();
+
         // Open channel on the Data Link Layer (Layer 2)
         let channel = channel(
-            &interface,         // Network interface
-            Default::default(), // Configuration info
+            &interface, // Network interface
+            config,     // Configuration info
         )?;
 
         // Destructure channel into TX and RX lines
@@ -88,14 +187,137 @@ impl<'a> NetworkInterface {
             Ok (Self {
                 tx: Arc::new(Mutex::new(tx)),
                 rx: Arc::new(Mutex::new(rx)),
+                name: interface.name,
                 mac,
                 ipv4,
+                ipv6,
+                config,
             })
         } else {
             Err (ProtonError::MustBeEthernetInterface)
         }
     }
 
+    /// Constructs a network interface from an existing channel.
+    /// 
+    /// This does not look up the interface, so its hardware and protocol
+    /// addresses are unknown.  It is mostly useful to drive a
+    /// `NetworkInterface` with a mock transmitter and receiver.
+    /// 
+    /// # Parameters
+    /// - `iface_name` (`&str`): the name of the network interface
+    /// - `tx` (`Box<dyn DataLinkSender>`): the transmitter line
+    /// - `rx` (`Box<dyn DataLinkReceiver>`): the receiver line
+    /// 
+    /// # Returns
+    /// The network interface.
+    pub fn from_channel(
+        iface_name: &str,
+        tx: Box<dyn DataLinkSender>,
+        rx: Box<dyn DataLinkReceiver>,
+    ) -> Self {
+        Self {
+            tx: Arc::new(Mutex::new(tx)),
+            rx: Arc::new(Mutex::new(rx)),
+            name: iface_name.to_string(),
+            mac: None,
+            ipv4: None,
+            ipv6: Vec::new(),
+            config: Config::default(),
+        }
+    }
+
+    /// Wait for a network interface to come up, then open it.
+    /// 
+    /// The interface is ready once it has both a MAC address and an IPv4
+    /// address, which a freshly created hotspot may take a moment to get.
+    /// Its addresses are polled without opening a channel, which is only
+    /// opened (with the default configuration) once it is ready.
+    /// 
+    /// # Parameters
+    /// - `iface_name` (`&str`): the name of the network interface
+    /// - `timeout` (`Duration`): the maximum time to wait
+    /// 
+    /// # Returns
+    /// `ProtonResult<Self>`, containing the network interface, whose `mac`
+    /// and `ipv4` are both set, if it became ready in time.  Otherwise, this
+    /// returns `ProtonError::InterfaceNotReady`.  See `NetworkInterface::new`
+    /// for the errors of opening the interface.
+    pub async fn wait_ready(iface_name: &str, timeout: Duration) -> ProtonResult<Self> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            // Check the addresses without opening a channel
+            let ready = interfaces()
+                .into_iter()
+                .find(|iface| iface.name == iface_name)
+                .is_some_and(|iface| iface.mac.is_some() && iface.ips.iter().any(|ip| ip.is_ipv4()));
+
+            if ready {
+                let interface = Self::new(iface_name)?;
+
+                // The addresses may have been removed since they were checked
+                if interface.mac.is_some() && interface.ipv4.is_some() {
+                    return Ok (interface);
+                }
+            }
+
+            if Instant::now() >= deadline {
+                return Err (ProtonError::InterfaceNotReady (iface_name.to_string()));
+            }
+
+            time::sleep(READY_POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now()))).await;
+        }
+    }
+
+    /// Open an independent channel on the same network interface.
+    /// 
+    /// Unlike `Clone::clone`, the new interface has its own transmitter and
+    /// receiver, so every frame is received by both interfaces.  The channel
+    /// is opened with the same configuration as this interface.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// `ProtonResult<Self>`, containing the new network interface, if a
+    /// channel could be opened.
+    pub fn try_clone(&self) -> ProtonResult<Self> {
+        Self::open_with_config(&self.name, self.config)
+    }
+
+    /// Check if this interface shares its channel with another interface.
+    /// 
+    /// # Parameters
+    /// - `other` (`&NetworkInterface`): the other network interface
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not both interfaces use the same
+    /// transmitter and receiver, i.e. one is a `Clone::clone` of the other.
+    pub fn shares_channel_with(&self, other: &NetworkInterface) -> bool {
+        Arc::ptr_eq(&self.tx, &other.tx) && Arc::ptr_eq(&self.rx, &other.rx)
+    }
+
+    /// Check if the link of this interface is operational.
+    /// 
+    /// This reads the operational state of the interface from
+    /// `/sys/class/net/<name>/operstate`.  An interface that is
+    /// administratively up but has no carrier is not operational.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `bool` indicating whether or not the link is up.  Unknown,
+    /// dormant, and down states (or an unreadable state) count as down.
+    pub fn link_is_up(&self) -> bool {
+        let path = format!("{}/{}/operstate", SYS_CLASS_NET_PATH, self.name);
+
+        fs::read_to_string(path)
+            .map(|state| state.trim() == "up")
+            .unwrap_or(false)
+    }
+
     /// Yield the next Ethernet frame from the receiver.
     /// 
     /// # Parameters
@@ -105,9 +327,101 @@ impl<'a> NetworkInterface {
     /// An `Option<Vec<u8>>` containing the received frame, if
     /// it was available.
     pub async fn recv(&'a mut self) -> Option<Vec<u8>> {
-        let mut rx_lock = self.rx.lock().await;
+        let rx_lock = self.rx.clone().lock_owned().await;
+        let (_, frame) = read_frame_blocking(rx_lock).await.ok()?;
+
+        frame
+    }
+
+    /// Yield the next Ethernet frame from the receiver, or nothing if the
+    /// read timeout of the channel expires first.
+    /// 
+    /// Unlike `NetworkInterface::recv`, an expired read timeout is not an
+    /// error, so a caller can poll the receiver in a loop and check other
+    /// conditions (e.g. cancellation) between reads.  Without a read timeout
+    /// (see `NetworkInterface::open_with_config`), this blocks until a frame
+    /// arrives.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Option<Vec<u8>>>` containing the received frame, or
+    /// `None` if the read timed out, if the receiver did not fail.
+    pub async fn poll_recv(&'a mut self) -> ProtonResult<Option<Vec<u8>>> {
+        let rx_lock = self.rx.clone().lock_owned().await;
+        let (_, frame) = read_frame_blocking(rx_lock).await?;
+
+        Ok (frame)
+    }
+
+    /// Yield the next Ethernet frame from the receiver, giving up after `timeout`.
+    /// 
+    /// The receiver is polled (see `NetworkInterface::poll_recv`) until a
+    /// frame arrives or the timeout expires, so no read is left running once
+    /// this returns.  Each read is bounded by the read timeout of the channel,
+    /// so this may give up up to one read timeout late; without a read
+    /// timeout (see `NetworkInterface::open_with_config`), a read blocks until
+    /// a frame arrives.
+    /// 
+    /// # Parameters
+    /// - `timeout` (`Duration`): the maximum time to wait for a frame
+    /// 
+    /// # Returns
+    /// An `Option<Vec<u8>>` containing the received frame, if it was
+    /// available before the timeout.
+    pub async fn recv_timeout(&self, timeout: Duration) -> Option<Vec<u8>> {
+        let deadline = Instant::now() + timeout;
+        let mut rx_lock = time::timeout_at(deadline, self.rx.clone().lock_owned()).await.ok()?;
 
-        Some (rx_lock.next().ok()?.to_vec())
+        while Instant::now() < deadline {
+            let (guard, frame) = read_frame_blocking(rx_lock).await.ok()?;
+
+            if frame.is_some() {
+                return frame;
+            }
+
+            rx_lock = guard;
+        }
+
+        None
+    }
+
+    /// Send an Ethernet frame and wait for a reply that satisfies `matches`.
+    /// 
+    /// Frames that do not satisfy `matches` are discarded.  The channel should
+    /// have a read timeout, which bounds how late this may give up (see
+    /// `NetworkInterface::recv_timeout`).
+    /// 
+    /// # Parameters
+    /// - `frame` (`&[u8]`): the Ethernet frame to send
+    /// - `timeout` (`Duration`): the maximum time to wait for the reply
+    /// - `matches` (`impl Fn(&[u8]) -> bool`): checks whether or not a
+    ///   received frame is the reply
+    /// 
+    /// # Returns
+    /// An `Option<Vec<u8>>` containing the reply, if it was received before
+    /// the timeout.
+    pub async fn send_and_wait_reply(
+        &mut self,
+        frame: &[u8],
+        timeout: Duration,
+        matches: impl Fn(&[u8]) -> bool,
+    ) -> Option<Vec<u8>> {
+        let deadline = Instant::now() + timeout;
+
+        // Send the request
+        self.tx.lock().await.send_to(frame, None);
+
+        // Wait for the reply
+        loop {
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+            let reply = self.recv_timeout(remaining).await?;
+
+            if matches(&reply) {
+                return Some (reply);
+            }
+        }
     }
 
     /// Send an Ethernet frame to this interface.
@@ -122,4 +436,144 @@ impl<'a> NetworkInterface {
 
         tx_lock.send_to(packet.packet(), None);
     }
-}
\ No newline at end of file
+
+    /// Yield the next Ethernet frame from the receiver, with its VLAN tag removed.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Option<(Option<VlanTag>, Vec<u8>)>` containing the VLAN tag of the
+    /// received frame (if it was tagged) and the untagged frame, if a frame
+    /// was available.
+    pub async fn recv_tagged(&'a mut self) -> Option<(Option<VlanTag>, Vec<u8>)> {
+        let frame = self.recv().await?;
+
+        Some (untag_frame(&frame))
+    }
+
+    /// Send an Ethernet frame to this interface on a VLAN.
+    /// 
+    /// # Parameters
+    /// - `vlan_id` (`u16`): the VLAN ID, from `MIN_VLAN_ID` to `MAX_VLAN_ID`
+    /// - `frame` (`&[u8]`): the untagged (or already tagged) Ethernet frame
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the frame could be tagged.
+    pub async fn send_tagged(&'a mut self, vlan_id: u16, frame: &[u8]) -> ProtonResult<()> {
+        let tagged = tag_frame(vlan_id, frame)?;
+        let mut tx_lock = self.tx.lock().await;
+
+        tx_lock.send_to(&tagged, None);
+
+        Ok (())
+    }
+
+    /// Find the default gateway of this interface and resolve its MAC address.
+    /// 
+    /// The gateway is read from the kernel routing table and then resolved
+    /// with a single ARP request.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<(Ipv4Addr, MacAddr)>` containing the IPv4 and MAC
+    /// addresses of the gateway, or `ProtonError::NoDefaultRoute` if this
+    /// interface has no default route.
+    pub async fn default_gateway(&self) -> ProtonResult<(Ipv4Addr, MacAddr)> {
+        // Find the default route of this interface
+        let table = fs::read_to_string(ROUTE_TABLE_PATH)?;
+        let gateway = parse_default_route(&table, &self.name)
+            .ok_or(ProtonError::NoDefaultRoute (self.name.clone()))?;
+
+        // An ARP request requires the addresses of this interface
+        let (interface_mac, interface_ipv4) = match (self.mac, self.ipv4) {
+            (Some (mac), Some (ipv4)) => (mac, ipv4),
+            _ => return Err (ProtonError::CouldNotResolveGateway (gateway)),
+        };
+
+        // Construct ETH frame
+        // Note: it's okay to use `Option::unwrap` here because the buffers
+        //  are large enough to hold the packets
+        let mut eth_frame = MutableEthernetPacket::owned(vec![0u8; 42]).unwrap();
+        eth_frame.set_ethertype(EtherTypes::Arp);
+        eth_frame.set_source(interface_mac);
+        eth_frame.set_destination(MacAddr::broadcast());
+
+        // Construct ARP packet
+        let mut arp_packet = MutableArpPacket::new(eth_frame.payload_mut()).unwrap();
+        arp_packet.set_hardware_type(ArpHardwareTypes::Ethernet);
+        arp_packet.set_protocol_type(EtherTypes::Ipv4);
+        arp_packet.set_hw_addr_len(6);
+        arp_packet.set_proto_addr_len(4);
+        arp_packet.set_operation(ArpOperations::Request);
+        arp_packet.set_sender_hw_addr(interface_mac);
+        arp_packet.set_sender_proto_addr(interface_ipv4);
+        arp_packet.set_target_hw_addr(MacAddr::zero());
+        arp_packet.set_target_proto_addr(gateway);
+
+        // Send the request and wait for the reply
+        let is_reply = |frame: &[u8]| EthernetPacket::new(frame)
+            .filter(|eth_frame| eth_frame.get_ethertype() == EtherTypes::Arp)
+            .and_then(|eth_frame| ArpPacket::owned(eth_frame.payload().to_vec()))
+            .is_some_and(|arp_packet| arp_packet.get_operation() == ArpOperations::Reply
+                && arp_packet.get_sender_proto_addr() == gateway);
+        // Note: an independent channel with a read timeout neither takes
+        //  frames from other clones of this interface nor blocks past the
+        //  resolve timeout
+        let config = Config {
+            read_timeout: Some (GATEWAY_READ_TIMEOUT),
+            ..self.config
+        };
+        let reply = Self::open_with_config(&self.name, config)?
+            .send_and_wait_reply(eth_frame.packet(), GATEWAY_RESOLVE_TIMEOUT, is_reply)
+            .await
+            .ok_or(ProtonError::CouldNotResolveGateway (gateway))?;
+
+        // Note: it's okay to use `Option::unwrap` here because the reply
+        //  was already checked to be an ARP packet
+        let eth_frame = EthernetPacket::new(&reply).unwrap();
+        let arp_packet = ArpPacket::new(eth_frame.payload()).unwrap();
+
+        Ok ((gateway, arp_packet.get_sender_hw_addr()))
+    }
+}
+
+/// Read the next Ethernet frame from a receiver.
+/// 
+/// # Parameters
+/// - `rx` (`&mut dyn DataLinkReceiver`): the receiver
+/// 
+/// # Returns
+/// A `ProtonResult<Option<Vec<u8>>>` containing the received frame, or
+/// `None` if the read timed out, if the receiver did not fail.
+fn read_frame(rx: &mut dyn DataLinkReceiver) -> ProtonResult<Option<Vec<u8>>> {
+    match rx.next() {
+        Ok (frame) => Ok (Some (frame.to_vec())),
+        Err (e) if matches!(e.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock) => Ok (None),
+        Err (e) => Err (e.into()),
+    }
+}
+
+/// Read the next Ethernet frame from a locked receiver on the blocking
+/// thread pool, since a read waits for a frame or the read timeout of the
+/// channel and would otherwise stall an async worker thread.
+/// 
+/// # Parameters
+/// - `rx_lock` (`OwnedMutexGuard<Box<dyn DataLinkReceiver>>`): the locked
+///   receiver
+/// 
+/// # Returns
+/// A `ProtonResult<(OwnedMutexGuard<Box<dyn DataLinkReceiver>>, Option<Vec<u8>>)>`
+/// containing the receiver, still locked, and the received frame, or `None`
+/// if the read timed out, if the receiver did not fail (see `read_frame`).
+async fn read_frame_blocking(
+    mut rx_lock: OwnedMutexGuard<Box<dyn DataLinkReceiver>>,
+) -> ProtonResult<(OwnedMutexGuard<Box<dyn DataLinkReceiver>>, Option<Vec<u8>>)> {
+    task::spawn_blocking(move || {
+        let frame = read_frame(&mut **rx_lock)?;
+
+        Ok ((rx_lock, frame))
+    }).await?
+}
@@ -0,0 +1,44 @@
+//! Kernel routing table utilities.
+
+use std::net::Ipv4Addr;
+
+/// Route flag indicating that the route is up (`RTF_UP`).
+const RTF_UP: u32 = 0x0001;
+
+/// Route flag indicating that the route uses a gateway (`RTF_GATEWAY`).
+const RTF_GATEWAY: u32 = 0x0002;
+
+/// Find the default gateway of a network interface in a routing table.
+/// 
+/// # Parameters
+/// - `table` (`&str`): the contents of `/proc/net/route`
+/// - `ifname` (`&str`): the name of the network interface
+/// 
+/// # Returns
+/// An `Option<Ipv4Addr>` containing the gateway of the first default
+/// route on the interface, if there is one.
+pub fn parse_default_route(table: &str, ifname: &str) -> Option<Ipv4Addr> {
+    table.lines()
+        .skip(1) // Skip the header line
+        .find_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+            // Columns: Iface, Destination, Gateway, Flags, ..., Mask, ...
+            if fields.len() < 8 || fields[0] != ifname {
+                return None;
+            }
+
+            let destination = u32::from_str_radix(fields[1], 16).ok()?;
+            let gateway = u32::from_str_radix(fields[2], 16).ok()?;
+            let flags = u32::from_str_radix(fields[3], 16).ok()?;
+            let mask = u32::from_str_radix(fields[7], 16).ok()?;
+
+            // A default route has an empty destination and mask
+            let is_default = destination == 0 && mask == 0;
+            let is_gateway = flags & (RTF_UP | RTF_GATEWAY) == RTF_UP | RTF_GATEWAY;
+
+            // Note: the kernel prints addresses in network byte order
+            //  as native-endian integers
+            (is_default && is_gateway).then(|| Ipv4Addr::from(gateway.to_ne_bytes()))
+        })
+}
@@ -0,0 +1,128 @@
+//! IEEE 802.1Q VLAN tagging utilities.
+
+use proton_err::{
+    ProtonError,
+    ProtonResult,
+};
+
+/// EtherType of an 802.1Q-tagged frame (TPID).
+const ETHERTYPE_VLAN: u16 = 0x8100;
+
+/// Offset of the EtherType (or TPID) field in an Ethernet frame.
+const ETHERTYPE_OFFSET: usize = 12;
+
+/// Length of an 802.1Q tag.
+const VLAN_TAG_LEN: usize = 4;
+
+/// Mask of the VLAN ID bits of a tag control information (TCI) field.
+const VLAN_ID_MASK: u16 = 0x0FFF;
+
+/// Smallest usable VLAN ID (`0` only carries priority).
+pub const MIN_VLAN_ID: u16 = 1;
+
+/// Largest usable VLAN ID (`4095` is reserved).
+pub const MAX_VLAN_ID: u16 = 4094;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// An IEEE 802.1Q VLAN tag.
+pub struct VlanTag {
+    /// Priority code point (PCP), from `0` to `7`.
+    pub priority: u8,
+
+    /// Drop eligible indicator (DEI).
+    pub drop_eligible: bool,
+
+    /// VLAN identifier (VID).
+    pub id: u16,
+}
+
+impl VlanTag {
+    /// Parse the VLAN tag of an Ethernet frame.
+    /// 
+    /// # Parameters
+    /// - `frame` (`&[u8]`): the Ethernet frame
+    /// 
+    /// # Returns
+    /// An `Option<VlanTag>` containing the tag, if the frame is tagged.
+    pub fn parse(frame: &[u8]) -> Option<Self> {
+        if frame.len() < ETHERTYPE_OFFSET + VLAN_TAG_LEN || ethertype(frame) != ETHERTYPE_VLAN {
+            return None;
+        }
+
+        let tci = u16::from_be_bytes([frame[ETHERTYPE_OFFSET + 2], frame[ETHERTYPE_OFFSET + 3]]);
+
+        Some (Self {
+            priority: (tci >> 13) as u8,
+            drop_eligible: tci & 0x1000 != 0,
+            id: tci & VLAN_ID_MASK,
+        })
+    }
+}
+
+/// Tag an Ethernet frame with a VLAN ID.
+/// 
+/// If the frame is already tagged, its VLAN ID is replaced and its
+/// priority and drop eligibility are preserved.
+/// 
+/// # Parameters
+/// - `vlan_id` (`u16`): the VLAN ID, from `MIN_VLAN_ID` to `MAX_VLAN_ID`
+/// - `frame` (`&[u8]`): the Ethernet frame
+/// 
+/// # Returns
+/// A `ProtonResult<Vec<u8>>` containing the tagged frame.
+pub fn tag_frame(vlan_id: u16, frame: &[u8]) -> ProtonResult<Vec<u8>> {
+    if !(MIN_VLAN_ID..=MAX_VLAN_ID).contains(&vlan_id) {
+        return Err (ProtonError::VlanIdOutOfRange (vlan_id));
+    }
+
+    if frame.len() < ETHERTYPE_OFFSET + 2 {
+        return Err (ProtonError::FrameTooShort (frame.len()));
+    }
+
+    // Replace the VLAN ID of an already-tagged frame
+    if let Some (tag) = VlanTag::parse(frame) {
+        let tci = (tag.priority as u16) << 13
+            | (tag.drop_eligible as u16) << 12
+            | vlan_id;
+
+        let mut tagged = frame.to_vec();
+        tagged[ETHERTYPE_OFFSET + 2..ETHERTYPE_OFFSET + 4].copy_from_slice(&tci.to_be_bytes());
+
+        return Ok (tagged);
+    }
+
+    // Insert a tag between the source MAC address and the EtherType
+    let mut tagged = Vec::with_capacity(frame.len() + VLAN_TAG_LEN);
+    tagged.extend_from_slice(&frame[..ETHERTYPE_OFFSET]);
+    tagged.extend_from_slice(&ETHERTYPE_VLAN.to_be_bytes());
+    tagged.extend_from_slice(&vlan_id.to_be_bytes());
+    tagged.extend_from_slice(&frame[ETHERTYPE_OFFSET..]);
+
+    Ok (tagged)
+}
+
+/// Remove the VLAN tag of an Ethernet frame.
+/// 
+/// # Parameters
+/// - `frame` (`&[u8]`): the Ethernet frame
+/// 
+/// # Returns
+/// A tuple `(Option<VlanTag>, Vec<u8>)` containing the tag, if the frame
+/// was tagged, and the untagged frame.
+pub fn untag_frame(frame: &[u8]) -> (Option<VlanTag>, Vec<u8>) {
+    match VlanTag::parse(frame) {
+        Some (tag) => {
+            let mut untagged = Vec::with_capacity(frame.len() - VLAN_TAG_LEN);
+            untagged.extend_from_slice(&frame[..ETHERTYPE_OFFSET]);
+            untagged.extend_from_slice(&frame[ETHERTYPE_OFFSET + VLAN_TAG_LEN..]);
+
+            (Some (tag), untagged)
+        },
+        None => (None, frame.to_vec()),
+    }
+}
+
+/// Read the EtherType (or TPID) field of an Ethernet frame.
+fn ethertype(frame: &[u8]) -> u16 {
+    u16::from_be_bytes([frame[ETHERTYPE_OFFSET], frame[ETHERTYPE_OFFSET + 1]])
+}
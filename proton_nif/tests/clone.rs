@@ -0,0 +1,25 @@
+//! Testing network interface cloning semantics.
+
+use proton_nif::NetworkInterface;
+
+#[test]
+#[ignore = "requires raw socket privileges"]
+fn clone_shares_channel() {
+    let interface = NetworkInterface::new("lo").unwrap();
+    let clone = interface.clone();
+
+    assert!(interface.shares_channel_with(&clone));
+    assert!(clone.shares_channel_with(&interface));
+}
+
+#[test]
+#[ignore = "requires raw socket privileges"]
+fn try_clone_opens_new_channel() {
+    let interface = NetworkInterface::new("lo").unwrap();
+    let independent = interface.try_clone().unwrap();
+
+    assert!(!interface.shares_channel_with(&independent));
+    assert_eq!(independent.name, interface.name);
+    assert_eq!(independent.mac, interface.mac);
+    assert_eq!(independent.ipv4, interface.ipv4);
+}
@@ -0,0 +1,52 @@
+//! Testing network interface channel configuration and readiness.
+
+use std::{
+    net::Ipv4Addr,
+    time::Duration,
+};
+
+use pnet::datalink::Config;
+
+use proton_err::ProtonError;
+
+use proton_nif::NetworkInterface;
+
+#[test]
+fn open_missing_interface() {
+    let result = NetworkInterface::open_with_config("proton-missing0", Config::default());
+
+    assert!(matches!(result, Err (ProtonError::CouldNotFindInterface (ifname)) if ifname == "proton-missing0"));
+}
+
+#[test]
+#[ignore = "requires raw socket privileges"]
+fn open_with_large_buffers() {
+    let config = Config {
+        read_buffer_size: 1 << 16,
+        write_buffer_size: 1 << 16,
+        read_timeout: Some (Duration::from_millis(100)),
+        ..Default::default()
+    };
+    let interface = NetworkInterface::open_with_config("lo", config).unwrap();
+    let independent = interface.try_clone().unwrap();
+
+    assert_eq!(independent.name, "lo");
+}
+
+#[tokio::test]
+async fn wait_ready_times_out() {
+    let result = NetworkInterface::wait_ready("proton-missing0", Duration::from_millis(250)).await;
+
+    assert!(matches!(result, Err (ProtonError::InterfaceNotReady (ifname)) if ifname == "proton-missing0"));
+}
+
+#[tokio::test]
+#[ignore = "requires raw socket privileges"]
+async fn wait_ready_on_loopback() {
+    // Note: the loopback interface has an all-zero MAC address
+    let interface = NetworkInterface::wait_ready("lo", Duration::from_secs(1)).await.unwrap();
+
+    assert_eq!(interface.name, "lo");
+    assert!(interface.mac.is_some());
+    assert_eq!(interface.ipv4, Some (Ipv4Addr::LOCALHOST));
+}
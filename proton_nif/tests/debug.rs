@@ -0,0 +1,100 @@
+//! Testing the frame debugging utilities.
+
+use proton_nif::{
+    describe_frame,
+    hex_dump,
+    tag_frame,
+};
+
+/// Construct an ARP frame from `192.168.0.1` (`02:00:00:00:00:01`).
+fn arp_frame(operation: u8, target_ipv4: [u8; 4]) -> Vec<u8> {
+    let mut frame = Vec::new();
+
+    // Ethernet header
+    frame.extend_from_slice(&[0xff; 6]);
+    frame.extend_from_slice(&[0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+    frame.extend_from_slice(&[0x08, 0x06]);
+
+    // ARP packet
+    frame.extend_from_slice(&[0x00, 0x01, 0x08, 0x00, 6, 4, 0x00, operation]);
+    frame.extend_from_slice(&[0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+    frame.extend_from_slice(&[192, 168, 0, 1]);
+    frame.extend_from_slice(&[0x00; 6]);
+    frame.extend_from_slice(&target_ipv4);
+
+    frame
+}
+
+#[test]
+fn hex_dump_format() {
+    let dump = hex_dump(b"\xff\xff\xff\xff\xff\xff\x02\x00\x00\x00\x00\x01\x08\x06\x00\x01Proton");
+
+    assert_eq!(
+        dump,
+        "00000000  ff ff ff ff ff ff 02 00  00 00 00 01 08 06 00 01  |................|\n\
+         00000010  50 72 6f 74 6f 6e                                 |Proton|\n",
+    );
+}
+
+#[test]
+fn hex_dump_empty() {
+    assert_eq!(hex_dump(&[]), "");
+}
+
+#[test]
+fn describe_arp_request() {
+    assert_eq!(
+        describe_frame(&arp_frame(1, [192, 168, 0, 5])),
+        "02:00:00:00:00:01 > ff:ff:ff:ff:ff:ff, ARP who-has 192.168.0.5 tell 192.168.0.1",
+    );
+}
+
+#[test]
+fn describe_arp_reply() {
+    assert_eq!(
+        describe_frame(&arp_frame(2, [192, 168, 0, 5])),
+        "02:00:00:00:00:01 > ff:ff:ff:ff:ff:ff, ARP 192.168.0.1 is-at 02:00:00:00:00:01",
+    );
+}
+
+#[test]
+fn describe_udp_datagram() {
+    let mut frame = Vec::new();
+
+    // Ethernet header
+    frame.extend_from_slice(&[0x01, 0x00, 0x5e, 0x00, 0x00, 0xfb]);
+    frame.extend_from_slice(&[0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+    frame.extend_from_slice(&[0x08, 0x00]);
+
+    // IPv4 header
+    frame.extend_from_slice(&[0x45, 0x00, 0x00, 28, 0, 0, 0, 0, 255, 17, 0, 0]);
+    frame.extend_from_slice(&[192, 168, 0, 2]);
+    frame.extend_from_slice(&[224, 0, 0, 251]);
+
+    // UDP header
+    frame.extend_from_slice(&[0x14, 0xe9, 0x14, 0xe9, 0, 8, 0, 0]);
+
+    assert_eq!(
+        describe_frame(&frame),
+        "02:00:00:00:00:01 > 01:00:5e:00:00:fb, IPv4 192.168.0.2:5353 > 224.0.0.251:5353, UDP, length 28",
+    );
+}
+
+#[test]
+fn describe_tagged_frame() {
+    let frame = tag_frame(10, &arp_frame(1, [192, 168, 0, 5])).unwrap();
+
+    assert_eq!(
+        describe_frame(&frame),
+        "02:00:00:00:00:01 > ff:ff:ff:ff:ff:ff, 802.1Q vlan 10 priority 0, ARP who-has 192.168.0.5 tell 192.168.0.1",
+    );
+}
+
+#[test]
+fn describe_other_frames() {
+    let mut frame = arp_frame(1, [192, 168, 0, 5]);
+    frame[12..14].copy_from_slice(&[0x88, 0xcc]);
+
+    assert_eq!(describe_frame(&frame), "02:00:00:00:00:01 > ff:ff:ff:ff:ff:ff, ethertype 0x88cc, length 28");
+    assert_eq!(describe_frame(&frame[..10]), "truncated frame (10 bytes)");
+}
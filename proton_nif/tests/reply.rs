@@ -0,0 +1,136 @@
+//! Testing request/reply exchanges with a mock channel.
+
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use pnet::datalink::{
+    self,
+    DataLinkReceiver,
+    DataLinkSender,
+};
+
+use tokio::time;
+
+use proton_nif::NetworkInterface;
+
+/// Frames sent through a `MockSender`.
+type Sent = Arc<Mutex<Vec<Vec<u8>>>>;
+
+/// A transmitter that records every frame it sends.
+struct MockSender (Sent);
+
+impl DataLinkSender for MockSender {
+    fn build_and_send(
+        &mut self,
+        num_packets: usize,
+        packet_size: usize,
+        func: &mut dyn FnMut(&mut [u8]),
+    ) -> Option<io::Result<()>> {
+        for _ in 0..num_packets {
+            let mut packet = vec![0u8; packet_size];
+            func(&mut packet);
+            self.0.lock().unwrap().push(packet);
+        }
+
+        Some (Ok (()))
+    }
+
+    fn send_to(&mut self, packet: &[u8], _dst: Option<datalink::NetworkInterface>) -> Option<io::Result<()>> {
+        self.0.lock().unwrap().push(packet.to_vec());
+
+        Some (Ok (()))
+    }
+}
+
+/// A receiver that yields queued frames, then blocks for `idle` per read.
+struct MockReceiver {
+    frames: VecDeque<Vec<u8>>,
+    current: Vec<u8>,
+    idle: Duration,
+}
+
+impl DataLinkReceiver for MockReceiver {
+    fn next(&mut self) -> io::Result<&[u8]> {
+        match self.frames.pop_front() {
+            Some (frame) => {
+                self.current = frame;
+                Ok (&self.current)
+            },
+            None => {
+                thread::sleep(self.idle);
+                Err (io::ErrorKind::TimedOut.into())
+            },
+        }
+    }
+}
+
+fn interface(frames: &[&[u8]], idle: Duration) -> (NetworkInterface, Sent) {
+    let sent = Sent::default();
+    let rx = MockReceiver {
+        frames: frames.iter().map(|frame| frame.to_vec()).collect(),
+        current: Vec::new(),
+        idle,
+    };
+    let interface = NetworkInterface::from_channel(
+        "mock0",
+        Box::new(MockSender (sent.clone())),
+        Box::new(rx),
+    );
+
+    (interface, sent)
+}
+
+#[tokio::test]
+async fn returns_first_matching_reply() {
+    let (mut interface, sent) = interface(&[b"noise", b"reply 1", b"reply 2"], Duration::ZERO);
+
+    let reply = interface.send_and_wait_reply(
+        b"request",
+        Duration::from_secs(1),
+        |frame| frame.starts_with(b"reply"),
+    ).await;
+
+    assert_eq!(reply.as_deref(), Some (b"reply 1".as_slice()));
+    assert_eq!(*sent.lock().unwrap(), vec![b"request".to_vec()]);
+}
+
+#[tokio::test]
+async fn returns_none_without_matching_reply() {
+    let (mut interface, _) = interface(&[b"noise"], Duration::ZERO);
+
+    let reply = interface.send_and_wait_reply(
+        b"request",
+        Duration::from_secs(1),
+        |frame| frame.starts_with(b"reply"),
+    ).await;
+
+    assert_eq!(reply, None);
+}
+
+#[tokio::test]
+async fn recv_timeout_gives_up_between_reads() {
+    // Each read times out after 20 ms, like a channel with a read timeout
+    let (mut interface, _) = interface(&[], Duration::from_millis(20));
+
+    let start = Instant::now();
+    let frame = interface.recv_timeout(Duration::from_millis(50)).await;
+
+    assert_eq!(frame, None);
+    assert!(start.elapsed() < Duration::from_millis(400));
+
+    // No read is left holding the receiver
+    let frame = time::timeout(Duration::from_millis(100), interface.poll_recv()).await;
+
+    assert!(matches!(frame, Ok (Ok (None))));
+}
@@ -0,0 +1,26 @@
+//! Testing the routing table parser.
+
+use std::net::Ipv4Addr;
+
+use proton_nif::parse_default_route;
+
+/// A routing table as printed by `/proc/net/route` on a little-endian host.
+const TABLE: &str = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlan0\t0000A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\t0\t0\t0
+eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+";
+
+#[test]
+#[cfg(target_endian = "little")]
+fn find_default_route() {
+    assert_eq!(parse_default_route(TABLE, "eth0"), Some (Ipv4Addr::new(192, 168, 1, 1)));
+}
+
+#[test]
+fn no_default_route() {
+    assert_eq!(parse_default_route(TABLE, "wlan0"), None);
+    assert_eq!(parse_default_route(TABLE, "wlan1"), None);
+    assert_eq!(parse_default_route("", "eth0"), None);
+}
@@ -0,0 +1,41 @@
+//! Testing 802.1Q VLAN tagging.
+
+use proton_nif::{
+    tag_frame,
+    untag_frame,
+    VlanTag,
+};
+
+/// An untagged IPv4 Ethernet frame with a short payload.
+const FRAME: [u8; 16] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // Destination
+    0x12, 0x34, 0x56, 0x78, 0x90, 0xab, // Source
+    0x08, 0x00,                         // EtherType
+    0xde, 0xad,                         // Payload
+];
+
+#[test]
+fn tag_and_untag() {
+    let tagged = tag_frame(42, &FRAME).unwrap();
+
+    assert_eq!(&tagged[12..16], &[0x81, 0x00, 0x00, 42]);
+    assert_eq!(untag_frame(&tagged), (Some (VlanTag { priority: 0, drop_eligible: false, id: 42 }), FRAME.to_vec()));
+}
+
+#[test]
+fn retag_preserves_priority() {
+    let mut tagged = tag_frame(42, &FRAME).unwrap();
+    tagged[14] |= 0xa0; // Priority 5
+
+    let retagged = tag_frame(100, &tagged).unwrap();
+
+    assert_eq!(VlanTag::parse(&retagged), Some (VlanTag { priority: 5, drop_eligible: false, id: 100 }));
+    assert_eq!(retagged.len(), tagged.len());
+}
+
+#[test]
+fn reject_invalid_vlan_ids() {
+    assert!(tag_frame(0, &FRAME).is_err());
+    assert!(tag_frame(4095, &FRAME).is_err());
+    assert!(tag_frame(42, &FRAME[..10]).is_err());
+}
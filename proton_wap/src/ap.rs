@@ -1,5 +1,16 @@
 //! Wireless access point abstraction.
 
+use std::{
+    net::Ipv4Addr,
+    ops::RangeInclusive,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use cidr::Ipv4Cidr;
+
 use network_manager::{
     Device as NmDevice,
     DeviceType,
@@ -10,11 +21,23 @@ use network_manager::{
 
 use nix::unistd::Uid;
 
-use proton_cfg::HotspotConfig;
+use proton_mac::MacAddr;
+
+use proton_nif::NetworkInterface;
+
+use proton_cfg::{
+    Band,
+    ConfigChange,
+    HotspotConfig,
+};
 
 use proton_dev::{
+    best_channel,
+    ClientSummary,
     Device,
+    devices_to_json,
     DeviceManager,
+    ReasonCode,
 };
 
 use proton_err::{
@@ -22,6 +45,25 @@ use proton_err::{
     ProtonError,
 };
 
+use crate::{
+    replace_hotspot,
+    Replacement,
+    settings::{
+        set_channel,
+        set_hidden,
+        set_security,
+    },
+};
+
+/// Time to wait for the wireless interface to come up after the hotspot is created.
+pub const INTERFACE_READY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Frequencies of the 2.4 GHz band, in MHz.
+const BAND_2_4_GHZ_MHZ: RangeInclusive<u32> = 2400..=2500;
+
+/// Frequencies of the 5 GHz band, in MHz.
+const BAND_5_GHZ_MHZ: RangeInclusive<u32> = 5150..=5925;
+
 /// A wireless access point.
 /// 
 /// **Note**: to construct and use this, you must run the associated
@@ -32,16 +74,27 @@ use proton_err::{
 /// This is enforced by `AccessPoint::new()`, as the constructor will return
 /// a `ProtonError` if you attempt to execute it without root permission.
 pub struct AccessPoint {
+    /// Wireless interface name.
+    wlifname: String,
+
     /// Device discovery manager.
     device_manager: DeviceManager,
 
     /// Hotspot configuration information.
     pub config: HotspotConfig,
+
+    /// The time at which the hotspot was last activated, if it is active.
+    activated_at: Option<Instant>,
 }
 
 impl AccessPoint {
     /// Constructs a new wireless access point.
     /// 
+    /// The hotspot is created first, and its devices are only managed once
+    /// the wireless interface is ready.  If the interface does not become
+    /// ready, or the driver does not support the security mode, the hotspot
+    /// is removed again.
+    /// 
     /// # Parameters
     /// - `wlifname` (`&str`): the name of the wireless interface over which
     /// this access point connects to remote devices (e.g. "wlan0")
@@ -59,11 +112,124 @@ impl AccessPoint {
             return Err (ProtonError::MustHaveRootPermissions);
         }
 
+        // Make sure the configuration can be applied
+        Self::check_config(&config)?;
+
+        // Create and activate the hotspot
+        let connection = Self::create_hotspot(wlifname, &config)?;
+
+        // Start managing it, removing it on failure
+        // Note: no `AccessPoint` is returned on failure, so the hotspot
+        //  could not be deleted later.
+        let device_manager = match Self::manage_hotspot(wlifname, &config).await {
+            Ok (device_manager) => device_manager,
+            Err (e) => {
+                let _ = connection.delete();
+
+                return Err (e);
+            },
+        };
+
+        Ok (Self {
+            wlifname: wlifname.to_string(),
+            device_manager,
+            config,
+            activated_at: Some (Instant::now()),
+        })
+    }
+
+    /// Wait for a new hotspot to come up, then start managing its devices.
+    /// 
+    /// # Parameters
+    /// - `wlifname` (`&str`): the name of the wireless interface
+    /// - `config` (`&HotspotConfig`): the hotspot configuration
+    /// 
+    /// # Returns
+    /// A `ProtonResult<DeviceManager>` containing the device manager of the
+    /// ready interface, if the driver supports the security mode.
+    async fn manage_hotspot(wlifname: &str, config: &HotspotConfig) -> ProtonResult<DeviceManager> {
+        // Wait for the interface to get its addresses before it is scanned
+        let interface = NetworkInterface::wait_ready(wlifname, INTERFACE_READY_TIMEOUT).await?;
+
+        // Initialize the device manager on the ready interface
+        let mut device_manager = DeviceManager::new(config.cidr, &interface.name)?;
+
+        // Make sure the driver supports WPA3 or OWE, if either was requested
+        Self::check_security(config, &mut device_manager).await?;
+
+        Ok (device_manager)
+    }
+
+    /// Check that a hotspot configuration can be applied.
+    /// 
+    /// # Parameters
+    /// - `config` (`&HotspotConfig`): the hotspot configuration
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the configuration
+    /// can be applied.
+    fn check_config(config: &HotspotConfig) -> ProtonResult<()> {
+        // Make sure the password is valid for the security mode
+        config.validate()?;
+
+        // Make sure gateway is in the CIDR range
+        if !config.cidr.contains(&config.gateway) {
+            return Err (ProtonError::CidrMustContainGateway {
+                cidr: config.cidr.to_string(),
+                gateway: config.gateway.to_string(),
+            });
+        }
+
+        // Make sure the IPv6 gateway is in the IPv6 prefix
+        if let (Some (prefix), Some (gateway)) = (config.ipv6_prefix, config.ipv6_gateway) {
+            if !prefix.contains(&gateway) {
+                return Err (ProtonError::PrefixMustContainGateway {
+                    prefix: prefix.to_string(),
+                    gateway: gateway.to_string(),
+                });
+            }
+        }
+
+        Ok (())
+    }
+
+    /// Check that the driver supports the security mode of a hotspot configuration.
+    /// 
+    /// # Parameters
+    /// - `config` (`&HotspotConfig`): the hotspot configuration
+    /// - `device_manager` (`&mut DeviceManager`): the device manager of the interface
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the security mode is supported.
+    async fn check_security(config: &HotspotConfig, device_manager: &mut DeviceManager) -> ProtonResult<()> {
+        if config.security.requires_sae() && !device_manager.supports_sae().await? {
+            return Err (ProtonError::SecurityNotSupported (config.security.to_string()));
+        }
+
+        if config.security.requires_owe() && !device_manager.supports_owe().await? {
+            return Err (ProtonError::SecurityNotSupported (config.security.to_string()));
+        }
+
+        Ok (())
+    }
+
+    /// Create and activate a hotspot.
+    /// 
+    /// # Parameters
+    /// - `wlifname` (`&str`): the name of the wireless interface
+    /// - `config` (`&HotspotConfig`): the hotspot configuration
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Connection>` containing the hotspot connection, if it
+    /// was created and activated.
+    fn create_hotspot(wlifname: &str, config: &HotspotConfig) -> ProtonResult<Connection> {
         // Initialize NetworkManager API
         let network_manager = NetworkManager::new();
 
-        // Is this device a Wi-Fi device?
-        let check_if_wifi_device = |device: &NmDevice| *device.device_type() == DeviceType::WiFi;
+        // Is this device the requested Wi-Fi device?
+        let check_if_wifi_device = |device: &NmDevice| {
+            *device.device_type() == DeviceType::WiFi && device.interface() == wlifname
+        };
 
         // Get Wi-Fi device
         let device = network_manager.get_devices()
@@ -76,57 +242,222 @@ impl AccessPoint {
         let wifi_device = device.as_wifi_device()
             .ok_or(ProtonError::CouldNotFindWirelessInterface)?;
 
-        // Make sure gateway is in the CIDR range
-        if !config.cidr.contains(&config.gateway) {
-            return Err (ProtonError::CidrMustContainGateway {
-                cidr: config.cidr.to_string(),
-                gateway: config.gateway.to_string(),
-            });
-        }
+        // Open and OWE hotspots are created without a password
+        // Note: the hotspot API only adds the `802-11-wireless-security`
+        //  setting when it is given a password, which open hotspots must
+        //  not have at all (see `Security::key_mgmt`).  The setting of OWE
+        //  hotspots is added by `Self::configure_connection`.
+        let password = if config.security.requires_password() {
+            Some (config.pass.as_str())
+        } else {
+            None
+        };
+        let key_mgmt = config.security.key_mgmt().unwrap_or_default();
 
         // Create a hotspot on the selected device
         let (connection, _state) = wifi_device.create_hotspot_advanced::<str>(
             config.ssid.as_str(),
-            Some (config.pass.as_str()),
+            password,
             config.gateway,
-            config.security.as_str(),
-            config.band.as_str(),
+            key_mgmt,
+            config.band.nm_band(),
         )?;
 
-        // Activate the hotspot
+        // Apply the settings the hotspot API does not take and activate the
+        // hotspot, removing it if it does not come up
+        // Note: these settings only take effect when the connection is
+        //  activated.  A failure to clean up would only hide the original error.
+        let activated = Self::configure_connection(&connection, config)
+            .and_then(|()| Self::activate_connection(&connection));
+
+        if let Err (e) = activated {
+            let _ = connection.delete();
+
+            return Err (e);
+        }
+
+        Ok (connection)
+    }
+
+    /// Write the settings of a hotspot configuration that the NetworkManager
+    /// hotspot API does not take to a hotspot connection.
+    /// 
+    /// # Parameters
+    /// - `connection` (`&Connection`): the hotspot connection
+    /// - `config` (`&HotspotConfig`): the hotspot configuration
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the connection
+    /// was updated.
+    fn configure_connection(connection: &Connection, config: &HotspotConfig) -> ProtonResult<()> {
+        let uuid = &connection.settings().uuid;
+
+        // Move the hotspot to the selected channel
+        if let Some (channel) = config.channel {
+            set_channel(uuid, channel, config.band)?;
+        }
+
+        // Hide the SSID, if requested
+        if config.hidden {
+            set_hidden(uuid, true)?;
+        }
+
+        // Set the key management of OWE, which the hotspot API leaves out
+        // along with the password, and PMF, which it does not take (e.g. the
+        // optional PMF of WPA2/WPA3 transitional hotspots)
+        if config.security.pmf().is_some() {
+            set_security(uuid, config.security)?;
+        }
+
+        Ok (())
+    }
+
+    /// Activate a hotspot connection.
+    /// 
+    /// # Parameters
+    /// - `connection` (`&Connection`): the hotspot connection
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the connection
+    /// was activated.
+    fn activate_connection(connection: &Connection) -> ProtonResult<()> {
         if ConnectionState::Activated != connection.activate()? {
             return Err (ProtonError::CouldNotActivateHotspot);
         }
 
-        Ok (Self {
-            device_manager: DeviceManager::new(config.cidr, wlifname)?,
-            config,
-        })
+        Ok (())
+    }
+
+    /// Replace the hotspot configuration, rolling back on failure.
+    /// 
+    /// The new configuration is validated before the hotspot is touched.  If
+    /// only fields outside of the NetworkManager connection changed (see
+    /// `ConfigChange::requires_restart`), the new configuration is applied
+    /// without disconnecting clients.  Otherwise, a hotspot is created with
+    /// the new configuration, and the previous hotspot is only deleted once
+    /// the new one is active.  If the new hotspot fails, the previous one is
+    /// reactivated and the error is returned.  The previous hotspot is never
+    /// deleted on failure, so if it cannot be reactivated either, it can be
+    /// retried with `AccessPoint::activate`.
+    /// 
+    /// # Parameters
+    /// - `new` (`HotspotConfig`): the new hotspot configuration
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the new configuration
+    /// was applied.
+    pub async fn reload_config(&mut self, new: HotspotConfig) -> ProtonResult<()> {
+        // Make sure the new configuration can be applied before tearing anything down
+        Self::check_config(&new)?;
+        Self::check_security(&new, &mut self.device_manager).await?;
+
+        // Apply changes that do not affect the connection in place
+        let changes = self.config.diff(&new);
+
+        if !changes.iter().any(ConfigChange::requires_restart) {
+            if changes.contains(&ConfigChange::Cidr) {
+                self.device_manager.set_range(new.cidr);
+            }
+            self.config = new;
+
+            return Ok (());
+        }
+
+        // Create the new hotspot before removing the current one, so that the
+        // current one can be reactivated if the new one fails
+        let previous = self.get_hotspot()?;
+        let replacement = replace_hotspot(
+            &previous,
+            || Self::create_hotspot(&self.wlifname, &new),
+            Self::activate_connection,
+        );
+
+        match replacement {
+            Replacement::Replaced (_) => {
+                self.device_manager.set_range(new.cidr);
+                self.config = new;
+                self.activated_at = Some (Instant::now());
+
+                // Remove the previous hotspot, now that the new one is active
+                previous.delete()?;
+
+                Ok (())
+            },
+            Replacement::Restored (e) => {
+                self.activated_at = Some (Instant::now());
+
+                Err (e)
+            },
+            Replacement::Down (e) => {
+                self.activated_at = None;
+
+                Err (e)
+            },
+        }
     }
 
-    /// Get the NetworkManager hotspot abstraction.
+    /// List all hotspot connections known to NetworkManager.
     /// 
     /// # Parameters
     /// None.
     /// 
     /// # Returns
-    /// A `ProtonResult<Connection>` containing the connection,
-    /// if the method could successfully construct it.
-    fn get_hotspot(&mut self) -> ProtonResult<Connection> {
+    /// A `ProtonResult<Vec<Connection>>` containing every connection in
+    /// access point mode, whether or not it is active.
+    pub fn list_hotspots(&self) -> ProtonResult<Vec<Connection>> {
         // Initialize NetworkManager API
         let nm = NetworkManager::new();
 
         // Check for hotspot
         let check_if_ap = |connection: &Connection| connection.settings().mode.as_str() == "ap";
 
-        nm.get_connections()
-            .unwrap_or_default()
+        let hotspots = nm.get_connections()?
             .into_iter()
-            .find(check_if_ap)
+            .filter(check_if_ap)
+            .collect();
+
+        Ok (hotspots)
+    }
+
+    /// Get a hotspot connection by SSID or UUID.
+    /// 
+    /// # Parameters
+    /// - `target` (`&str`): the SSID or UUID of the connection
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Connection>` containing the connection, or
+    /// `ProtonError::HotspotNotInitialized` if there is no such hotspot.
+    fn find_hotspot(&self, target: &str) -> ProtonResult<Connection> {
+        let check_target = |connection: &Connection| {
+            let settings = connection.settings();
+
+            settings.uuid == target || settings.ssid.as_bytes() == target.as_bytes()
+        };
+
+        self.list_hotspots()?
+            .into_iter()
+            .find(check_target)
             .ok_or(ProtonError::HotspotNotInitialized)
     }
 
-    /// Activate the hotspot.
+    /// Get the NetworkManager hotspot abstraction of the configured SSID.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Connection>` containing the connection,
+    /// if the method could successfully construct it.
+    fn get_hotspot(&self) -> ProtonResult<Connection> {
+        self.find_hotspot(&self.config.ssid)
+    }
+
+    /// Check if a connection is the hotspot of the configured SSID.
+    fn is_primary(&self, connection: &Connection) -> bool {
+        connection.settings().ssid.as_bytes() == self.config.ssid.as_bytes()
+    }
+
+    /// Activate the hotspot of the configured SSID.
     /// 
     /// # Parameters
     /// None.
@@ -135,12 +466,48 @@ impl AccessPoint {
     /// A `ProtonResult<()>` indicating whether or not the activation
     /// was successful.
     pub async fn activate(&mut self) -> ProtonResult<()> {
-        self.get_hotspot()?.activate()?;
+        let ssid = self.config.ssid.clone();
+
+        self.activate_hotspot(&ssid).await
+    }
+
+    /// Activate a hotspot by SSID or UUID.
+    /// 
+    /// If this is the hotspot of the configured SSID, its gateway address is
+    /// then announced with a gratuitous ARP so that clients refresh their ARP
+    /// caches, and so that another device using the gateway address answers.
+    /// 
+    /// # Parameters
+    /// - `target` (`&str`): the SSID or UUID of the hotspot
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the activation
+    /// was successful.  If the hotspot was activated but the announcement
+    /// could not be sent, that error is returned, and if another device is
+    /// using the gateway address, `ProtonError::GatewayConflict` is returned;
+    /// in both cases the hotspot stays activated.
+    pub async fn activate_hotspot(&mut self, target: &str) -> ProtonResult<()> {
+        let connection = self.find_hotspot(target)?;
+        Self::activate_connection(&connection)?;
+
+        if !self.is_primary(&connection) {
+            return Ok (());
+        }
+
+        self.activated_at = Some (Instant::now());
+
+        // Announce the gateway address, watching for conflicts
+        if let Some (mac) = self.device_manager.announce(self.config.gateway).await? {
+            return Err (ProtonError::GatewayConflict {
+                gateway: self.config.gateway,
+                mac,
+            });
+        }
 
         Ok (())
     }
 
-    /// Deactivate the hotspot.
+    /// Deactivate the hotspot of the configured SSID.
     /// 
     /// # Parameters
     /// None.
@@ -149,12 +516,31 @@ impl AccessPoint {
     /// A `ProtonResult<()>` indicating whether or not the deactivation
     /// was successful.
     pub async fn deactivate(&mut self) -> ProtonResult<()> {
-        self.get_hotspot()?.deactivate()?;
+        let ssid = self.config.ssid.clone();
+
+        self.deactivate_hotspot(&ssid).await
+    }
+
+    /// Deactivate a hotspot by SSID or UUID.
+    /// 
+    /// # Parameters
+    /// - `target` (`&str`): the SSID or UUID of the hotspot
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the deactivation
+    /// was successful.
+    pub async fn deactivate_hotspot(&mut self, target: &str) -> ProtonResult<()> {
+        let connection = self.find_hotspot(target)?;
+        connection.deactivate()?;
+
+        if self.is_primary(&connection) {
+            self.activated_at = None;
+        }
 
         Ok (())
     }
 
-    /// Delete the hotspot.
+    /// Delete the hotspot of the configured SSID.
     /// 
     /// # Parameters
     /// None.
@@ -163,13 +549,113 @@ impl AccessPoint {
     /// A `ProtonResult<()>` indicating whether or not the deletion
     /// was successful.
     pub async fn delete(&mut self) -> ProtonResult<()> {
-        self.get_hotspot()?.delete()?;
+        let ssid = self.config.ssid.clone();
+
+        self.delete_hotspot(&ssid).await
+    }
+
+    /// Delete a hotspot by SSID or UUID.
+    /// 
+    /// # Parameters
+    /// - `target` (`&str`): the SSID or UUID of the hotspot
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the deletion
+    /// was successful.
+    pub async fn delete_hotspot(&mut self, target: &str) -> ProtonResult<()> {
+        let connection = self.find_hotspot(target)?;
+        let primary = self.is_primary(&connection);
+        connection.delete()?;
+
+        if primary {
+            self.activated_at = None;
+        }
+
+        Ok (())
+    }
+
+    /// Tear down this access point.
+    /// 
+    /// The hotspot of the configured SSID is deactivated (if it is active)
+    /// and deleted, and the device manager and its sockets are released.  A
+    /// hotspot that was already deleted is not an error, so this is safe to
+    /// call after `AccessPoint::delete` (e.g. from a `SIGTERM` handler).
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the hotspot was torn down.
+    pub async fn shutdown(self) -> ProtonResult<()> {
+        // The hotspot may already have been deleted
+        let connection = match self.get_hotspot() {
+            Ok (connection) => connection,
+            Err (ProtonError::HotspotNotInitialized) => return Ok (()),
+            Err (e) => return Err (e),
+        };
+
+        // Deactivate the hotspot, if it is active
+        if connection.get_state()? == ConnectionState::Activated {
+            connection.deactivate()?;
+        }
+
+        // Note: the device manager (and its sockets) is dropped with `self`
+        connection.delete()?;
 
         Ok (())
     }
 
+    /// Get the name of the wireless interface of this access point.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `&str` containing the interface name (e.g. "wlan0").
+    pub fn interface_name(&self) -> &str {
+        &self.wlifname
+    }
+
+    /// Get the gateway address of this access point.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Ipv4Addr` containing the gateway address.
+    pub fn gateway(&self) -> Ipv4Addr {
+        self.config.gateway
+    }
+
+    /// Get the CIDR range of the network of this access point.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Ipv4Cidr` containing the network range.
+    pub fn cidr(&self) -> Ipv4Cidr {
+        self.config.cidr
+    }
+
+    /// Get the time elapsed since the hotspot was activated.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// An `Option<Duration>` containing the uptime of the hotspot, or `None`
+    /// if it is not active.
+    pub fn uptime(&self) -> Option<Duration> {
+        self.activated_at.map(|activated_at| activated_at.elapsed())
+    }
+
     /// Get a list of all connected devices.
     /// 
+    /// If `config.ipv6_prefix` is set, the IPv6 addresses of the devices are
+    /// also resolved.  IPv6 resolution is best-effort, so devices that could
+    /// not be resolved keep `ipv6` as `None`.
+    /// 
     /// # Parameters
     /// None.
     /// 
@@ -177,6 +663,214 @@ impl AccessPoint {
     /// A `ProtonResult<Vec<Device>>` wrappping the list of devices, if
     /// the network scan was successful.
     pub async fn scan(&mut self) -> ProtonResult<Vec<Device>> {
-        Ok (self.device_manager.scan().await?)
+        let mut devices = self.device_manager.scan().await?;
+
+        if let Some (prefix) = self.config.ipv6_prefix {
+            let _ = self.device_manager.resolve_ipv6(&mut devices, prefix).await;
+        }
+
+        Ok (devices)
+    }
+
+    /// Get a lightweight summary of all connected clients.
+    /// 
+    /// Unlike `AccessPoint::scan`, this skips the ARP scan and address
+    /// resolution, so it is cheap enough for a frequently polled status
+    /// endpoint.  Use `AccessPoint::scan` for network addresses.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<Vec<ClientSummary>>` containing the MAC address,
+    /// signal strength, and connection time of each client.
+    pub async fn clients(&mut self) -> ProtonResult<Vec<ClientSummary>> {
+        self.device_manager.clients().await
+    }
+
+    /// Get a list of all connected devices as a JSON array.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<String>` containing the JSON array of devices, if
+    /// the network scan was successful.
+    pub async fn devices_json(&mut self) -> ProtonResult<String> {
+        devices_to_json(&self.scan().await?)
+    }
+
+    /// Count the connected devices on each frequency band.
+    /// 
+    /// Only the `nl80211` station list is queried, since the operating
+    /// frequency of a device does not depend on its network address, so this
+    /// does not scan the network.  Devices whose operating frequency is
+    /// unknown, or outside of the 2.4 GHz and 5 GHz bands, are not counted.
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<(usize, usize)>` containing the number of devices on the
+    /// 2.4 GHz band and on the 5 GHz band, if the stations could be queried.
+    pub async fn clients_by_band(&mut self) -> ProtonResult<(usize, usize)> {
+        let devices = self.device_manager.scan_stations_only().await?;
+
+        let two_four = devices.iter()
+            .filter(|device| BAND_2_4_GHZ_MHZ.contains(&device.frequency_mhz))
+            .count();
+        let five = devices.iter()
+            .filter(|device| BAND_5_GHZ_MHZ.contains(&device.frequency_mhz))
+            .count();
+
+        Ok ((two_four, five))
+    }
+
+    /// Deauthenticate a connected device.
+    /// 
+    /// # Parameters
+    /// - `mac` (`MacAddr`): the MAC address of the device
+    /// - `reason` (`ReasonCode`): the reason given to the device
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the device
+    /// was deauthenticated.
+    pub async fn deauth(&mut self, mac: MacAddr, reason: ReasonCode) -> ProtonResult<()> {
+        self.device_manager.deauth(mac, reason).await
+    }
+
+    /// Deauthenticate all connected devices.
+    /// 
+    /// This is useful right before calling `deactivate` or `delete`.
+    /// 
+    /// # Parameters
+    /// - `reason` (`ReasonCode`): the reason given to the devices
+    /// 
+    /// # Returns
+    /// A `ProtonResult<(usize, Vec<MacAddr>)>` containing the number of
+    /// devices deauthenticated and the MAC addresses of those that could not
+    /// be deauthenticated.
+    pub async fn deauth_all(&mut self, reason: ReasonCode) -> ProtonResult<(usize, Vec<MacAddr>)> {
+        self.device_manager.deauth_all(reason).await
+    }
+
+    /// Recommend the least congested channel for the hotspot.
+    /// 
+    /// This surveys nearby access points and scores each candidate channel
+    /// by the occupancy and signal strength of its neighbors.  On 2.4 GHz,
+    /// only the non-overlapping channels 1, 6, and 11 are considered.  The
+    /// hotspot is left unchanged (see `AccessPoint::auto_channel`).
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<u8>` containing the recommended channel, if the
+    /// site survey was successful.
+    pub async fn recommend_channel(&mut self) -> ProtonResult<u8> {
+        // Survey nearby access points on the configured band
+        // Note: channel numbers are only unique within a band (e.g. 6 GHz
+        //  channel 37 would count as 5 GHz channel 37)
+        let mut aps = self.device_manager.survey().await?;
+        aps.retain(|ap| self.config.band.contains_frequency(ap.frequency_mhz));
+
+        // Determine candidate channels for the configured band
+        let candidates = Self::channels(self.config.band);
+
+        // Pick the least congested channel
+        best_channel(&aps, &candidates)
+            .ok_or(ProtonError::NoCandidateChannel (self.config.band.to_string()))
+    }
+
+    /// Move the hotspot to the least congested channel.
+    /// 
+    /// The channel is selected as in `AccessPoint::recommend_channel`, and
+    /// stored in `config.channel`.  If it differs from the current channel,
+    /// the hotspot is recreated on it, which disconnects clients; if that
+    /// fails, the previous hotspot is restored (see
+    /// `AccessPoint::reload_config`).
+    /// 
+    /// # Parameters
+    /// None.
+    /// 
+    /// # Returns
+    /// A `ProtonResult<u8>` containing the selected channel, if the hotspot
+    /// was moved to it.
+    pub async fn auto_channel(&mut self) -> ProtonResult<u8> {
+        let channel = self.recommend_channel().await?;
+
+        let mut new = self.config.clone();
+        new.channel = Some (channel);
+
+        self.reload_config(new).await?;
+
+        Ok (channel)
+    }
+
+    /// Move the hotspot to another frequency band.
+    /// 
+    /// The hotspot is recreated on the new band, which disconnects clients;
+    /// if that fails, the previous hotspot is restored (see
+    /// `AccessPoint::reload_config`).  A selected channel that is not legal
+    /// on the new band is cleared, leaving the channel up to the driver.
+    /// 
+    /// # Parameters
+    /// - `band` (`Band`): the new frequency band
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the hotspot was moved,
+    /// or `ProtonError::BandNotSupported` if the radio does not support
+    /// `band`.
+    pub async fn set_band(&mut self, band: Band) -> ProtonResult<()> {
+        // Make sure the radio supports the band
+        let supported = match band {
+            Band::TwoFourGhz => self.device_manager.supports_2_4_ghz().await?,
+            Band::FiveGhz => self.device_manager.supports_5_ghz().await?,
+        };
+
+        if !supported {
+            return Err (ProtonError::BandNotSupported (band.to_string()));
+        }
+
+        let mut new = self.config.clone();
+        new.band = band;
+        new.channel = new.channel.filter(|channel| band.valid_channel(*channel));
+
+        self.reload_config(new).await
+    }
+
+    /// Get the candidate channels of a frequency band for `auto_channel`.
+    /// 
+    /// These are a subset of `Band::channels`: the non-overlapping channels
+    /// on 2.4 GHz, and the non-DFS channels on 5 GHz.
+    fn channels(band: Band) -> Vec<u8> {
+        // Channels five apart (1, 6, and 11) do not overlap on 2.4 GHz
+        let step = match band {
+            Band::TwoFourGhz => 5,
+            Band::FiveGhz => 1,
+        };
+
+        band.channels()
+            .iter()
+            .step_by(step)
+            .filter(|channel| !band.dfs_channels().contains(channel))
+            .copied()
+            .collect()
+    }
+
+    /// Set the transmit power of the hotspot.
+    /// 
+    /// Power levels above the regulatory limit of the operating channel are
+    /// rejected before they reach the driver, which rejects any other level
+    /// outside of its supported range (see `DeviceManager::set_tx_power`).
+    /// 
+    /// # Parameters
+    /// - `dbm` (`u8`): the transmit power, in dBm
+    /// 
+    /// # Returns
+    /// A `ProtonResult<()>` indicating whether or not the transmit power
+    /// was set.
+    pub async fn set_tx_power(&mut self, dbm: u8) -> ProtonResult<()> {
+        self.device_manager.set_tx_power(dbm).await
     }
 }
\ No newline at end of file
@@ -4,5 +4,12 @@
 #![deny(missing_docs)]
 
 mod ap;
+mod reload;
+mod settings;
 
 pub use ap::AccessPoint;
+
+pub use reload::{
+    replace_hotspot,
+    Replacement,
+};
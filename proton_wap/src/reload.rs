@@ -0,0 +1,50 @@
+//! Replacement of a hotspot connection that never leaves the access point down.
+
+use proton_err::{
+    ProtonError,
+    ProtonResult,
+};
+
+#[derive(Debug)]
+/// The outcome of replacing a hotspot connection.
+pub enum Replacement<C> {
+    /// The new connection is active, so the previous one can be deleted.
+    Replaced (C),
+
+    /// The new connection failed, with this error, and the previous one was
+    /// reactivated.
+    Restored (ProtonError),
+
+    /// The new connection failed, with this error, and the previous one could
+    /// not be reactivated.  The previous connection is kept, so that it can
+    /// be activated again later.
+    Down (ProtonError),
+}
+
+/// Replace an active hotspot connection, without deleting it first.
+/// 
+/// The previous connection is never deleted here: it is only reactivated if
+/// the new connection fails, so a failed replacement can always be retried.
+/// 
+/// # Parameters
+/// - `previous` (`&C`): the active connection
+/// - `create` (`FnOnce() -> ProtonResult<C>`): creates and activates the new
+///   connection, removing it if it cannot be activated
+/// - `reactivate` (`FnOnce(&C) -> ProtonResult<()>`): reactivates the
+///   previous connection
+/// 
+/// # Returns
+/// A `Replacement<C>` containing the new connection, or the error of the new
+/// connection and whether or not the previous one was restored.
+pub fn replace_hotspot<C, F, R>(previous: &C, create: F, reactivate: R) -> Replacement<C>
+    where F: FnOnce() -> ProtonResult<C>,
+          R: FnOnce(&C) -> ProtonResult<()>,
+{
+    match create() {
+        Ok (connection) => Replacement::Replaced (connection),
+        Err (e) => match reactivate(previous) {
+            Ok (()) => Replacement::Restored (e),
+            Err (_) => Replacement::Down (e),
+        },
+    }
+}
@@ -0,0 +1,163 @@
+//! Direct updates of NetworkManager connection settings over D-Bus.
+//! 
+//! The hotspot API of the `network-manager` crate only takes the SSID,
+//! password, gateway, key management, and band of a hotspot, so settings
+//! beyond those are written to the connection through the settings API of
+//! NetworkManager (`org.freedesktop.NetworkManager.Settings.Connection`).
+
+use std::collections::HashMap;
+
+use zbus::{
+    blocking::Connection as Bus,
+    zvariant::{
+        OwnedObjectPath,
+        OwnedValue,
+        Value,
+    },
+};
+
+use proton_cfg::{
+    Band,
+    Security,
+};
+
+use proton_err::ProtonResult;
+
+/// D-Bus name of NetworkManager.
+const NM_SERVICE: &str = "org.freedesktop.NetworkManager";
+
+/// D-Bus path of the NetworkManager settings.
+const NM_SETTINGS_PATH: &str = "/org/freedesktop/NetworkManager/Settings";
+
+/// D-Bus interface of the NetworkManager settings.
+const NM_SETTINGS_INTERFACE: &str = "org.freedesktop.NetworkManager.Settings";
+
+/// D-Bus interface of a NetworkManager connection profile.
+const NM_CONNECTION_INTERFACE: &str = "org.freedesktop.NetworkManager.Settings.Connection";
+
+/// Name of the wireless setting of a connection.
+const WIRELESS_SETTING: &str = "802-11-wireless";
+
+/// Name of the wireless security setting of a connection.
+const WIRELESS_SECURITY_SETTING: &str = "802-11-wireless-security";
+
+/// Settings of a connection profile, by setting and property name.
+type Settings = HashMap<String, HashMap<String, OwnedValue>>;
+
+/// Set the operating channel of a hotspot connection.
+/// 
+/// The channel is only used the next time the connection is activated, so
+/// an active hotspot must be reactivated to move to the new channel.
+/// 
+/// # Parameters
+/// - `uuid` (`&str`): the UUID of the connection
+/// - `channel` (`u8`): the operating channel
+/// - `band` (`Band`): the frequency band of the channel, which NetworkManager
+///   requires along with a channel
+/// 
+/// # Returns
+/// A `ProtonResult<()>` indicating whether or not the connection was updated.
+pub fn set_channel(uuid: &str, channel: u8, band: Band) -> ProtonResult<()> {
+    update_settings(uuid, |settings| {
+        let wireless = settings.entry(WIRELESS_SETTING.to_string()).or_default();
+        wireless.insert("band".to_string(), Value::from(band.nm_band()).try_into()?);
+        wireless.insert("channel".to_string(), Value::from(u32::from(channel)).try_into()?);
+
+        Ok (())
+    })
+}
+
+/// Set whether or not a hotspot connection hides its SSID.
+/// 
+/// Like the channel, this is only used the next time the connection is
+/// activated.
+/// 
+/// # Parameters
+/// - `uuid` (`&str`): the UUID of the connection
+/// - `hidden` (`bool`): whether or not the SSID is hidden
+/// 
+/// # Returns
+/// A `ProtonResult<()>` indicating whether or not the connection was updated.
+pub fn set_hidden(uuid: &str, hidden: bool) -> ProtonResult<()> {
+    update_settings(uuid, |settings| {
+        let wireless = settings.entry(WIRELESS_SETTING.to_string()).or_default();
+        wireless.insert("hidden".to_string(), Value::from(hidden).try_into()?);
+
+        Ok (())
+    })
+}
+
+/// Set the key management and protected management frames modes of a
+/// hotspot connection.
+/// 
+/// This adds the `802-11-wireless-security` setting if the connection has
+/// none, as is the case of hotspots created without a password.
+/// 
+/// # Parameters
+/// - `uuid` (`&str`): the UUID of the connection
+/// - `security` (`Security`): the security mode of the hotspot
+/// 
+/// # Returns
+/// A `ProtonResult<()>` indicating whether or not the connection was updated.
+pub fn set_security(uuid: &str, security: Security) -> ProtonResult<()> {
+    update_settings(uuid, |settings| {
+        let wireless_security = settings.entry(WIRELESS_SECURITY_SETTING.to_string()).or_default();
+
+        if let Some (key_mgmt) = security.key_mgmt() {
+            wireless_security.insert("key-mgmt".to_string(), Value::from(key_mgmt).try_into()?);
+        }
+        if let Some (pmf) = security.pmf() {
+            wireless_security.insert("pmf".to_string(), Value::from(pmf).try_into()?);
+        }
+
+        Ok (())
+    })
+}
+
+/// Update the settings of a connection profile.
+/// 
+/// The settings are read back and written in full, since NetworkManager
+/// replaces every setting of a connection on update.  Secrets are not read,
+/// so NetworkManager keeps the existing ones (e.g. the pre-shared key).
+/// 
+/// # Parameters
+/// - `uuid` (`&str`): the UUID of the connection
+/// - `update` (`impl FnOnce(&mut Settings) -> ProtonResult<()>`): the
+///   changes to make to the current settings
+/// 
+/// # Returns
+/// A `ProtonResult<()>` indicating whether or not the connection was updated.
+fn update_settings(uuid: &str, update: impl FnOnce(&mut Settings) -> ProtonResult<()>) -> ProtonResult<()> {
+    let bus = Bus::system()?;
+
+    // Find the connection profile
+    let path: OwnedObjectPath = bus.call_method(
+        Some (NM_SERVICE),
+        NM_SETTINGS_PATH,
+        Some (NM_SETTINGS_INTERFACE),
+        "GetConnectionByUuid",
+        &(uuid,),
+    )?.body().deserialize()?;
+
+    // Read its current settings
+    let mut settings: Settings = bus.call_method(
+        Some (NM_SERVICE),
+        &path,
+        Some (NM_CONNECTION_INTERFACE),
+        "GetSettings",
+        &(),
+    )?.body().deserialize()?;
+
+    update(&mut settings)?;
+
+    // Write the settings back
+    bus.call_method(
+        Some (NM_SERVICE),
+        &path,
+        Some (NM_CONNECTION_INTERFACE),
+        "Update",
+        &(settings,),
+    )?;
+
+    Ok (())
+}
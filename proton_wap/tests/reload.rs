@@ -0,0 +1,87 @@
+//! Testing the replacement of a hotspot connection.
+
+use std::cell::Cell;
+
+use proton_err::ProtonError;
+
+use proton_wap::{
+    replace_hotspot,
+    Replacement,
+};
+
+/// A mock hotspot connection, counting its activations.
+struct Hotspot {
+    /// Name of the hotspot.
+    name: &'static str,
+
+    /// Number of times the hotspot was activated.
+    activations: Cell<usize>,
+}
+
+impl Hotspot {
+    /// Construct a mock hotspot that has not been activated.
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            activations: Cell::new(0),
+        }
+    }
+}
+
+#[test]
+fn replace_keeps_previous_until_active() {
+    let previous = Hotspot::new("old");
+
+    let replacement = replace_hotspot(
+        &previous,
+        || Ok (Hotspot::new("new")),
+        |_| panic!("the previous hotspot must not be reactivated"),
+    );
+
+    assert!(matches!(replacement, Replacement::Replaced (Hotspot { name: "new", .. })));
+}
+
+#[test]
+fn restore_previous_on_failure() {
+    let previous = Hotspot::new("old");
+
+    let replacement = replace_hotspot(
+        &previous,
+        || Err (ProtonError::CouldNotActivateHotspot),
+        |hotspot| {
+            hotspot.activations.set(hotspot.activations.get() + 1);
+            Ok (())
+        },
+    );
+
+    assert!(matches!(replacement, Replacement::Restored (ProtonError::CouldNotActivateHotspot)));
+    assert_eq!(previous.activations.get(), 1);
+}
+
+#[test]
+fn keep_previous_when_restore_fails() {
+    let previous = Hotspot::new("old");
+
+    let replacement = replace_hotspot(
+        &previous,
+        || Err (ProtonError::CouldNotActivateHotspot),
+        |_| Err (ProtonError::CouldNotFindWirelessInterface),
+    );
+
+    // The error of the new hotspot is reported, not that of the restore
+    assert!(matches!(replacement, Replacement::Down (ProtonError::CouldNotActivateHotspot)));
+
+    // The previous hotspot still exists, so the restore can be retried
+    let retry = replace_hotspot(
+        &previous,
+        || Err (ProtonError::CouldNotActivateHotspot),
+        |hotspot| {
+            hotspot.activations.set(hotspot.activations.get() + 1);
+            Ok (())
+        },
+    );
+
+    assert!(matches!(retry, Replacement::Restored (_)));
+    assert_eq!(previous.name, "old");
+    assert_eq!(previous.activations.get(), 1);
+}
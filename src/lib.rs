@@ -5,18 +5,63 @@
 
 /// Access point utilities.
 pub mod ap {
-    pub use proton_cfg::HotspotConfig;
+    pub use proton_cfg::{
+        Band,
+        ConfigChange,
+        HotspotConfig,
+        Security,
+    };
     pub use proton_wap::AccessPoint;
 }
 
+/// Blocking ARP scans, for callers without an asynchronous runtime.
+/// 
+/// Each call spawns a runtime of 2 worker threads for the duration of the
+/// scan, so these must not be called from within a Tokio runtime.
+#[cfg(feature = "blocking")]
+pub mod blocking {
+    pub use proton_arp::{
+        resolve_blocking,
+        scan_blocking,
+    };
+}
+
 /// CIDR network range structure.
 pub mod cidr {
     pub use cidr::Ipv4Cidr;
+
+    pub use proton_arp::Ipv4CidrExt;
+}
+
+/// Frame debugging utilities.
+pub mod debug {
+    pub use proton_nif::{
+        describe_frame,
+        hex_dump,
+    };
 }
 
 /// Device management functionality.
 pub mod device {
-    pub use proton_dev::Device;
+    pub use proton_dev::{
+        Accounting,
+        ApInfo,
+        ApSecurity,
+        ClientSummary,
+        Counters,
+        Device,
+        devices_to_csv,
+        devices_to_json,
+        glob_match,
+        HostnamePolicy,
+        Presence,
+        PresenceTracker,
+        ReasonCode,
+        SignalHistory,
+    };
+
+    #[cfg(feature = "metrics")]
+    pub use proton_dev::Metrics;
 }
 
 /// Error handling functionality.
@@ -27,7 +72,10 @@ pub mod error {
     };
 }
 
-/// Native MAC address structure.
+/// Native MAC address structures.
 pub mod mac {
-    pub use proton_mac::MacAddr;
+    pub use proton_mac::{
+        MacAddr,
+        MacPrefix,
+    };
 }
\ No newline at end of file